@@ -0,0 +1,42 @@
+#!/usr/bin/env python3
+"""Downloads the reference clips bundled for model benchmarks into
+src-tauri/resources/benchmark and writes their clips.json.
+
+Only add recordings whose license allows redistribution, and record it:
+
+    python3 scripts/fetch_benchmark_clips.py
+"""
+
+import json
+import pathlib
+import urllib.request
+
+BENCHMARK_DIR = pathlib.Path(__file__).resolve().parent.parent / "src-tauri/resources/benchmark"
+
+CLIPS = [
+    {
+        "file": "jfk.wav",
+        "url": "https://github.com/ggml-org/whisper.cpp/raw/master/samples/jfk.wav",
+        "transcript": "And so, my fellow Americans, ask not what your country can do for you, ask what you can do for your country.",
+        "language": "en",
+        "source": "John F. Kennedy, inaugural address, 1961, via the whisper.cpp samples",
+        "license": "Public domain",
+    },
+]
+
+
+def main():
+    BENCHMARK_DIR.mkdir(parents=True, exist_ok=True)
+    manifest = {"clips": []}
+    for clip in CLIPS:
+        print(f"{clip['file']}: {clip['url']}", flush=True)
+        with urllib.request.urlopen(clip["url"]) as response:
+            (BENCHMARK_DIR / clip["file"]).write_bytes(response.read())
+        manifest["clips"].append({key: value for key, value in clip.items() if key != "url"})
+    (BENCHMARK_DIR / "clips.json").write_text(
+        json.dumps(manifest, indent=2, ensure_ascii=False) + "\n"
+    )
+
+
+if __name__ == "__main__":
+    main()
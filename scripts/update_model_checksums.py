@@ -0,0 +1,37 @@
+#!/usr/bin/env python3
+"""Downloads every model in src-tauri/resources/models.json and records its
+SHA-256 and exact size, which the app checks downloads against.
+
+Run after adding a model or changing a URL:
+
+    python3 scripts/update_model_checksums.py
+"""
+
+import hashlib
+import json
+import pathlib
+import urllib.request
+
+MANIFEST = pathlib.Path(__file__).resolve().parent.parent / "src-tauri/resources/models.json"
+
+
+def digest(url):
+    sha256 = hashlib.sha256()
+    size = 0
+    with urllib.request.urlopen(url) as response:
+        while chunk := response.read(1 << 20):
+            sha256.update(chunk)
+            size += len(chunk)
+    return sha256.hexdigest(), size
+
+
+def main():
+    manifest = json.loads(MANIFEST.read_text())
+    for model in manifest["models"]:
+        print(f"{model['id']}: {model['url']}", flush=True)
+        model["sha256"], model["size_bytes"] = digest(model["url"])
+    MANIFEST.write_text(json.dumps(manifest, indent=2, ensure_ascii=False) + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,33 +1,36 @@
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
-use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
-use crate::managers::audio::AudioRecordingManager;
-use crate::managers::history::HistoryManager;
+use crate::audio_toolkit::diarization::{render_turns, speaker_count};
+use crate::audio_toolkit::{constants::WHISPER_SAMPLE_RATE, FrameSink};
+use crate::backends::{self, BackendTranscription, LiveTranscription, TranscriptionRequest};
+use crate::cancellation::CancellationToken;
+use crate::helpers::active_window::active_window;
+use crate::managers::account::AccountManager;
+use crate::managers::audio::{AudioRecordingManager, RecordedAudio};
+use crate::managers::history::{HistoryManager, TranscriptionRecord};
 use crate::managers::transcription::TranscriptionManager;
-use crate::overlay::{show_recording_overlay, show_transcribing_overlay};
-use crate::secure_store;
-use crate::settings::{get_settings, AppSettings, TranscriptionProvider, UsageMode};
-use crate::tray::{change_tray_icon, TrayIconState};
-use crate::utils;
-use async_openai::types::{
-    ChatCompletionRequestMessage, ChatCompletionRequestUserMessageArgs,
-    CreateChatCompletionRequestArgs,
+use crate::managers::transcription_queue::{
+    JobOutcome, JobStatus, TranscriptionJob, TranscriptionQueue,
+};
+use crate::managers::usage::UsageManager;
+use crate::overlay::{emit_interim_transcript, show_recording_overlay, show_transcribing_overlay};
+use crate::post_process::{edit_selection, run_pipeline, PipelineInput, PromptContext};
+use crate::settings::{
+    get_settings, AppProfile, AppSettings, TranscriptionProvider, UsageMode,
+    EDIT_SELECTION_BINDING_ID,
 };
-use ferrous_opencc::{config::BuiltinConfig, OpenCC};
-use base64::Engine;
+use crate::tray::{change_tray_icon, TrayIconState};
 use log::{debug, error, warn};
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::collections::HashMap;
-use std::io::Cursor;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
+use tauri::async_runtime::JoinHandle;
 use tauri::AppHandle;
-use tauri::Emitter;
 use tauri::Manager;
-use reqwest::StatusCode;
 
 // Shortcut Action Trait
-pub trait ShortcutAction: Send + Sync {
+pub trait ShortcutAction: Any + Send + Sync {
     fn start(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
     fn stop(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
 }
@@ -35,568 +38,388 @@ pub trait ShortcutAction: Send + Sync {
 // Transcribe Action
 struct TranscribeAction;
 
-async fn maybe_post_process_transcription(
-    settings: &AppSettings,
-    transcription: &str,
-) -> Option<String> {
-    if !settings.post_process_enabled {
-        return None;
-    }
+// Edit Selection Action
+struct EditSelectionAction;
 
-    let provider = match settings.active_post_process_provider().cloned() {
-        Some(provider) => provider,
-        None => {
-            debug!("Post-processing enabled but no provider is selected");
-            return None;
-        }
-    };
-
-    let model = settings
-        .post_process_models
-        .get(&provider.id)
-        .cloned()
-        .unwrap_or_default();
+/// What a recording is for: text to paste, or an instruction for
+/// rewriting the selected text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RecordingMode {
+    Dictation,
+    EditSelection,
+}
 
-    if model.trim().is_empty() {
-        debug!(
-            "Post-processing skipped because provider '{}' has no model configured",
-            provider.id
-        );
+/// Live sessions streaming the current recording, by binding id
+static LIVE_TRANSCRIPTIONS: Lazy<Mutex<HashMap<String, LiveTranscription>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Prompt variables and the matched app profile for each binding's
+/// recording, read in the background so neither a simulated copy nor the
+/// window lookup delays the recording
+static PROMPT_CONTEXTS: Lazy<
+    Mutex<HashMap<String, JoinHandle<(PromptContext, Option<AppProfile>)>>>,
+> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The app profile for the focused application, if any are set up. Finds
+/// nothing where the focused window can't be read, e.g. under Wayland
+/// without XWayland.
+fn focused_app_profile(settings: &AppSettings) -> Option<AppProfile> {
+    if settings.app_profiles.is_empty() {
         return None;
     }
-
-    let selected_prompt_id = match &settings.post_process_selected_prompt_id {
-        Some(id) => id.clone(),
-        None => {
-            debug!("Post-processing skipped because no prompt is selected");
-            return None;
-        }
-    };
-
-    let prompt = match settings
-        .post_process_prompts
-        .iter()
-        .find(|prompt| prompt.id == selected_prompt_id)
-    {
-        Some(prompt) => prompt.prompt.clone(),
-        None => {
-            debug!(
-                "Post-processing skipped because prompt '{}' was not found",
-                selected_prompt_id
-            );
-            return None;
-        }
+    let Some(window) = active_window() else {
+        debug!("Focused application unknown; app profiles not applied");
+        return None;
     };
+    let profile = settings.app_profile_for(&window.names()).cloned();
+    debug!(
+        "Focused application {:?} (process {:?}) matches app profile {:?}",
+        window.app_name,
+        window.process_name,
+        profile.as_ref().map(|profile| &profile.name)
+    );
+    profile
+}
 
-    if prompt.trim().is_empty() {
-        debug!("Post-processing skipped because the selected prompt is empty");
-        return None;
+/// Settings for a recording: the binding's profile, then the app profile
+/// that matched when it started.
+fn recording_settings(
+    app: &AppHandle,
+    binding_id: &str,
+    app_profile: Option<&AppProfile>,
+) -> AppSettings {
+    let settings = get_settings(app).for_binding(binding_id);
+    match app_profile {
+        Some(app_profile) => settings.with_app_profile(app_profile),
+        None => settings,
     }
+}
 
-    let api_key = settings
-        .post_process_api_keys
-        .get(&provider.id)
-        .cloned()
-        .unwrap_or_default();
+/// Closes the live sessions of cancelled recordings.
+pub fn cancel_live_transcriptions() {
+    let sessions: Vec<_> = LIVE_TRANSCRIPTIONS.lock().unwrap().drain().collect();
+    for (binding_id, live) in sessions {
+        debug!(
+            "Closing live transcription via {} for binding {}",
+            live.backend_id(),
+            binding_id
+        );
+    }
+}
 
-    debug!(
-        "Starting LLM post-processing with provider '{}' (model: {})",
-        provider.id, model
-    );
+/// Opens a live session for the recording about to start, if the provider
+/// streams, and returns the sink that feeds it frames.
+fn start_live_transcription(
+    app: &AppHandle,
+    settings: &AppSettings,
+    binding_id: &str,
+) -> Option<FrameSink> {
+    let app_handle = app.clone();
+    let (frames, live) = backends::start_live_transcription(settings, move |text| {
+        emit_interim_transcript(&app_handle, text)
+    })?;
+    debug!("Streaming recording to {}", live.backend_id());
+    LIVE_TRANSCRIPTIONS
+        .lock()
+        .unwrap()
+        .insert(binding_id.to_string(), live);
+
+    Some(Box::new(move |frame: &[f32]| {
+        // Fails only once the session has ended, which the stop path handles
+        let _ = frames.send(frame.to_vec());
+    }))
+}
 
-    // Replace ${output} variable in the prompt with the actual text
-    let processed_prompt = prompt.replace("${output}", transcription);
-    debug!("Processed prompt length: {} chars", processed_prompt.len());
+/// A stopped recording and what was captured alongside it.
+struct Recording {
+    samples: Vec<f32>,
+    /// Share of the recording the VAD kept as speech, if known
+    speech_ratio: Option<f32>,
+    live: Option<LiveTranscription>,
+    context: PromptContext,
+    app_profile: Option<AppProfile>,
+}
 
-    // Create OpenAI-compatible client
-    let client = match crate::llm_client::create_client(&provider, api_key) {
-        Ok(client) => client,
-        Err(e) => {
-            error!("Failed to create LLM client: {}", e);
-            return None;
+/// Runs the recording through the transcription backends and records the
+/// usage. Returns the outcome to report instead when it failed or was
+/// cancelled.
+async fn transcribe_recording(
+    app: &AppHandle,
+    settings: &AppSettings,
+    samples: &[f32],
+    live: Option<LiveTranscription>,
+    cancel: &CancellationToken,
+) -> Result<BackendTranscription, JobOutcome> {
+    let transcription_time = Instant::now();
+    // The session opened before the app profile was matched, so it may
+    // stream with a different model, language or keyterms
+    let live = live.filter(|live| {
+        let matches = live.matches(settings);
+        if !matches {
+            debug!("App profile changed the live session's settings; transcribing the recording");
         }
+        matches
+    });
+    let chain = backends::build_chain(app, settings);
+    let request = TranscriptionRequest {
+        samples,
+        settings,
+        cancel,
     };
-
-    // Build the chat completion request
-    let message = match ChatCompletionRequestUserMessageArgs::default()
-        .content(processed_prompt)
-        .build()
-    {
-        Ok(msg) => ChatCompletionRequestMessage::User(msg),
-        Err(e) => {
-            error!("Failed to build chat message: {}", e);
-            return None;
-        }
+    let transcription_result = match live {
+        Some(live) => backends::finish_live_with_fallback(live, &chain, request).await,
+        None => backends::transcribe_with_fallback(&chain, request).await,
     };
 
-    let request = match CreateChatCompletionRequestArgs::default()
-        .model(&model)
-        .messages(vec![message])
-        .build()
-    {
-        Ok(req) => req,
-        Err(e) => {
-            error!("Failed to build chat completion request: {}", e);
-            return None;
+    // The cancel already reset the overlay and tray, so just drop the result
+    if cancel.is_cancelled() {
+        return Err(JobOutcome::Cancelled);
+    }
+
+    let result = match transcription_result {
+        Ok(result) => result,
+        Err(err) => {
+            warn!("Transcription failed: {}", err);
+            return Err(JobOutcome::Failed(err));
         }
     };
+    debug!(
+        "Transcription completed via {} in {:?} ({} chars, {} words, confidence {:?})",
+        result.backend,
+        transcription_time.elapsed(),
+        result.transcript.text.len(),
+        result.transcript.words.len(),
+        result.transcript.confidence
+    );
 
-    // Send the request
-    match client.chat().create(request).await {
-        Ok(response) => {
-            if let Some(choice) = response.choices.first() {
-                if let Some(content) = &choice.message.content {
-                    debug!(
-                        "LLM post-processing succeeded for provider '{}'. Output length: {} chars",
-                        provider.id,
-                        content.len()
-                    );
-                    return Some(content.clone());
-                }
-            }
-            error!("LLM API response has no content");
-            None
-        }
-        Err(e) => {
-            error!(
-                "LLM post-processing failed for provider '{}': {}. Falling back to original transcription.",
-                provider.id,
-                e
-            );
-            None
-        }
+    // Relayed and credit-paid requests are billed through the account, so
+    // they are recorded without a cost estimate
+    let direct = settings.usage_mode == UsageMode::OwnKeys && !settings.uses_backend_relay();
+    let usage_manager = app.state::<Arc<UsageManager>>();
+    usage_manager.record_transcription(
+        &result.backend,
+        samples.len() as f64 / WHISPER_SAMPLE_RATE as f64,
+        direct,
+    );
+    if result.remote && settings.usage_mode == UsageMode::Credits {
+        // Credits were spent, so refresh the remaining minutes
+        let account_manager = Arc::clone(&app.state::<Arc<AccountManager>>());
+        tauri::async_runtime::spawn(async move {
+            account_manager.sync().await;
+        });
     }
+    Ok(result)
 }
 
-async fn maybe_convert_chinese_variant(
-    settings: &AppSettings,
-    transcription: &str,
-) -> Option<String> {
-    // Check if language is set to Simplified or Traditional Chinese
-    let is_simplified = settings.selected_language == "zh-Hans";
-    let is_traditional = settings.selected_language == "zh-Hant";
-
-    if !is_simplified && !is_traditional {
-        debug!("selected_language is not Simplified or Traditional Chinese; skipping translation");
-        return None;
-    }
+/// Transcribes and post-processes one queued recording and saves it to
+/// history. Delivery is left to the queue so pastes stay in recording order.
+async fn run_transcription_job(
+    app: &AppHandle,
+    queue: &Arc<TranscriptionQueue>,
+    job: &TranscriptionJob,
+    binding_id: &str,
+    recording: Recording,
+) -> JobOutcome {
+    let Recording {
+        samples,
+        speech_ratio,
+        live,
+        context,
+        app_profile,
+    } = recording;
+
+    // Held until post-processing is done, bounding how many jobs run at once
+    let _slot = match queue.acquire_slot(job).await {
+        Ok(slot) => slot,
+        Err(_) => return JobOutcome::Cancelled,
+    };
 
-    debug!(
-        "Starting Chinese translation using OpenCC for language: {}",
-        settings.selected_language
-    );
+    let cancel = &job.cancel;
+    let settings = recording_settings(app, binding_id, app_profile.as_ref());
 
-    // Use OpenCC to convert based on selected language
-    let config = if is_simplified {
-        // Convert Traditional Chinese to Simplified Chinese
-        BuiltinConfig::Tw2sp
+    let BackendTranscription {
+        transcript,
+        backend,
+        ..
+    } = match transcribe_recording(app, &settings, &samples, live, cancel).await {
+        Ok(result) => result,
+        Err(outcome) => return outcome,
+    };
+    let segments = transcript.speaker_segments();
+    let translated = transcript.translated;
+    let source_transcription = transcript.source_text;
+    let transcription = transcript.text;
+    // Paste "Speaker N:" turns when more than one voice was heard; history
+    // keeps the plain text alongside the segments
+    let labelled = if settings.speaker_labels && speaker_count(&segments) > 1 {
+        Some(render_turns(&segments))
     } else {
-        // Convert Simplified Chinese to Traditional Chinese
-        BuiltinConfig::S2twp
+        None
     };
 
-    match OpenCC::from_config(config) {
-        Ok(converter) => {
-            let converted = converter.convert(transcription);
-            debug!(
-                "OpenCC translation completed. Input length: {}, Output length: {}",
-                transcription.len(),
-                converted.len()
-            );
-            Some(converted)
-        }
-        Err(e) => {
-            error!("Failed to initialize OpenCC converter: {}. Falling back to original transcription.", e);
-            None
-        }
+    let usage_manager = Arc::clone(&app.state::<Arc<UsageManager>>());
+    queue.set_status(job, JobStatus::PostProcessing);
+    let source_text = labelled.as_deref().unwrap_or(&transcription);
+    let output = run_pipeline(PipelineInput {
+        settings: &settings,
+        text: source_text,
+        speech_ratio,
+        translated,
+        cancel,
+        usage_manager: &usage_manager,
+        context: &context,
+    })
+    .await;
+
+    if cancel.is_cancelled() {
+        return JobOutcome::Cancelled;
     }
-}
-
-async fn finalize_transcription(
-    settings: &AppSettings,
-    transcription: &str,
-) -> (String, Option<String>, Option<String>) {
-    let mut final_text = transcription.to_string();
-    let mut post_processed_text: Option<String> = None;
-    let mut post_process_prompt: Option<String> = None;
-
-    if let Some(converted_text) = maybe_convert_chinese_variant(settings, transcription).await {
-        final_text = converted_text.clone();
-        post_processed_text = Some(converted_text);
-    } else if let Some(processed_text) =
-        maybe_post_process_transcription(settings, transcription).await
-    {
-        final_text = processed_text.clone();
-        post_processed_text = Some(processed_text);
-
-        if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
-            if let Some(prompt) = settings
-                .post_process_prompts
-                .iter()
-                .find(|p| &p.id == prompt_id)
-            {
-                post_process_prompt = Some(prompt.prompt.clone());
-            }
-        }
+    if output.text.trim().is_empty() {
+        return JobOutcome::Empty;
     }
 
-    (final_text, post_processed_text, post_process_prompt)
-}
-
-#[derive(Deserialize)]
-struct RemoteTranscriptionResponse {
-    text: Option<String>,
-    success: Option<bool>,
-    error: Option<String>,
-}
-
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-struct DeepgramTranscriptionRequest {
-    audio_blob: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    api_key: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    model: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    language: Option<String>,
-}
-
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-struct UnifiedTranscriptionRequest {
-    audio: String,
-    provider: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    model: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    language: Option<String>,
-}
-
-fn encode_audio_to_wav_base64(samples: &[f32]) -> Result<String, String> {
-    let bytes = encode_audio_to_wav_bytes(samples)?;
-    let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
-    Ok(format!("data:audio/wav;base64,{}", encoded))
-}
-
-fn encode_audio_to_wav_bytes(samples: &[f32]) -> Result<Vec<u8>, String> {
-    let mut cursor = Cursor::new(Vec::new());
-    let spec = hound::WavSpec {
-        channels: 1,
-        sample_rate: WHISPER_SAMPLE_RATE,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
+    let final_text = output.text.clone();
+    let post_processed_text = (output.text != source_text).then_some(output.text);
+    let translation_language = (translated || output.translation.is_some())
+        .then(|| settings.translation_target().map(str::to_string))
+        .flatten();
+    // When the engine translated, history keeps what was said as the
+    // transcription and the engine's English as the translation
+    let (transcription_text, translation) = match source_transcription {
+        Some(source) => (source, Some(transcription)),
+        None => (transcription, output.translation),
     };
 
-    {
-        let mut writer = hound::WavWriter::new(&mut cursor, spec)
-            .map_err(|e| format!("Failed to create WAV writer: {}", e))?;
-        for sample in samples {
-            let clamped = (sample * i16::MAX as f32)
-                .clamp(i16::MIN as f32, i16::MAX as f32) as i16;
-            writer
-                .write_sample(clamped)
-                .map_err(|e| format!("Failed to write WAV sample: {}", e))?;
+    // Save to history with post-processed text and prompt
+    let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = hm
+            .save_transcription(
+                samples,
+                TranscriptionRecord {
+                    transcription_text,
+                    post_processed_text,
+                    post_process_prompt: output.post_process_prompt,
+                    backend: Some(backend),
+                    speaker_segments: segments,
+                    translation,
+                    translation_language,
+                    pipeline_steps: output.steps,
+                    edited_selection: None,
+                },
+            )
+            .await
+        {
+            error!("Failed to save transcription to history: {}", e);
         }
-        writer
-            .finalize()
-            .map_err(|e| format!("Failed to finalize WAV buffer: {}", e))?;
-    }
+    });
 
-    Ok(cursor.into_inner())
-}
-
-fn emit_overlay_error(app: &AppHandle, message: &str) {
-    if let Some(overlay) = app.get_webview_window("recording_overlay") {
-        let _ = overlay.emit("transcription-error", message);
+    JobOutcome::Text {
+        text: final_text,
+        paste_method: settings.paste_method,
     }
 }
 
-async fn transcribe_remote(settings: &AppSettings, samples: &[f32]) -> Result<String, String> {
-    let base_url = settings
-        .api_base_url
-        .as_ref()
-        .map(|url| url.trim_end_matches('/'))
-        .unwrap_or("");
-    let can_use_direct_deepgram =
-        settings.provider == TranscriptionProvider::Deepgram && secure_store::fetch_api_key("deepgram").is_some();
-    if base_url.is_empty() && !can_use_direct_deepgram {
-        return Err(
-            "Remote transcription selected but no API base URL is configured in settings"
-                .to_string(),
-        );
-    }
-
-    let audio = encode_audio_to_wav_base64(samples)?;
-
-    let language = if settings.selected_language == "auto" {
-        None
-    } else {
-        Some(settings.selected_language.clone())
+/// Transcribes a spoken instruction and applies it to the text that was
+/// selected when the recording started. The edited text replaces the
+/// selection when the queue pastes it.
+async fn run_edit_job(
+    app: &AppHandle,
+    queue: &Arc<TranscriptionQueue>,
+    job: &TranscriptionJob,
+    binding_id: &str,
+    recording: Recording,
+) -> JobOutcome {
+    let Recording {
+        samples,
+        live,
+        context,
+        app_profile,
+        ..
+    } = recording;
+    let Some(selection) = context.selection.clone().filter(|text| !text.is_empty()) else {
+        return JobOutcome::Failed("Select some text before giving an edit instruction".into());
     };
 
-    let model = match settings.provider {
-        TranscriptionProvider::Deepgram => Some(deepgram_model_from_settings(settings)),
-        _ if settings.selected_model.is_empty() => None,
-        _ => Some(settings.selected_model.clone()),
+    let _slot = match queue.acquire_slot(job).await {
+        Ok(slot) => slot,
+        Err(_) => return JobOutcome::Cancelled,
     };
 
-    let (endpoint, body, provider_id) = match settings.provider {
-        TranscriptionProvider::Local => {
-            return Err("Remote transcription requested while provider is set to local".to_string())
-        }
-        TranscriptionProvider::Deepgram => {
-            // If no backend is configured, try direct Deepgram API using the user's key
-            if base_url.is_empty() {
-                let api_key = if settings.use_secure_key_storage {
-                    secure_store::fetch_api_key("deepgram")
-                } else {
-                    settings
-                        .deepgram_api_key
-                        .as_ref()
-                        .filter(|k| !k.trim().is_empty())
-                        .cloned()
-                }
-                .ok_or_else(|| {
-                    "Deepgram provider requires a backend base URL or a stored Deepgram API key"
-                        .to_string()
-                })?;
-
-                return transcribe_deepgram_direct(
-                    samples,
-                    api_key,
-                    model.clone(),
-                    language.clone(),
-                )
-                .await;
-            }
-
-            let api_key = if settings.usage_mode == UsageMode::OwnKeys {
-                if settings.use_secure_key_storage {
-                    secure_store::fetch_api_key("deepgram")
-                } else {
-                    settings
-                        .deepgram_api_key
-                        .as_ref()
-                        .filter(|k| !k.trim().is_empty())
-                        .cloned()
-                }
-            } else {
-                None
-            };
+    let cancel = &job.cancel;
+    let settings = recording_settings(app, binding_id, app_profile.as_ref());
 
-            (
-                format!("{}/api/transcribe/deepgram", base_url),
-                serde_json::to_value(DeepgramTranscriptionRequest {
-                    audio_blob: audio,
-                    api_key,
-                    model: model.clone(),
-                    language,
-                })
-                .map_err(|e| format!("Failed to serialize Deepgram request: {}", e))?,
-                "deepgram".to_string(),
-            )
-        }
-        TranscriptionProvider::Openai => (
-            format!("{}/api/transcribe", base_url),
-            serde_json::to_value(UnifiedTranscriptionRequest {
-                audio,
-                provider: "openai".to_string(),
-                model,
-                language,
-            })
-            .map_err(|e| format!("Failed to serialize unified request: {}", e))?,
-            "openai".to_string(),
-        ),
+    let BackendTranscription {
+        transcript,
+        backend,
+        ..
+    } = match transcribe_recording(app, &settings, &samples, live, cancel).await {
+        Ok(result) => result,
+        Err(outcome) => return outcome,
     };
-
-    debug!(
-        "Sending remote transcription request to {} for provider '{}'",
-        endpoint, provider_id
-    );
-
-    let client = reqwest::Client::new();
-    let mut builder = client.post(endpoint).json(&body);
-
-    if let Some(token) = settings.auth_token.as_ref() {
-        if !token.trim().is_empty() {
-            builder = builder.bearer_auth(token.trim());
-        }
+    let instruction = transcript.text.trim().to_string();
+    if instruction.is_empty() {
+        return JobOutcome::Empty;
     }
 
-    let response = builder
-        .send()
-        .await
-        .map_err(|e| format!("Remote transcription request failed: {}", e))?;
-
-    let status = response.status();
-    let parsed: RemoteTranscriptionResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse remote transcription response: {}", e))?;
-
-    if parsed.success == Some(false) {
-        if let Some(error) = parsed.error {
-            return Err(error);
-        }
+    queue.set_status(job, JobStatus::PostProcessing);
+    let usage_manager = Arc::clone(&app.state::<Arc<UsageManager>>());
+    let edited = edit_selection(&settings, &instruction, &context, cancel, &usage_manager).await;
+    if cancel.is_cancelled() {
+        return JobOutcome::Cancelled;
     }
-
-    if !status.is_success() {
-        return Err(
-            parsed
-                .error
-                .unwrap_or_else(|| format!("Remote transcription failed with status {}", status)),
+    let Some(edited) = edited else {
+        return JobOutcome::Failed(
+            "Could not edit the selection; check the post-processing provider".into(),
         );
-    }
-
-    parsed
-        .text
-        .ok_or_else(|| "Remote transcription returned no text".to_string())
-}
-
-fn extract_deepgram_transcript(json: &serde_json::Value) -> Option<String> {
-    json.get("results")
-        .and_then(|r| r.get("channels"))
-        .and_then(|c| c.get(0))
-        .and_then(|ch| ch.get("alternatives"))
-        .and_then(|alts| alts.get(0))
-        .and_then(|alt| alt.get("transcript"))
-        .and_then(|t| t.as_str())
-        .map(|s| s.to_string())
-}
-
-fn extract_deepgram_duration(json: &serde_json::Value) -> Option<f64> {
-    json.get("results")
-        .and_then(|r| r.get("channels"))
-        .and_then(|c| c.get(0))
-        .and_then(|ch| ch.get("alternatives"))
-        .and_then(|alts| alts.get(0))
-        .and_then(|alt| alt.get("words"))
-        .and_then(|words| words.as_array())
-        .and_then(|words| words.last())
-        .and_then(|word| word.get("end"))
-        .and_then(|end| end.as_f64())
-}
-
-async fn transcribe_deepgram_direct(
-    samples: &[f32],
-    api_key: String,
-    model: Option<String>,
-    language: Option<String>,
-) -> Result<String, String> {
-    let audio_bytes = encode_audio_to_wav_bytes(samples)?;
-    let client = reqwest::Client::new();
-
-    // Prefer provided model; default to nova-3, but fall back to nova-2 on 403
-    let primary_model = model.unwrap_or_else(|| "nova-3".to_string());
-    let mut models_to_try = vec![primary_model.clone()];
-    if primary_model.starts_with("nova-3") {
-        models_to_try.push("nova-2".to_string());
-    }
-
-    let mut last_error = None;
-
-    for model_name in models_to_try {
-        let mut url = reqwest::Url::parse("https://api.deepgram.com/v1/listen")
-            .map_err(|e| format!("Failed to parse Deepgram URL: {}", e))?;
-        {
-            let mut pairs = url.query_pairs_mut();
-            pairs.append_pair("smart_format", "true");
-            if let Some(lang) = language.as_ref() {
-                pairs.append_pair("language", lang);
-            }
-            pairs.append_pair("model", &model_name);
-        }
-
-        let response = client
-            .post(url.clone())
-            .header("Authorization", format!("Token {}", api_key))
-            .header("Content-Type", "audio/wav")
-            .body(audio_bytes.clone())
-            .send()
-            .await
-            .map_err(|e| format!("Deepgram request failed: {}", e))?;
-
-        let status = response.status();
-        let text_body = response
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read Deepgram response: {}", e))?;
-
-        let json: serde_json::Value = serde_json::from_str(&text_body)
-            .map_err(|e| format!("Failed to parse Deepgram response JSON: {}", e))?;
-
-        if status.is_success() {
-            let transcript = extract_deepgram_transcript(&json)
-                .ok_or_else(|| "Deepgram response missing transcript".to_string())?;
-
-            debug!(
-                "Deepgram direct transcript length: {}, duration: {:?} (model: {})",
-                transcript.len(),
-                extract_deepgram_duration(&json),
-                model_name
-            );
+    };
 
-            return Ok(transcript);
+    let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
+    let record = TranscriptionRecord {
+        transcription_text: instruction,
+        post_processed_text: Some(edited.clone()),
+        post_process_prompt: Some(settings.edit_selection_prompt.clone()),
+        backend: Some(backend),
+        speaker_segments: Vec::new(),
+        translation: None,
+        translation_language: None,
+        pipeline_steps: Vec::new(),
+        edited_selection: Some(selection),
+    };
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = hm.save_transcription(samples, record).await {
+            error!("Failed to save edit to history: {}", e);
         }
+    });
 
-        let err_msg = json
-            .get("err_msg")
-            .or_else(|| json.get("error"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown Deepgram error")
-            .to_string();
-
-        if status == StatusCode::FORBIDDEN && model_name.starts_with("nova-3") {
-            warn!(
-                "Deepgram denied access to model '{}', retrying with nova-2",
-                model_name
-            );
-            last_error = Some(format!(
-                "Deepgram transcription failed ({}): {}",
-                status, err_msg
-            ));
-            continue;
-        } else {
-            return Err(format!(
-                "Deepgram transcription failed ({}): {}",
-                status, err_msg
-            ));
-        }
+    JobOutcome::Text {
+        text: edited,
+        paste_method: settings.paste_method,
     }
-
-    Err(last_error.unwrap_or_else(|| "Deepgram transcription failed".to_string()))
 }
 
-fn deepgram_model_from_settings(settings: &AppSettings) -> String {
-    let selected = settings.deepgram_model.trim();
-    if selected.is_empty() {
-        return "nova-3".to_string();
-    }
-    let lowered = selected.to_lowercase();
+fn start_recording(app: &AppHandle, binding_id: &str, mode: RecordingMode) {
+    let start_time = Instant::now();
+    debug!("Starting {:?} recording for binding: {}", mode, binding_id);
 
-    if lowered.starts_with("nova") || lowered.starts_with("general") {
-        selected.to_string()
-    } else {
-        warn!(
-            "Selected model '{}' is not a Deepgram model; defaulting to nova-3",
-            selected
-        );
-        "nova-3".to_string()
-    }
-}
-
-impl ShortcutAction for TranscribeAction {
-    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
-        let start_time = Instant::now();
-        debug!("TranscribeAction::start called for binding: {}", binding_id);
+    // The app profile is matched off this thread, so the recording starts
+    // with the binding's settings
+    let settings = get_settings(app).for_binding(binding_id);
 
-        let tm = app.state::<Arc<TranscriptionManager>>();
-        let settings = get_settings(app);
+    let binding_id = binding_id.to_string();
+    // Read while the target application still has focus, alongside the
+    // recording; the stop path waits for it
+    let capture_app = app.clone();
+    let capture_binding_id = binding_id.clone();
+    let context = tauri::async_runtime::spawn_blocking(move || {
+        // Matched once, so the whole recording follows the app it started in
+        let app_profile = focused_app_profile(&get_settings(&capture_app));
+        let settings = recording_settings(&capture_app, &capture_binding_id, app_profile.as_ref());
 
         // Load model in the background only for local provider
         if settings.provider == TranscriptionProvider::Local {
-            tm.initiate_model_load();
+            let tm = capture_app.state::<Arc<TranscriptionManager>>();
+            tm.initiate_model_load(&settings.selected_model);
         } else {
             debug!(
                 "Skipping local model preload because provider is {:?}",
@@ -604,182 +427,163 @@ impl ShortcutAction for TranscribeAction {
             );
         }
 
-        let binding_id = binding_id.to_string();
-        change_tray_icon(app, TrayIconState::Recording);
-        show_recording_overlay(app);
-
-        let rm = app.state::<Arc<AudioRecordingManager>>();
-
-        // Get the microphone mode to determine audio feedback timing
-        let is_always_on = settings.always_on_microphone;
-        debug!("Microphone mode - always_on: {}", is_always_on);
+        let context = match mode {
+            RecordingMode::Dictation => PromptContext::capture(&capture_app, &settings),
+            RecordingMode::EditSelection => {
+                PromptContext::capture_for_edit(&capture_app, &settings)
+            }
+        };
+        (context, app_profile)
+    });
+    PROMPT_CONTEXTS
+        .lock()
+        .unwrap()
+        .insert(binding_id.clone(), context);
+    // Closes any session a previous recording left behind
+    LIVE_TRANSCRIPTIONS.lock().unwrap().remove(&binding_id);
+    let frame_sink = start_live_transcription(app, &settings, &binding_id);
+
+    change_tray_icon(app, TrayIconState::Recording);
+    show_recording_overlay(app);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+
+    // Get the microphone mode to determine audio feedback timing
+    let is_always_on = settings.always_on_microphone;
+    debug!("Microphone mode - always_on: {}", is_always_on);
+
+    if is_always_on {
+        // Always-on mode: Play audio feedback immediately, then apply mute after sound finishes
+        debug!("Always-on mode: Playing audio feedback immediately");
+        let rm_clone = Arc::clone(&rm);
+        let app_clone = app.clone();
+        // The blocking helper exits immediately if audio feedback is disabled,
+        // so we can always reuse this thread to ensure mute happens right after playback.
+        std::thread::spawn(move || {
+            play_feedback_sound_blocking(&app_clone, SoundType::Start);
+            rm_clone.apply_mute();
+        });
 
-        if is_always_on {
-            // Always-on mode: Play audio feedback immediately, then apply mute after sound finishes
-            debug!("Always-on mode: Playing audio feedback immediately");
-            let rm_clone = Arc::clone(&rm);
+        let recording_started = rm.try_start_recording_with_sink(&binding_id, frame_sink);
+        debug!("Recording started: {}", recording_started);
+        if !recording_started {
+            LIVE_TRANSCRIPTIONS.lock().unwrap().remove(&binding_id);
+        }
+    } else {
+        // On-demand mode: Start recording first, then play audio feedback, then apply mute
+        // This allows the microphone to be activated before playing the sound
+        debug!("On-demand mode: Starting recording first, then audio feedback");
+        let recording_start_time = Instant::now();
+        if rm.try_start_recording_with_sink(&binding_id, frame_sink) {
+            debug!("Recording started in {:?}", recording_start_time.elapsed());
+            // Small delay to ensure microphone stream is active
             let app_clone = app.clone();
-            // The blocking helper exits immediately if audio feedback is disabled,
-            // so we can always reuse this thread to ensure mute happens right after playback.
+            let rm_clone = Arc::clone(&rm);
             std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                debug!("Handling delayed audio feedback/mute sequence");
+                // Helper handles disabled audio feedback by returning early, so we reuse it
+                // to keep mute sequencing consistent in every mode.
                 play_feedback_sound_blocking(&app_clone, SoundType::Start);
                 rm_clone.apply_mute();
             });
-
-            let recording_started = rm.try_start_recording(&binding_id);
-            debug!("Recording started: {}", recording_started);
         } else {
-            // On-demand mode: Start recording first, then play audio feedback, then apply mute
-            // This allows the microphone to be activated before playing the sound
-            debug!("On-demand mode: Starting recording first, then audio feedback");
-            let recording_start_time = Instant::now();
-            if rm.try_start_recording(&binding_id) {
-                debug!("Recording started in {:?}", recording_start_time.elapsed());
-                // Small delay to ensure microphone stream is active
-                let app_clone = app.clone();
-                let rm_clone = Arc::clone(&rm);
-                std::thread::spawn(move || {
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                    debug!("Handling delayed audio feedback/mute sequence");
-                    // Helper handles disabled audio feedback by returning early, so we reuse it
-                    // to keep mute sequencing consistent in every mode.
-                    play_feedback_sound_blocking(&app_clone, SoundType::Start);
-                    rm_clone.apply_mute();
-                });
-            } else {
-                debug!("Failed to start recording");
-            }
+            debug!("Failed to start recording");
+            LIVE_TRANSCRIPTIONS.lock().unwrap().remove(&binding_id);
         }
-
-        debug!(
-            "TranscribeAction::start completed in {:?}",
-            start_time.elapsed()
-        );
     }
 
-    fn stop(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
-        let stop_time = Instant::now();
-        debug!("TranscribeAction::stop called for binding: {}", binding_id);
+    debug!("{:?} recording started in {:?}", mode, start_time.elapsed());
+}
 
-        let ah = app.clone();
-        let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
-        let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
-        let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
+fn stop_recording(app: &AppHandle, binding_id: &str, mode: RecordingMode) {
+    let stop_time = Instant::now();
+    debug!("Stopping {:?} recording for binding: {}", mode, binding_id);
 
-        change_tray_icon(app, TrayIconState::Transcribing);
-        show_transcribing_overlay(app);
+    let ah = app.clone();
+    let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
+    let queue = Arc::clone(&app.state::<Arc<TranscriptionQueue>>());
 
-        // Unmute before playing audio feedback so the stop sound is audible
-        rm.remove_mute();
+    change_tray_icon(app, TrayIconState::Transcribing);
+    show_transcribing_overlay(app);
 
-        // Play audio feedback for recording stop
-        play_feedback_sound(app, SoundType::Stop);
+    // Unmute before playing audio feedback so the stop sound is audible
+    rm.remove_mute();
 
-        let binding_id = binding_id.to_string(); // Clone binding_id for the async task
+    // Play audio feedback for recording stop
+    play_feedback_sound(app, SoundType::Stop);
 
-        tauri::async_runtime::spawn(async move {
-            let binding_id = binding_id.clone(); // Clone for the inner async task
+    let binding_id = binding_id.to_string(); // Clone binding_id for the async task
+
+    // Take the job id now, before any async work, so it fixes this recording's paste order
+    let job = queue.begin_job();
+
+    tauri::async_runtime::spawn(async move {
+        let binding_id = binding_id.clone(); // Clone for the inner async task
+        debug!(
+            "Starting transcription job {} for binding: {}",
+            job.id, binding_id
+        );
+
+        let stop_recording_time = Instant::now();
+        let outcome = if let Some(RecordedAudio {
+            samples,
+            speech_ratio,
+        }) = rm.stop_recording(&binding_id)
+        {
             debug!(
-                "Starting async transcription task for binding: {}",
-                binding_id
+                "Recording stopped and samples retrieved in {:?}, sample count: {}",
+                stop_recording_time.elapsed(),
+                samples.len()
             );
-
-            let stop_recording_time = Instant::now();
-            if let Some(samples) = rm.stop_recording(&binding_id) {
-                debug!(
-                    "Recording stopped and samples retrieved in {:?}, sample count: {}",
-                    stop_recording_time.elapsed(),
-                    samples.len()
-                );
-
-                let transcription_time = Instant::now();
-                let samples_for_history = samples.clone();
-                let settings = get_settings(&ah);
-                let provider = settings.provider;
-
-                let transcription_result = if provider == TranscriptionProvider::Local {
-                    tm.transcribe(samples).map_err(|e| e.to_string())
-                } else {
-                    transcribe_remote(&settings, &samples_for_history).await
-                };
-
-                match transcription_result {
-                    Ok(transcription) => {
-                        debug!(
-                            "Transcription completed via {:?} in {:?} ({} chars)",
-                            provider,
-                            transcription_time.elapsed(),
-                            transcription.len()
-                        );
-
-                        let (final_text, post_processed_text, post_process_prompt) =
-                            finalize_transcription(&settings, &transcription).await;
-
-                        if final_text.trim().is_empty() {
-                            utils::hide_recording_overlay(&ah);
-                            change_tray_icon(&ah, TrayIconState::Idle);
-                            return;
-                        }
-
-                        // Save to history with post-processed text and prompt
-                        let hm_clone = Arc::clone(&hm);
-                        let transcription_for_history = transcription.clone();
-                        let post_processed_for_history = post_processed_text.clone();
-                        let post_process_prompt_for_history = post_process_prompt.clone();
-                        tauri::async_runtime::spawn(async move {
-                            if let Err(e) = hm_clone
-                                .save_transcription(
-                                    samples_for_history,
-                                    transcription_for_history,
-                                    post_processed_for_history,
-                                    post_process_prompt_for_history,
-                                )
-                                .await
-                            {
-                                error!("Failed to save transcription to history: {}", e);
-                            }
-                        });
-
-                        // Paste the final text (either processed or original)
-                        let ah_clone = ah.clone();
-                        let paste_time = Instant::now();
-                        ah.run_on_main_thread(move || {
-                            match utils::paste(final_text.clone(), ah_clone.clone()) {
-                                Ok(()) => debug!(
-                                    "Text pasted successfully in {:?}",
-                                    paste_time.elapsed()
-                                ),
-                                Err(e) => error!("Failed to paste transcription: {}", e),
-                            }
-                            // Hide the overlay after transcription is complete
-                            utils::hide_recording_overlay(&ah_clone);
-                            change_tray_icon(&ah_clone, TrayIconState::Idle);
-                        })
-                        .unwrap_or_else(|e| {
-                            error!("Failed to run paste on main thread: {:?}", e);
-                            utils::hide_recording_overlay(&ah);
-                            change_tray_icon(&ah, TrayIconState::Idle);
-                        });
-                    }
-                    Err(err) => {
-                        warn!("Transcription failed via {:?}: {}", provider, err);
-                        emit_overlay_error(&ah, &err);
-                        utils::hide_recording_overlay(&ah);
-                        change_tray_icon(&ah, TrayIconState::Idle);
-                    }
+            let pending_context = PROMPT_CONTEXTS.lock().unwrap().remove(&binding_id);
+            let (context, app_profile) = match pending_context {
+                Some(pending) => pending.await.unwrap_or_default(),
+                None => Default::default(),
+            };
+            let recording = Recording {
+                samples,
+                speech_ratio,
+                live: LIVE_TRANSCRIPTIONS.lock().unwrap().remove(&binding_id),
+                context,
+                app_profile,
+            };
+            match mode {
+                RecordingMode::Dictation => {
+                    run_transcription_job(&ah, &queue, &job, &binding_id, recording).await
+                }
+                RecordingMode::EditSelection => {
+                    run_edit_job(&ah, &queue, &job, &binding_id, recording).await
                 }
-            } else {
-                debug!("No samples retrieved from recording stop");
-                emit_overlay_error(&ah, "No audio captured");
-                utils::hide_recording_overlay(&ah);
-                change_tray_icon(&ah, TrayIconState::Idle);
             }
-        });
+        } else {
+            debug!("No samples retrieved from recording stop");
+            JobOutcome::Failed("No audio captured".into())
+        };
 
-        debug!(
-            "TranscribeAction::stop completed in {:?}",
-            stop_time.elapsed()
-        );
+        queue.complete(job, outcome);
+    });
+
+    debug!("{:?} recording stopped in {:?}", mode, stop_time.elapsed());
+}
+
+impl ShortcutAction for TranscribeAction {
+    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        start_recording(app, binding_id, RecordingMode::Dictation);
+    }
+
+    fn stop(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        stop_recording(app, binding_id, RecordingMode::Dictation);
+    }
+}
+
+impl ShortcutAction for EditSelectionAction {
+    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        start_recording(app, binding_id, RecordingMode::EditSelection);
+    }
+
+    fn stop(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        stop_recording(app, binding_id, RecordingMode::EditSelection);
     }
 }
 
@@ -806,6 +610,20 @@ impl ShortcutAction for TestAction {
     }
 }
 
+/// Id prefix of transcription shortcuts added by the user
+pub const CUSTOM_TRANSCRIBE_PREFIX: &str = "transcribe_";
+
+/// The action a shortcut triggers. Shortcuts added by the user all
+/// transcribe, each with its own action profile.
+pub fn action_for_binding(binding_id: &str) -> Option<&'static Arc<dyn ShortcutAction>> {
+    ACTION_MAP.get(binding_id).or_else(|| {
+        binding_id
+            .starts_with(CUSTOM_TRANSCRIBE_PREFIX)
+            .then(|| ACTION_MAP.get("transcribe"))
+            .flatten()
+    })
+}
+
 // Static Action Map
 pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::new(|| {
     let mut map = HashMap::new();
@@ -813,9 +631,31 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "transcribe".to_string(),
         Arc::new(TranscribeAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        EDIT_SELECTION_BINDING_ID.to_string(),
+        Arc::new(EditSelectionAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "test".to_string(),
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
     );
     map
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_action<T: ShortcutAction>(binding_id: &str) -> bool {
+        action_for_binding(binding_id).is_some_and(|action| (action.as_ref() as &dyn Any).is::<T>())
+    }
+
+    #[test]
+    fn test_action_for_binding() {
+        assert!(is_action::<TranscribeAction>("transcribe"));
+        assert!(is_action::<TranscribeAction>("transcribe_2"));
+        assert!(is_action::<EditSelectionAction>(EDIT_SELECTION_BINDING_ID));
+        assert!(is_action::<TestAction>("test"));
+        assert!(action_for_binding("unknown").is_none());
+    }
+}
@@ -0,0 +1,488 @@
+//! Encoders for sending 16 kHz mono recordings over the network. WAV is
+//! accepted everywhere, FLAC halves it losslessly and Ogg Opus is a small
+//! fraction of it at speech quality.
+
+use anyhow::Result;
+use audiopus::coder::Encoder as OpusEncoder;
+use audiopus::{Application, Bitrate, Channels, SampleRate};
+use hound::{WavSpec, WavWriter};
+use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+use std::io::Cursor;
+
+const SAMPLE_RATE: u32 = 16000;
+
+const FLAC_BLOCK_SIZE: usize = 4096;
+const FLAC_MAX_FIXED_ORDER: usize = 4;
+const FLAC_MAX_PARTITION_ORDER: u32 = 6;
+/// 15 is the escape code in 4-bit Rice parameters
+const FLAC_MAX_RICE_PARAM: u32 = 14;
+
+/// 20 ms at 16 kHz, the frame size Opus is tuned for
+const OPUS_FRAME_SIZE: usize = 320;
+const OPUS_MAX_PACKET: usize = 4000;
+const OGG_SERIAL: u32 = 1;
+
+fn to_i16(sample: f32) -> i16 {
+    (sample * i16::MAX as f32).clamp(i16::MIN as f32, i16::MAX as f32) as i16
+}
+
+/// 16-bit PCM WAV.
+pub fn encode_wav(samples: &[f32]) -> Result<Vec<u8>> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut cursor = Cursor::new(Vec::new());
+    {
+        let mut writer = WavWriter::new(&mut cursor, spec)?;
+        for sample in samples {
+            writer.write_sample(to_i16(*sample))?;
+        }
+        writer.finalize()?;
+    }
+    Ok(cursor.into_inner())
+}
+
+/// 16-bit FLAC using the fixed predictors, which get most of the way to
+/// what LPC would for speech at a fraction of the work.
+pub fn encode_flac(samples: &[f32]) -> Result<Vec<u8>> {
+    let pcm: Vec<i32> = samples.iter().map(|s| to_i16(*s) as i32).collect();
+    let block_size = if pcm.len() >= FLAC_BLOCK_SIZE {
+        FLAC_BLOCK_SIZE
+    } else {
+        pcm.len().max(16)
+    };
+
+    let mut out = BitWriter::default();
+    out.write_bytes(b"fLaC");
+
+    // STREAMINFO, the only metadata block
+    out.write(1, 1);
+    out.write(0, 7);
+    out.write(34, 24);
+    out.write(block_size as u64, 16);
+    out.write(block_size as u64, 16);
+    out.write(0, 24); // frame sizes unknown
+    out.write(0, 24);
+    out.write(SAMPLE_RATE as u64, 20);
+    out.write(0, 3); // one channel
+    out.write(15, 5); // 16 bits per sample
+    out.write((pcm.len() as u64) >> 32, 4);
+    out.write(pcm.len() as u64 & 0xffff_ffff, 32);
+    out.write_bytes(&[0; 16]); // no MD5
+
+    for (index, block) in pcm.chunks(FLAC_BLOCK_SIZE).enumerate() {
+        out.write_bytes(&flac_frame(block, index as u64));
+    }
+    Ok(out.into_bytes())
+}
+
+fn flac_frame(block: &[i32], frame_number: u64) -> Vec<u8> {
+    let mut frame = BitWriter::default();
+    frame.write(0b11_1111_1111_1110, 14);
+    frame.write(0, 1);
+    frame.write(0, 1); // fixed block size
+    let full_block = block.len() == FLAC_BLOCK_SIZE;
+    frame.write(if full_block { 0b1100 } else { 0b0111 }, 4);
+    frame.write(0b0101, 4); // 16 kHz
+    frame.write(0, 4); // mono
+    frame.write(0b100, 3); // 16 bits per sample
+    frame.write(0, 1);
+    frame.write_bytes(&utf8_number(frame_number));
+    if !full_block {
+        frame.write(block.len() as u64 - 1, 16);
+    }
+    let header_crc = crc8(frame.bytes());
+    frame.write(header_crc as u64, 8);
+
+    write_subframe(&mut frame, block);
+    frame.align();
+
+    let crc = crc16(frame.bytes());
+    frame.write(crc as u64, 16);
+    frame.into_bytes()
+}
+
+/// Picks the cheapest of a constant, verbatim or fixed-predictor subframe.
+fn write_subframe(out: &mut BitWriter, block: &[i32]) {
+    if block.iter().all(|&s| s == block[0]) {
+        out.write(0, 8); // CONSTANT
+        out.write_signed(block[0], 16);
+        return;
+    }
+
+    let verbatim_bits = 8 + 16 * block.len() as u64;
+    let best = (0..=FLAC_MAX_FIXED_ORDER.min(block.len() - 1))
+        .map(|order| {
+            let residuals = fixed_residuals(block, order);
+            let (bits, partition_order) = best_partitioning(&residuals, block.len(), order);
+            (
+                order,
+                residuals,
+                partition_order,
+                8 + 16 * order as u64 + 6 + bits,
+            )
+        })
+        .min_by_key(|candidate| candidate.3);
+
+    match best {
+        Some((order, residuals, partition_order, bits)) if bits < verbatim_bits => {
+            out.write(0b0001_0000 | (order as u64) << 1, 8); // FIXED
+            for &sample in &block[..order] {
+                out.write_signed(sample, 16);
+            }
+            out.write(0, 2); // Rice coding with 4-bit parameters
+            out.write(partition_order as u64, 4);
+            let partitions = 1usize << partition_order;
+            let per_partition = block.len() >> partition_order;
+            let mut start = 0;
+            for partition in 0..partitions {
+                let count = per_partition - if partition == 0 { order } else { 0 };
+                let slice = &residuals[start..start + count];
+                let param = rice_param(slice);
+                out.write(param as u64, 4);
+                for &value in slice {
+                    out.write_unary(value >> param);
+                    out.write(value as u64, param);
+                }
+                start += count;
+            }
+        }
+        _ => {
+            out.write(0b0000_0010, 8); // VERBATIM
+            for &sample in block {
+                out.write_signed(sample, 16);
+            }
+        }
+    }
+}
+
+/// Residuals of the fixed polynomial predictor of `order`, zigzag-folded
+/// to unsigned for Rice coding.
+fn fixed_residuals(block: &[i32], order: usize) -> Vec<u32> {
+    (order..block.len())
+        .map(|i| {
+            let residual = match order {
+                0 => block[i],
+                1 => block[i] - block[i - 1],
+                2 => block[i] - 2 * block[i - 1] + block[i - 2],
+                3 => block[i] - 3 * block[i - 1] + 3 * block[i - 2] - block[i - 3],
+                _ => {
+                    block[i] - 4 * block[i - 1] + 6 * block[i - 2] - 4 * block[i - 3] + block[i - 4]
+                }
+            };
+            ((residual << 1) ^ (residual >> 31)) as u32
+        })
+        .collect()
+}
+
+/// Cheapest partition order for the residuals and its cost in bits.
+fn best_partitioning(residuals: &[u32], block_len: usize, order: usize) -> (u64, u32) {
+    (0..=FLAC_MAX_PARTITION_ORDER)
+        .filter(|&p| block_len.trailing_zeros() >= p && (block_len >> p) > order)
+        .map(|p| {
+            let per_partition = block_len >> p;
+            let mut start = 0;
+            let mut bits = 0;
+            for partition in 0..1usize << p {
+                let count = per_partition - if partition == 0 { order } else { 0 };
+                let slice = &residuals[start..start + count];
+                bits += 4 + rice_bits(slice, rice_param(slice));
+                start += count;
+            }
+            (bits, p)
+        })
+        .min()
+        .unwrap_or((u64::MAX, 0))
+}
+
+/// Rice parameter close to optimal for the values: log2 of their mean.
+fn rice_param(values: &[u32]) -> u32 {
+    if values.is_empty() {
+        return 0;
+    }
+    let mean = values.iter().map(|&v| v as u64).sum::<u64>() / values.len() as u64;
+    (64 - mean.leading_zeros())
+        .saturating_sub(1)
+        .min(FLAC_MAX_RICE_PARAM)
+}
+
+fn rice_bits(values: &[u32], param: u32) -> u64 {
+    values
+        .iter()
+        .map(|&v| (v >> param) as u64 + 1 + param as u64)
+        .sum()
+}
+
+/// Frame numbers use the same variable-length scheme as UTF-8.
+fn utf8_number(value: u64) -> Vec<u8> {
+    if value < 0x80 {
+        return vec![value as u8];
+    }
+    let len = match value {
+        0..=0x7ff => 2,
+        0x800..=0xffff => 3,
+        0x1_0000..=0x1f_ffff => 4,
+        0x20_0000..=0x3ff_ffff => 5,
+        0x400_0000..=0x7fff_ffff => 6,
+        _ => 7,
+    };
+    let mut bytes = vec![0u8; len];
+    let mut rest = value;
+    for byte in bytes[1..].iter_mut().rev() {
+        *byte = 0x80 | (rest & 0x3f) as u8;
+        rest >>= 6;
+    }
+    bytes[0] = (0xff00u16 >> len) as u8 | rest as u8;
+    bytes
+}
+
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// MSB-first bit packing for the FLAC bitstream.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    pending: u64,
+    pending_bits: u32,
+}
+
+impl BitWriter {
+    /// Appends the low `count` bits of `value`, at most 32 at a time.
+    fn write(&mut self, value: u64, count: u32) {
+        if count == 0 {
+            return;
+        }
+        self.pending = (self.pending << count) | (value & ((1u64 << count) - 1));
+        self.pending_bits += count;
+        while self.pending_bits >= 8 {
+            self.pending_bits -= 8;
+            self.bytes.push((self.pending >> self.pending_bits) as u8);
+        }
+        self.pending &= (1u64 << self.pending_bits) - 1;
+    }
+
+    fn write_signed(&mut self, value: i32, count: u32) {
+        self.write(value as u32 as u64, count);
+    }
+
+    fn write_unary(&mut self, zeros: u32) {
+        let mut remaining = zeros;
+        while remaining >= 32 {
+            self.write(0, 32);
+            remaining -= 32;
+        }
+        self.write(1, remaining + 1);
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write(byte as u64, 8);
+        }
+    }
+
+    fn align(&mut self) {
+        if self.pending_bits > 0 {
+            self.write(0, 8 - self.pending_bits);
+        }
+    }
+
+    /// Whole bytes written so far.
+    fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+/// Opus in an Ogg container at `bitrate` bits per second, which OpenAI,
+/// Groq and Deepgram all accept as `audio/ogg`.
+pub fn encode_ogg_opus(samples: &[f32], bitrate: i32) -> Result<Vec<u8>> {
+    let mut encoder = OpusEncoder::new(SampleRate::Hz16000, Channels::Mono, Application::Voip)?;
+    encoder.set_bitrate(Bitrate::BitsPerSecond(bitrate))?;
+    let lookahead = encoder.lookahead()? as usize;
+    // Ogg Opus counts granule positions in 48 kHz samples
+    let pre_skip = lookahead * 3;
+    let end_granule = (pre_skip + samples.len() * 3) as u64;
+
+    let mut writer = PacketWriter::new(Vec::new());
+
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(1); // channels
+    head.extend_from_slice(&(pre_skip as u16).to_le_bytes());
+    head.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // mono/stereo channel mapping
+    writer.write_packet(
+        head.into_boxed_slice(),
+        OGG_SERIAL,
+        PacketWriteEndInfo::EndPage,
+        0,
+    )?;
+
+    let vendor = b"Handy";
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor);
+    tags.extend_from_slice(&0u32.to_le_bytes()); // no comments
+    writer.write_packet(
+        tags.into_boxed_slice(),
+        OGG_SERIAL,
+        PacketWriteEndInfo::EndPage,
+        0,
+    )?;
+
+    // Pad by the encoder delay so the end of the recording is flushed out
+    let padded_len = samples.len() + lookahead;
+    let frame_count = padded_len.div_ceil(OPUS_FRAME_SIZE).max(1);
+    let mut frame = [0f32; OPUS_FRAME_SIZE];
+    let mut packet = [0u8; OPUS_MAX_PACKET];
+
+    for index in 0..frame_count {
+        let start = (index * OPUS_FRAME_SIZE).min(samples.len());
+        let end = ((index + 1) * OPUS_FRAME_SIZE).min(samples.len());
+        let chunk = &samples[start..end];
+        frame[..chunk.len()].copy_from_slice(chunk);
+        frame[chunk.len()..].fill(0.0);
+
+        let len = encoder.encode_float(&frame, &mut packet)?;
+        let granule = ((pre_skip + (index + 1) * OPUS_FRAME_SIZE * 3) as u64).min(end_granule);
+        let end_info = if index + 1 == frame_count {
+            PacketWriteEndInfo::EndStream
+        } else {
+            PacketWriteEndInfo::NormalPacket
+        };
+        writer.write_packet(packet[..len].into(), OGG_SERIAL, end_info, granule)?;
+    }
+
+    Ok(writer.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / SAMPLE_RATE as f32).sin() * 0.5)
+            .collect()
+    }
+
+    /// Decodes with an independent FLAC implementation.
+    fn decode_flac(flac: &[u8]) -> Vec<i32> {
+        let mut reader = claxon::FlacReader::new(Cursor::new(flac)).unwrap();
+        reader.samples().map(|sample| sample.unwrap()).collect()
+    }
+
+    fn assert_flac_round_trips(samples: &[f32]) {
+        let expected: Vec<i32> = samples.iter().map(|s| to_i16(*s) as i32).collect();
+        assert_eq!(decode_flac(&encode_flac(samples).unwrap()), expected);
+    }
+
+    fn assert_valid_crc(frame: &[u8]) {
+        let (body, crc) = frame.split_at(frame.len() - 2);
+        assert_eq!(crc16(body).to_be_bytes(), crc);
+    }
+
+    #[test]
+    fn flac_has_stream_info() {
+        let flac = encode_flac(&tone(10_000)).unwrap();
+        assert_eq!(&flac[..4], b"fLaC");
+        assert_eq!(flac[4], 0x80); // last metadata block, STREAMINFO
+        assert_eq!(&flac[8..10], &(FLAC_BLOCK_SIZE as u16).to_be_bytes());
+        // 20-bit sample rate, 3-bit channels, 5-bit depth and 36-bit length
+        let info = u64::from_be_bytes(flac[18..26].try_into().unwrap());
+        assert_eq!(info >> 44, SAMPLE_RATE as u64);
+        assert_eq!((info >> 41) & 0x7, 0);
+        assert_eq!((info >> 36) & 0x1f, 15);
+        assert_eq!(info & 0xf_ffff_ffff, 10_000);
+    }
+
+    #[test]
+    fn flac_decodes_to_the_same_samples() {
+        // A partial last block, exactly one block and a single sample
+        assert_flac_round_trips(&tone(10_000));
+        assert_flac_round_trips(&tone(FLAC_BLOCK_SIZE));
+        assert_flac_round_trips(&tone(1));
+        assert_flac_round_trips(&vec![0.0; FLAC_BLOCK_SIZE * 2]);
+
+        // Noise defeats the predictors and clipping hits the sample limits
+        let mut seed = 1u32;
+        let noise: Vec<f32> = (0..20_000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as f32 / (1u32 << 23) as f32 - 1.0
+            })
+            .collect();
+        assert_flac_round_trips(&noise);
+        let clipped: Vec<f32> = tone(9_000).iter().map(|s| s * 2.5).collect();
+        assert_flac_round_trips(&clipped);
+    }
+
+    #[test]
+    fn flac_frames_carry_valid_crcs() {
+        let pcm: Vec<i32> = tone(5_000).iter().map(|s| to_i16(*s) as i32).collect();
+        assert_valid_crc(&flac_frame(&pcm[..FLAC_BLOCK_SIZE], 0));
+        assert_valid_crc(&flac_frame(&pcm[FLAC_BLOCK_SIZE..], 1));
+    }
+
+    #[test]
+    fn flac_is_smaller_than_wav() {
+        let samples = tone(SAMPLE_RATE as usize * 2);
+        let wav = encode_wav(&samples).unwrap();
+        let flac = encode_flac(&samples).unwrap();
+        assert!(
+            flac.len() < wav.len() / 2,
+            "{} vs {}",
+            flac.len(),
+            wav.len()
+        );
+    }
+
+    #[test]
+    fn flac_stores_silence_as_constant_subframes() {
+        let flac = encode_flac(&vec![0.0; FLAC_BLOCK_SIZE * 2]).unwrap();
+        // Header, STREAMINFO and two frames of a few bytes each
+        assert!(flac.len() < 80, "{} bytes", flac.len());
+    }
+
+    #[test]
+    fn frame_numbers_use_utf8_coding() {
+        assert_eq!(utf8_number(0x41), vec![0x41]);
+        assert_eq!(utf8_number(0x3a9), vec![0xce, 0xa9]);
+        assert_eq!(utf8_number(0x20ac), vec![0xe2, 0x82, 0xac]);
+    }
+}
@@ -1,12 +1,14 @@
 // Re-export all audio components
 mod device;
+mod encoding;
 mod recorder;
 mod resampler;
 mod utils;
 mod visualizer;
 
 pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
-pub use recorder::AudioRecorder;
+pub use encoding::{encode_flac, encode_ogg_opus, encode_wav};
+pub use recorder::{AudioRecorder, FrameSink, RecordingStats};
 pub use resampler::FrameResampler;
-pub use utils::save_wav_file;
+pub use utils::{read_wav_file, save_wav_file};
 pub use visualizer::AudioVisualiser;
@@ -16,18 +16,41 @@ use crate::audio_toolkit::{
     VoiceActivityDetector,
 };
 
+/// Receives each 16 kHz frame of a recording as it is captured, before the
+/// VAD drops silence. Dropped when the recording stops.
+pub type FrameSink = Box<dyn FnMut(&[f32]) + Send + 'static>;
+
 enum Cmd {
-    Start,
-    Stop(mpsc::Sender<Vec<f32>>),
+    Start(Option<FrameSink>),
+    Stop(mpsc::Sender<(Vec<f32>, RecordingStats)>),
+    /// Run the VAD between recordings and report speech onsets
+    WatchSpeech(bool),
     Shutdown,
 }
 
+/// How much of a recording the VAD kept as speech.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecordingStats {
+    /// Samples captured between start and stop, at 16 kHz
+    pub recorded_samples: usize,
+    /// Samples the VAD passed through, including pre-roll and hangover
+    pub speech_samples: usize,
+}
+
+impl RecordingStats {
+    pub fn speech_ratio(&self) -> Option<f32> {
+        (self.recorded_samples > 0)
+            .then(|| (self.speech_samples as f32 / self.recorded_samples as f32).min(1.0))
+    }
+}
+
 pub struct AudioRecorder {
     device: Option<Device>,
     cmd_tx: Option<mpsc::Sender<Cmd>>,
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    speech_cb: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
 }
 
 impl AudioRecorder {
@@ -38,6 +61,7 @@ impl AudioRecorder {
             worker_handle: None,
             vad: None,
             level_cb: None,
+            speech_cb: None,
         })
     }
 
@@ -54,6 +78,16 @@ impl AudioRecorder {
         self
     }
 
+    /// Called when the VAD hears speech while not recording. Only fires
+    /// after `watch_speech(true)`.
+    pub fn with_speech_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.speech_cb = Some(Arc::new(cb));
+        self
+    }
+
     pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
@@ -74,6 +108,7 @@ impl AudioRecorder {
         let vad = self.vad.clone();
         // Move the optional level callback into the worker thread
         let level_cb = self.level_cb.clone();
+        let speech_cb = self.speech_cb.clone();
 
         let worker = std::thread::spawn(move || {
             let config = AudioRecorder::get_preferred_config(&thread_device)
@@ -117,7 +152,7 @@ impl AudioRecorder {
             stream.play().expect("failed to start stream");
 
             // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
+            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb, speech_cb);
             // stream is dropped here, after run_consumer returns
         });
 
@@ -129,13 +164,38 @@ impl AudioRecorder {
     }
 
     pub fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.start_with_sink(None)
+    }
+
+    /// Starts recording and also hands every captured frame to `sink`, for
+    /// transcribing while the user is still speaking.
+    pub fn start_with_sink(
+        &self,
+        sink: Option<FrameSink>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Start(sink))?;
+        }
+        Ok(())
+    }
+
+    /// Turns the idle speech watch on or off for the open stream. Costs one
+    /// VAD inference per frame while on.
+    pub fn watch_speech(&self, enabled: bool) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(tx) = &self.cmd_tx {
-            tx.send(Cmd::Start)?;
+            tx.send(Cmd::WatchSpeech(enabled))?;
         }
         Ok(())
     }
 
     pub fn stop(&self) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+        Ok(self.stop_with_stats()?.0)
+    }
+
+    /// Stops recording and also reports how much of it the VAD kept.
+    pub fn stop_with_stats(
+        &self,
+    ) -> Result<(Vec<f32>, RecordingStats), Box<dyn std::error::Error>> {
         let (resp_tx, resp_rx) = mpsc::channel();
         if let Some(tx) = &self.cmd_tx {
             tx.send(Cmd::Stop(resp_tx))?;
@@ -228,6 +288,7 @@ fn run_consumer(
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    speech_cb: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
 ) {
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
@@ -236,7 +297,11 @@ fn run_consumer(
     );
 
     let mut processed_samples = Vec::<f32>::new();
+    let mut recorded_samples = 0usize;
     let mut recording = false;
+    let mut watching_speech = false;
+    let mut heard_speech = false;
+    let mut frame_sink: Option<FrameSink> = None;
 
     // ---------- spectrum visualisation setup ---------------------------- //
     const BUCKETS: usize = 16;
@@ -254,10 +319,16 @@ fn run_consumer(
         recording: bool,
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
+        recorded: &mut usize,
+        sink: &mut Option<FrameSink>,
     ) {
         if !recording {
             return;
         }
+        *recorded += samples.len();
+        if let Some(sink) = sink {
+            sink(samples);
+        }
 
         if let Some(vad_arc) = vad {
             let mut det = vad_arc.lock().unwrap();
@@ -270,6 +341,23 @@ fn run_consumer(
         }
     }
 
+    /// Fires `speech_cb` once per speech onset heard between recordings.
+    fn watch_frame(
+        samples: &[f32],
+        vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+        speech_cb: &Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+        heard_speech: &mut bool,
+    ) {
+        let (Some(vad_arc), Some(cb)) = (vad, speech_cb) else {
+            return;
+        };
+        let is_speech = vad_arc.lock().unwrap().is_voice(samples).unwrap_or(false);
+        if is_speech && !*heard_speech {
+            cb();
+        }
+        *heard_speech = is_speech;
+    }
+
     loop {
         let raw = match sample_rx.recv() {
             Ok(s) => s,
@@ -285,14 +373,26 @@ fn run_consumer(
 
         // ---------- existing pipeline ------------------------------------ //
         frame_resampler.push(&raw, &mut |frame: &[f32]| {
-            handle_frame(frame, recording, &vad, &mut processed_samples)
+            if !recording && watching_speech {
+                watch_frame(frame, &vad, &speech_cb, &mut heard_speech);
+            }
+            handle_frame(
+                frame,
+                recording,
+                &vad,
+                &mut processed_samples,
+                &mut recorded_samples,
+                &mut frame_sink,
+            )
         });
 
         // non-blocking check for a command
         while let Ok(cmd) = cmd_rx.try_recv() {
             match cmd {
-                Cmd::Start => {
+                Cmd::Start(sink) => {
+                    frame_sink = sink;
                     processed_samples.clear();
+                    recorded_samples = 0;
                     recording = true;
                     visualizer.reset(); // Reset visualization buffer
                     if let Some(v) = &vad {
@@ -304,10 +404,31 @@ fn run_consumer(
 
                     frame_resampler.finish(&mut |frame: &[f32]| {
                         // we still want to process the last few frames
-                        handle_frame(frame, true, &vad, &mut processed_samples)
+                        handle_frame(
+                            frame,
+                            true,
+                            &vad,
+                            &mut processed_samples,
+                            &mut recorded_samples,
+                            &mut frame_sink,
+                        )
                     });
-
-                    let _ = reply_tx.send(std::mem::take(&mut processed_samples));
+                    // Closes a live stream once it has the last frames
+                    frame_sink = None;
+
+                    let stats = RecordingStats {
+                        recorded_samples,
+                        speech_samples: processed_samples.len(),
+                    };
+                    let _ = reply_tx.send((std::mem::take(&mut processed_samples), stats));
+                    heard_speech = false;
+                }
+                Cmd::WatchSpeech(enabled) => {
+                    watching_speech = enabled;
+                    heard_speech = false;
+                    if let Some(v) = &vad {
+                        v.lock().unwrap().reset();
+                    }
                 }
                 Cmd::Shutdown => return,
             }
@@ -1,7 +1,54 @@
+use super::FrameResampler;
 use anyhow::Result;
-use hound::{WavSpec, WavWriter};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
 use log::debug;
 use std::path::Path;
+use std::time::Duration;
+
+const TARGET_SAMPLE_RATE: usize = 16000;
+
+/// Read a WAV file as 16kHz mono samples, the format the transcription engines expect
+pub fn read_wav_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<f32>> {
+    let mut reader = WavReader::open(file_path.as_ref())?;
+    let spec = reader.spec();
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|s| s as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    // Downmix to mono by averaging channels
+    let channels = spec.channels.max(1) as usize;
+    let mono: Vec<f32> = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    let sample_rate = spec.sample_rate as usize;
+    if sample_rate == TARGET_SAMPLE_RATE {
+        return Ok(mono);
+    }
+
+    let mut resampler =
+        FrameResampler::new(sample_rate, TARGET_SAMPLE_RATE, Duration::from_millis(30));
+    let mut output = Vec::with_capacity(mono.len() * TARGET_SAMPLE_RATE / sample_rate + 1);
+    resampler.push(&mono, |frame| output.extend_from_slice(frame));
+    resampler.finish(|frame| output.extend_from_slice(frame));
+
+    debug!(
+        "Read WAV file {:?} ({} Hz, {} channels)",
+        file_path.as_ref(),
+        spec.sample_rate,
+        spec.channels
+    );
+    Ok(output)
+}
 
 /// Save audio samples as a WAV file
 pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
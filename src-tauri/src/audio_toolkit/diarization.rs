@@ -0,0 +1,522 @@
+//! Speaker diarization: who spoke when. Cloud providers label words with
+//! speakers themselves; for local engines the recording is split into
+//! turns by clustering spectral embeddings of short windows, and each turn
+//! is then transcribed on its own.
+
+use rustfft::{num_complex::Complex32, FftPlanner};
+use serde::{Deserialize, Serialize};
+
+use super::constants::WHISPER_SAMPLE_RATE;
+
+const FRAME_LEN: usize = 400; // 25 ms
+const FRAME_HOP: usize = 160; // 10 ms
+const FFT_LEN: usize = 512;
+const MEL_BANDS: usize = 24;
+const CEPSTRA: usize = 13;
+const MEL_MIN_HZ: f32 = 80.0;
+const MEL_MAX_HZ: f32 = 7600.0;
+
+/// Frames per embedding window (1.5 s) and between windows (0.75 s)
+const WINDOW_FRAMES: usize = 150;
+const WINDOW_HOP_FRAMES: usize = 75;
+/// Windows with less speech than this are left to their neighbours
+const MIN_SPEECH_SHARE: f32 = 0.5;
+/// Frames this far above the noise floor count as speech
+const SPEECH_MARGIN_DB: f32 = 12.0;
+/// Frames quieter than this are never speech
+const SILENCE_DB: f32 = -55.0;
+/// Clusters further apart than this are treated as different speakers
+const MERGE_DISTANCE: f32 = 0.25;
+/// Turns shorter than this are folded into the previous one
+const MIN_TURN_SECS: f32 = 1.0;
+
+/// A stretch of one speaker's speech, stored with the history entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerSegment {
+    /// Zero-based, in order of first appearance
+    pub speaker: u32,
+    /// Seconds from the start of the recording
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// A stretch of one speaker in a recording, as sample offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeakerTurn {
+    pub speaker: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl SpeakerTurn {
+    pub fn start_secs(&self) -> f64 {
+        self.start as f64 / WHISPER_SAMPLE_RATE as f64
+    }
+
+    pub fn end_secs(&self) -> f64 {
+        self.end as f64 / WHISPER_SAMPLE_RATE as f64
+    }
+}
+
+/// Splits 16 kHz mono audio into speaker turns covering the whole
+/// recording. `max_speakers` caps the number of clusters; 0 leaves it to
+/// the distance threshold. A single turn means one speaker was found.
+pub fn diarize(samples: &[f32], max_speakers: usize) -> Vec<SpeakerTurn> {
+    let whole = vec![SpeakerTurn {
+        speaker: 0,
+        start: 0,
+        end: samples.len(),
+    }];
+
+    let frames = frame_features(samples);
+    if frames.len() < WINDOW_FRAMES {
+        return whole;
+    }
+    let is_speech = speech_frames(&frames);
+
+    // One embedding per window with enough speech
+    let mut windows = Vec::new();
+    let mut start = 0;
+    while start + WINDOW_FRAMES <= frames.len() {
+        let speech: Vec<&Frame> = frames[start..start + WINDOW_FRAMES]
+            .iter()
+            .zip(&is_speech[start..start + WINDOW_FRAMES])
+            .filter_map(|(frame, &speech)| speech.then_some(frame))
+            .collect();
+        if speech.len() as f32 >= WINDOW_FRAMES as f32 * MIN_SPEECH_SHARE {
+            windows.push((start, embedding(&speech)));
+        }
+        start += WINDOW_HOP_FRAMES;
+    }
+    if windows.len() < 2 {
+        return whole;
+    }
+
+    let embeddings: Vec<Vec<f32>> = windows.iter().map(|(_, e)| e.clone()).collect();
+    let labels = cluster(&embeddings, max_speakers);
+
+    // Each window owns the hop that starts at it, silent stretches go to
+    // the speaker before them
+    let mut turns: Vec<SpeakerTurn> = Vec::new();
+    for (i, &(frame, _)) in windows.iter().enumerate() {
+        let start = if i == 0 { 0 } else { frame * FRAME_HOP };
+        let speaker = labels[i];
+        match turns.last_mut() {
+            Some(last) if last.speaker == speaker => {}
+            _ => {
+                if let Some(last) = turns.last_mut() {
+                    last.end = start;
+                }
+                turns.push(SpeakerTurn {
+                    speaker,
+                    start,
+                    end: samples.len(),
+                });
+            }
+        }
+    }
+
+    let turns = fold_short_turns(turns);
+    renumber(turns)
+}
+
+struct Frame {
+    cepstra: [f32; CEPSTRA],
+    energy_db: f32,
+}
+
+fn frame_features(samples: &[f32]) -> Vec<Frame> {
+    if samples.len() < FRAME_LEN {
+        return Vec::new();
+    }
+
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(FFT_LEN);
+    let window: Vec<f32> = (0..FRAME_LEN)
+        .map(|i| {
+            0.54 - 0.46 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LEN - 1) as f32).cos()
+        })
+        .collect();
+    let filters = mel_filterbank();
+
+    let mut buffer = vec![Complex32::new(0.0, 0.0); FFT_LEN];
+    let mut frames = Vec::with_capacity((samples.len() - FRAME_LEN) / FRAME_HOP + 1);
+    let mut start = 0;
+    while start + FRAME_LEN <= samples.len() {
+        let frame = &samples[start..start + FRAME_LEN];
+        let energy = frame.iter().map(|s| s * s).sum::<f32>() / FRAME_LEN as f32;
+
+        for (i, slot) in buffer.iter_mut().enumerate() {
+            *slot = if i < FRAME_LEN {
+                Complex32::new(frame[i] * window[i], 0.0)
+            } else {
+                Complex32::new(0.0, 0.0)
+            };
+        }
+        fft.process(&mut buffer);
+        let power: Vec<f32> = buffer[..FFT_LEN / 2 + 1]
+            .iter()
+            .map(|c| c.norm_sqr())
+            .collect();
+
+        let log_mel: Vec<f32> = filters
+            .iter()
+            .map(|(first_bin, weights)| {
+                let band: f32 = weights
+                    .iter()
+                    .enumerate()
+                    .map(|(i, w)| w * power[first_bin + i])
+                    .sum();
+                (band + 1e-10).ln()
+            })
+            .collect();
+
+        frames.push(Frame {
+            cepstra: dct(&log_mel),
+            energy_db: 10.0 * (energy + 1e-10).log10(),
+        });
+        start += FRAME_HOP;
+    }
+    frames
+}
+
+/// Triangular filters as (first FFT bin, weights), evenly spaced in mel.
+fn mel_filterbank() -> Vec<(usize, Vec<f32>)> {
+    let to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let to_hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+    let bin_of = |hz: f32| hz * FFT_LEN as f32 / WHISPER_SAMPLE_RATE as f32;
+
+    let (low, high) = (to_mel(MEL_MIN_HZ), to_mel(MEL_MAX_HZ));
+    let edges: Vec<f32> = (0..MEL_BANDS + 2)
+        .map(|i| {
+            bin_of(to_hz(
+                low + (high - low) * i as f32 / (MEL_BANDS + 1) as f32,
+            ))
+        })
+        .collect();
+
+    (0..MEL_BANDS)
+        .map(|band| {
+            let (left, center, right) = (edges[band], edges[band + 1], edges[band + 2]);
+            let first = left.floor() as usize;
+            let last = (right.ceil() as usize).min(FFT_LEN / 2);
+            let weights = (first..=last)
+                .map(|bin| {
+                    let bin = bin as f32;
+                    if bin <= center {
+                        ((bin - left) / (center - left)).max(0.0)
+                    } else {
+                        ((right - bin) / (right - center)).max(0.0)
+                    }
+                })
+                .collect();
+            (first, weights)
+        })
+        .collect()
+}
+
+/// DCT-II of the log mel energies, the usual MFCCs.
+fn dct(log_mel: &[f32]) -> [f32; CEPSTRA] {
+    let n = log_mel.len() as f32;
+    let mut cepstra = [0.0; CEPSTRA];
+    for (k, coefficient) in cepstra.iter_mut().enumerate() {
+        *coefficient = log_mel
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                value * (std::f32::consts::PI * k as f32 * (i as f32 + 0.5) / n).cos()
+            })
+            .sum();
+    }
+    cepstra
+}
+
+/// Frames well above the recording's noise floor. Without pauses the
+/// floor is the speech itself, so anything close to the loudest frames
+/// counts too.
+fn speech_frames(frames: &[Frame]) -> Vec<bool> {
+    let mut energies: Vec<f32> = frames.iter().map(|f| f.energy_db).collect();
+    energies.sort_by(|a, b| a.total_cmp(b));
+    let noise_floor = energies[energies.len() / 10];
+    let peak = energies[energies.len() - 1];
+    let threshold = (noise_floor + SPEECH_MARGIN_DB)
+        .min(peak - SPEECH_MARGIN_DB / 2.0)
+        .max(SILENCE_DB);
+    frames.iter().map(|f| f.energy_db > threshold).collect()
+}
+
+/// Mean and spread of the cepstra, leaving out c0 so loudness doesn't
+/// separate speakers, scaled to unit length.
+fn embedding(frames: &[&Frame]) -> Vec<f32> {
+    let n = frames.len() as f32;
+    let mut mean = [0.0f32; CEPSTRA - 1];
+    for frame in frames {
+        for (m, c) in mean.iter_mut().zip(&frame.cepstra[1..]) {
+            *m += c / n;
+        }
+    }
+    let mut spread = [0.0f32; CEPSTRA - 1];
+    for frame in frames {
+        for ((s, m), c) in spread.iter_mut().zip(&mean).zip(&frame.cepstra[1..]) {
+            *s += (c - m).powi(2) / n;
+        }
+    }
+
+    let mut embedding: Vec<f32> = mean
+        .iter()
+        .copied()
+        .chain(spread.iter().map(|s| s.sqrt()))
+        .collect();
+    let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        embedding.iter_mut().for_each(|v| *v /= norm);
+    }
+    embedding
+}
+
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>()
+}
+
+/// Average-linkage agglomerative clustering. Merges the closest pair until
+/// every pair is further apart than `MERGE_DISTANCE` and there are at most
+/// `max_speakers` clusters. Returns one label per embedding.
+fn cluster(embeddings: &[Vec<f32>], max_speakers: usize) -> Vec<u32> {
+    let n = embeddings.len();
+    let mut distance = vec![vec![0.0f32; n]; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let d = cosine_distance(&embeddings[i], &embeddings[j]);
+            distance[i][j] = d;
+            distance[j][i] = d;
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let linkage = |a: &[usize], b: &[usize]| {
+        let total: f32 = a
+            .iter()
+            .flat_map(|&i| b.iter().map(move |&j| (i, j)))
+            .map(|(i, j)| distance[i][j])
+            .sum();
+        total / (a.len() * b.len()) as f32
+    };
+
+    while clusters.len() > 1 {
+        let mut closest = (0, 1, f32::MAX);
+        for i in 0..clusters.len() {
+            for j in i + 1..clusters.len() {
+                let d = linkage(&clusters[i], &clusters[j]);
+                if d < closest.2 {
+                    closest = (i, j, d);
+                }
+            }
+        }
+
+        let over_cap = max_speakers > 0 && clusters.len() > max_speakers;
+        if closest.2 > MERGE_DISTANCE && !over_cap {
+            break;
+        }
+        let merged = clusters.remove(closest.1);
+        clusters[closest.0].extend(merged);
+    }
+
+    let mut labels = vec![0; n];
+    for (label, members) in clusters.iter().enumerate() {
+        for &member in members {
+            labels[member] = label as u32;
+        }
+    }
+    labels
+}
+
+/// Folds turns too short to be a real change of speaker into the turn
+/// before them, or the one after for the first turn.
+fn fold_short_turns(turns: Vec<SpeakerTurn>) -> Vec<SpeakerTurn> {
+    let min_len = (MIN_TURN_SECS * WHISPER_SAMPLE_RATE as f32) as usize;
+    let mut folded: Vec<SpeakerTurn> = Vec::with_capacity(turns.len());
+    for turn in turns {
+        let is_first = folded.len() == 1;
+        match folded.last_mut() {
+            Some(last) if last.speaker == turn.speaker || turn.end - turn.start < min_len => {
+                last.end = turn.end;
+            }
+            Some(last) if is_first && last.end - last.start < min_len => {
+                *last = SpeakerTurn {
+                    start: last.start,
+                    ..turn
+                };
+            }
+            _ => folded.push(turn),
+        }
+    }
+    folded
+}
+
+/// Numbers speakers in order of first appearance.
+fn renumber(mut turns: Vec<SpeakerTurn>) -> Vec<SpeakerTurn> {
+    let mut order: Vec<u32> = Vec::new();
+    for turn in &mut turns {
+        let index = match order.iter().position(|&s| s == turn.speaker) {
+            Some(index) => index,
+            None => {
+                order.push(turn.speaker);
+                order.len() - 1
+            }
+        };
+        turn.speaker = index as u32;
+    }
+    turns
+}
+
+/// Groups consecutive words by speaker. Words without a speaker join the
+/// current segment.
+pub fn segments_from_words<'a>(
+    words: impl IntoIterator<Item = (&'a str, f64, f64, Option<u32>)>,
+) -> Vec<SpeakerSegment> {
+    let mut segments: Vec<SpeakerSegment> = Vec::new();
+    for (word, start, end, speaker) in words {
+        match segments.last_mut() {
+            Some(last) if speaker.is_none() || speaker == Some(last.speaker) => {
+                last.end = end;
+                last.text.push(' ');
+                last.text.push_str(word);
+            }
+            _ => segments.push(SpeakerSegment {
+                speaker: speaker.unwrap_or(0),
+                start,
+                end,
+                text: word.to_string(),
+            }),
+        }
+    }
+    segments
+}
+
+/// Number of distinct speakers in the segments.
+pub fn speaker_count(segments: &[SpeakerSegment]) -> usize {
+    let mut speakers: Vec<u32> = segments.iter().map(|s| s.speaker).collect();
+    speakers.sort_unstable();
+    speakers.dedup();
+    speakers.len()
+}
+
+/// Renders the segments as `Speaker 1: …` turns separated by blank lines.
+pub fn render_turns(segments: &[SpeakerSegment]) -> String {
+    let mut turns: Vec<(u32, String)> = Vec::new();
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        match turns.last_mut() {
+            Some((speaker, turn)) if *speaker == segment.speaker => {
+                turn.push(' ');
+                turn.push_str(text);
+            }
+            _ => turns.push((segment.speaker, text.to_string())),
+        }
+    }
+    turns
+        .iter()
+        .map(|(speaker, text)| format!("Speaker {}: {}", speaker + 1, text))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = WHISPER_SAMPLE_RATE as f32;
+
+    /// A voiced sound: harmonics of `pitch` shaped by one formant, with a
+    /// slow wobble in pitch so windows aren't identical.
+    fn voice(pitch: f32, formant: f32, seconds: f32, seed: f32) -> Vec<f32> {
+        let len = (seconds * RATE) as usize;
+        let mut phase = 0.0f32;
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / RATE;
+                let f0 = pitch * (1.0 + 0.03 * (2.0 * std::f32::consts::PI * 3.0 * t + seed).sin());
+                phase =
+                    (phase + 2.0 * std::f32::consts::PI * f0 / RATE) % (2.0 * std::f32::consts::PI);
+                (1..)
+                    .map(|k| k as f32)
+                    .take_while(|k| k * f0 < 7000.0)
+                    .map(|k| {
+                        let gain = (-((k * f0 - formant) / 600.0).powi(2)).exp();
+                        gain * (k * phase).sin()
+                    })
+                    .sum::<f32>()
+                    * 0.1
+            })
+            .collect()
+    }
+
+    fn speakers(turns: &[SpeakerTurn]) -> Vec<u32> {
+        turns.iter().map(|t| t.speaker).collect()
+    }
+
+    #[test]
+    fn finds_alternating_speakers() {
+        let mut samples = voice(110.0, 500.0, 4.0, 0.0);
+        samples.extend(voice(230.0, 2200.0, 4.0, 1.0));
+        samples.extend(voice(115.0, 520.0, 4.0, 2.0));
+
+        let turns = diarize(&samples, 0);
+        assert_eq!(speakers(&turns), vec![0, 1, 0], "{:?}", turns);
+        assert_eq!(turns[0].start, 0);
+        assert_eq!(turns.last().unwrap().end, samples.len());
+        // Boundaries land within a window hop of the real change
+        assert!((turns[1].start_secs() - 4.0).abs() <= 0.8, "{:?}", turns);
+    }
+
+    #[test]
+    fn keeps_a_single_speaker_together() {
+        let samples = voice(120.0, 700.0, 10.0, 0.5);
+        assert_eq!(speakers(&diarize(&samples, 0)), vec![0]);
+    }
+
+    #[test]
+    fn max_speakers_caps_the_clusters() {
+        let mut samples = voice(110.0, 500.0, 3.0, 0.0);
+        samples.extend(voice(230.0, 2200.0, 3.0, 1.0));
+        samples.extend(voice(170.0, 1300.0, 3.0, 2.0));
+
+        let turns = diarize(&samples, 1);
+        assert_eq!(speakers(&turns), vec![0]);
+    }
+
+    #[test]
+    fn short_recordings_are_one_turn() {
+        let samples = vec![0.0; 8000];
+        assert_eq!(
+            diarize(&samples, 0),
+            vec![SpeakerTurn {
+                speaker: 0,
+                start: 0,
+                end: 8000
+            }]
+        );
+    }
+
+    #[test]
+    fn groups_words_and_renders_turns() {
+        let segments = segments_from_words([
+            ("Hello", 0.0, 0.4, Some(0)),
+            ("there.", 0.4, 0.8, Some(0)),
+            ("Hi!", 1.0, 1.2, Some(1)),
+            ("How", 1.5, 1.7, Some(0)),
+            ("are", 1.7, 1.8, None),
+        ]);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].text, "Hello there.");
+        assert_eq!(segments[0].end, 0.8);
+        assert_eq!(speaker_count(&segments), 2);
+        assert_eq!(
+            render_turns(&segments),
+            "Speaker 1: Hello there.\n\nSpeaker 2: Hi!\n\nSpeaker 1: How are"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+/// Phrases Whisper tends to produce on silence, learned from subtitled video
+/// in its training data. Keyed by Whisper language code and stored in the
+/// normalized form produced by `normalize_phrase`.
+const KNOWN_HALLUCINATIONS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "thank you for watching",
+            "thanks for watching",
+            "thank you for watching and please subscribe",
+            "please subscribe",
+            "please like and subscribe",
+            "don t forget to like and subscribe",
+            "see you in the next video",
+            "subtitles by the amara org community",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "gracias por ver el video",
+            "gracias por ver",
+            "suscríbete al canal",
+            "subtítulos realizados por la comunidad de amara org",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "merci d avoir regardé cette vidéo",
+            "merci d avoir regardé",
+            "sous titres réalisés par la communauté d amara org",
+            "sous titrage st 501",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "vielen dank fürs zuschauen",
+            "untertitel der amara org community",
+            "untertitel im auftrag des zdf für funk 2017",
+            "untertitel im auftrag des zdf",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "grazie per la visione",
+            "sottotitoli creati dalla comunità amara org",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "obrigado por assistir",
+            "legendas pela comunidade amara org",
+        ],
+    ),
+    ("nl", &["ondertiteld door de amara org gemeenschap"]),
+    (
+        "ru",
+        &[
+            "продолжение следует",
+            "спасибо за просмотр",
+            "субтитры сделал dimatorzok",
+        ],
+    ),
+    ("ja", &["ご視聴ありがとうございました"]),
+    ("ko", &["시청해주셔서 감사합니다"]),
+    (
+        "zh",
+        &[
+            "谢谢观看",
+            "字幕由amara org社区提供",
+            "请不吝点赞 订阅 转发 打赏支持明镜与点点栏目",
+        ],
+    ),
+];
+
+/// A phrase repeated back-to-back this many times is treated as a decoding loop.
+const MIN_LOOP_REPEATS: usize = 4;
+/// Longest phrase, in tokens, checked for repetition loops.
+const MAX_LOOP_TOKENS: usize = 12;
+
+/// What the hallucination filter did to a transcription.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterAction {
+    Kept,
+    /// Part of the text was removed; carries the reason.
+    Trimmed(String),
+    /// The whole text was discarded; carries the reason.
+    Dropped(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilteredTranscription {
+    pub text: String,
+    pub action: FilterAction,
+}
+
+/// Removes output that Whisper commonly invents when there is little or no speech
+///
+/// Checks, in order:
+/// - the share of the recording the VAD kept as speech, dropping everything
+///   below `min_speech_ratio`
+/// - leading or trailing sentences that are known hallucination phrases
+/// - the same phrase repeated back-to-back, collapsed to one occurrence
+///
+/// # Arguments
+/// * `text` - The transcription to check
+/// * `language` - Whisper language code, or `None` to check every language's phrases
+/// * `speech_ratio` - Fraction of the recording the VAD classified as speech, if known
+/// * `min_speech_ratio` - Ratio below which the recording is considered silent
+pub fn filter_hallucinations(
+    text: &str,
+    language: Option<&str>,
+    speech_ratio: Option<f32>,
+    min_speech_ratio: f32,
+) -> FilteredTranscription {
+    if text.trim().is_empty() {
+        return FilteredTranscription {
+            text: String::new(),
+            action: FilterAction::Kept,
+        };
+    }
+
+    if let Some(ratio) = speech_ratio {
+        if ratio < min_speech_ratio {
+            return FilteredTranscription {
+                text: String::new(),
+                action: FilterAction::Dropped(format!(
+                    "only {:.1}% of the recording contained speech",
+                    ratio * 100.0
+                )),
+            };
+        }
+    }
+
+    let mut reasons = Vec::new();
+    let mut current = text.trim().to_string();
+
+    if let Some(stripped) = strip_known_phrases(&current, language) {
+        if stripped.is_empty() {
+            return FilteredTranscription {
+                text: stripped,
+                action: FilterAction::Dropped("output is a known hallucination phrase".to_string()),
+            };
+        }
+        reasons.push("removed a known hallucination phrase");
+        current = stripped;
+    }
+
+    if let Some(collapsed) = collapse_repetitions(&current) {
+        reasons.push("collapsed a repetition loop");
+        current = collapsed;
+    }
+
+    let action = if reasons.is_empty() {
+        FilterAction::Kept
+    } else {
+        FilterAction::Trimmed(reasons.join(", "))
+    };
+    FilteredTranscription {
+        text: current,
+        action,
+    }
+}
+
+/// Lowercases and replaces punctuation with single spaces so phrases match
+/// regardless of how Whisper punctuated them.
+fn normalize_phrase(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_known_phrase(sentence: &str, language: Option<&str>) -> bool {
+    let normalized = normalize_phrase(sentence);
+    if normalized.is_empty() {
+        return false;
+    }
+
+    KNOWN_HALLUCINATIONS
+        .iter()
+        .filter(|(code, _)| language.map_or(true, |language| language == *code))
+        .any(|(_, phrases)| phrases.contains(&normalized.as_str()))
+}
+
+/// Splits after sentence-ending punctuation, keeping the punctuation.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        if matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '\n') {
+            let end = index + c.len_utf8();
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+        .into_iter()
+        .filter(|sentence| !sentence.trim().is_empty())
+        .collect()
+}
+
+/// Drops known phrases from the start and end of the text. Returns `None`
+/// when nothing was removed.
+fn strip_known_phrases(text: &str, language: Option<&str>) -> Option<String> {
+    let sentences = split_sentences(text);
+    let mut start = 0;
+    let mut end = sentences.len();
+
+    while start < end && is_known_phrase(sentences[start], language) {
+        start += 1;
+    }
+    while end > start && is_known_phrase(sentences[end - 1], language) {
+        end -= 1;
+    }
+
+    if start == 0 && end == sentences.len() {
+        return None;
+    }
+    Some(sentences[start..end].concat().trim().to_string())
+}
+
+/// Collapses any phrase repeated `MIN_LOOP_REPEATS` or more times in a row
+/// down to a single occurrence. Works on words, or on characters for text
+/// written without spaces. Returns `None` when there was no loop.
+fn collapse_repetitions(text: &str) -> Option<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (tokens, separator, min_tokens) = if words.len() > 1 {
+        (words, " ", 1)
+    } else {
+        let chars = text
+            .char_indices()
+            .map(|(index, c)| &text[index..index + c.len_utf8()])
+            .collect();
+        // Single repeated characters are usually deliberate, like 哈哈哈哈
+        (chars, "", 2)
+    };
+    let keys: Vec<String> = tokens.iter().map(|token| normalize_phrase(token)).collect();
+
+    let mut output: Vec<&str> = Vec::with_capacity(tokens.len());
+    let mut changed = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let remaining = tokens.len() - i;
+        let longest = MAX_LOOP_TOKENS.min(remaining / MIN_LOOP_REPEATS);
+
+        let repetition = (min_tokens..=longest).find_map(|n| {
+            let mut repeats = 1;
+            while i + (repeats + 1) * n <= tokens.len()
+                && keys[i..i + n] == keys[i + repeats * n..i + (repeats + 1) * n]
+            {
+                repeats += 1;
+            }
+            (repeats >= MIN_LOOP_REPEATS).then_some((n, repeats))
+        });
+
+        match repetition {
+            Some((n, repeats)) => {
+                output.extend_from_slice(&tokens[i..i + n]);
+                i += n * repeats;
+                changed = true;
+            }
+            None => {
+                output.push(tokens[i]);
+                i += 1;
+            }
+        }
+    }
+
+    changed.then(|| output.join(separator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_normal_speech() {
+        let result =
+            filter_hallucinations("Send the report by Friday.", Some("en"), Some(0.8), 0.05);
+        assert_eq!(result.text, "Send the report by Friday.");
+        assert_eq!(result.action, FilterAction::Kept);
+    }
+
+    #[test]
+    fn test_drops_known_phrase() {
+        let result = filter_hallucinations(" Thank you for watching! ", Some("en"), None, 0.05);
+        assert_eq!(result.text, "");
+        assert!(matches!(result.action, FilterAction::Dropped(_)));
+    }
+
+    #[test]
+    fn test_known_phrase_respects_language() {
+        let result = filter_hallucinations("Vielen Dank fürs Zuschauen.", Some("en"), None, 0.05);
+        assert_eq!(result.action, FilterAction::Kept);
+
+        let result = filter_hallucinations("Vielen Dank fürs Zuschauen.", None, None, 0.05);
+        assert!(matches!(result.action, FilterAction::Dropped(_)));
+    }
+
+    #[test]
+    fn test_trims_trailing_phrase() {
+        let result = filter_hallucinations(
+            "The build is green. Thanks for watching.",
+            Some("en"),
+            None,
+            0.05,
+        );
+        assert_eq!(result.text, "The build is green.");
+        assert!(matches!(result.action, FilterAction::Trimmed(_)));
+    }
+
+    #[test]
+    fn test_drops_low_speech_ratio() {
+        let result = filter_hallucinations("Okay.", Some("en"), Some(0.01), 0.05);
+        assert_eq!(result.text, "");
+        assert!(matches!(result.action, FilterAction::Dropped(_)));
+    }
+
+    #[test]
+    fn test_collapses_repetition_loop() {
+        let result = filter_hallucinations(
+            "Let me check. I'll be right back. I'll be right back. I'll be right back. I'll be right back.",
+            Some("en"),
+            None,
+            0.05,
+        );
+        assert_eq!(result.text, "Let me check. I'll be right back.");
+        assert!(matches!(result.action, FilterAction::Trimmed(_)));
+    }
+
+    #[test]
+    fn test_short_repetition_is_kept() {
+        let result = filter_hallucinations("No, no, no.", Some("en"), None, 0.05);
+        assert_eq!(result.action, FilterAction::Kept);
+    }
+
+    #[test]
+    fn test_collapses_character_loop() {
+        assert_eq!(
+            collapse_repetitions("好的谢谢谢谢谢谢谢谢"),
+            Some("好的谢谢".to_string())
+        );
+        assert_eq!(collapse_repetitions("哈哈哈哈"), None);
+    }
+}
@@ -1,12 +1,20 @@
 pub mod audio;
 pub mod constants;
+pub mod diarization;
+pub mod hallucination;
 pub mod text;
 pub mod utils;
 pub mod vad;
 
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    encode_flac, encode_ogg_opus, encode_wav, list_input_devices, list_output_devices,
+    read_wav_file, save_wav_file, AudioRecorder, CpalDeviceInfo, FrameSink, RecordingStats,
+};
+pub use diarization::{diarize, render_turns, SpeakerSegment};
+pub use hallucination::{filter_hallucinations, FilterAction};
+pub use text::{
+    apply_custom_words, character_error_rate, fix_case_and_spacing, normalize_for_scoring,
+    word_error_rate,
 };
-pub use text::apply_custom_words;
 pub use utils::get_cpal_host;
 pub use vad::{SileroVad, VoiceActivityDetector};
@@ -132,10 +132,160 @@ fn extract_punctuation(word: &str) -> (&str, &str) {
     (prefix, suffix)
 }
 
+/// Splits text into lowercase words with punctuation removed, so scoring
+/// ignores differences in casing and punctuation between transcripts.
+pub fn normalize_for_scoring(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '\'')
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Minimum number of substitutions, insertions and deletions turning `reference` into `hypothesis`.
+fn edit_distance<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> usize {
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    let mut current = vec![0; hypothesis.len() + 1];
+
+    for (i, r) in reference.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, h) in hypothesis.iter().enumerate() {
+            let substitution = previous[j] + usize::from(r != h);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[hypothesis.len()]
+}
+
+/// Word error rate of `hypothesis` against `reference`, after normalization.
+/// Can exceed 1.0 when the hypothesis has many insertions.
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    let reference = normalize_for_scoring(reference);
+    let hypothesis = normalize_for_scoring(hypothesis);
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+    edit_distance(&reference, &hypothesis) as f64 / reference.len() as f64
+}
+
+/// Character error rate of `hypothesis` against `reference`, ignoring whitespace.
+/// More meaningful than WER for languages written without spaces.
+pub fn character_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    let reference: Vec<char> = normalize_for_scoring(reference).concat().chars().collect();
+    let hypothesis: Vec<char> = normalize_for_scoring(hypothesis).concat().chars().collect();
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+    edit_distance(&reference, &hypothesis) as f64 / reference.len() as f64
+}
+
+/// Tidies spacing and capitalization: collapses repeated spaces, removes
+/// spaces before closing punctuation, adds a space where punctuation ran
+/// into the next word and capitalizes the start of each sentence. Line
+/// breaks are kept.
+pub fn fix_case_and_spacing(text: &str) -> String {
+    text.lines()
+        .map(fix_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn fix_line(line: &str) -> String {
+    let chars: Vec<char> = line
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .collect();
+    let mut out = String::with_capacity(line.len());
+    let mut capitalize = true;
+    // A space dropped before punctuation moves to after it: "a ,b" -> "a, b"
+    let mut moved_space = false;
+
+    for (index, &c) in chars.iter().enumerate() {
+        let next = chars.get(index + 1).copied();
+        if c == ' ' {
+            if out.ends_with('(') {
+                continue;
+            }
+            if next.is_some_and(|next| matches!(next, ',' | '.' | '!' | '?' | ';' | ':' | ')')) {
+                moved_space = true;
+                continue;
+            }
+        }
+
+        if capitalize && c.is_alphabetic() {
+            out.extend(c.to_uppercase());
+            capitalize = false;
+        } else {
+            out.push(c);
+            if c.is_alphanumeric() {
+                capitalize = false;
+            }
+        }
+
+        if matches!(c, '.' | '!' | '?') && !ends_with_abbreviation(&out) {
+            capitalize = true;
+        }
+
+        // "word,next" or "end.Next", but not "3.5", "1,000" or "U.S."
+        let missing_space = next.is_some_and(|next| match c {
+            _ if moved_space => next.is_alphanumeric(),
+            ',' | ';' | '!' | '?' => next.is_alphabetic(),
+            '.' => next.is_uppercase() && index > 0 && chars[index - 1].is_lowercase(),
+            _ => false,
+        });
+        if missing_space {
+            out.push(' ');
+        }
+        if c != ' ' {
+            moved_space = false;
+        }
+    }
+    out
+}
+
+/// True when the text ends in an initial or abbreviation like "J." or "e.g.".
+fn ends_with_abbreviation(text: &str) -> bool {
+    let word = text.rsplit(' ').next().unwrap_or(text);
+    let Some(stem) = word.strip_suffix('.') else {
+        return false;
+    };
+    stem.contains('.') || stem.chars().count() == 1
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fix_case_and_spacing() {
+        assert_eq!(
+            fix_case_and_spacing("  hello ,world .this is  fine ! ok"),
+            "Hello, world. This is fine! Ok"
+        );
+        assert_eq!(
+            fix_case_and_spacing("it costs 3.5 dollars,or 1,000 cents"),
+            "It costs 3.5 dollars, or 1,000 cents"
+        );
+        assert_eq!(
+            fix_case_and_spacing("see e.g. the docs( page 2 )"),
+            "See e.g. the docs(page 2)"
+        );
+        assert_eq!(
+            fix_case_and_spacing("speaker 1: hi\n\nspeaker 2: hey"),
+            "Speaker 1: hi\n\nSpeaker 2: hey"
+        );
+    }
+
     #[test]
     fn test_apply_custom_words_exact_match() {
         let text = "hello world";
@@ -166,6 +316,25 @@ mod tests {
         assert_eq!(extract_punctuation("...hello..."), ("...", "..."));
     }
 
+    #[test]
+    fn test_word_error_rate() {
+        assert_eq!(word_error_rate("Hello, world!", "hello world"), 0.0);
+        assert_eq!(
+            word_error_rate("the cat sat", "the cat sat down"),
+            1.0 / 3.0
+        );
+        assert_eq!(word_error_rate("the cat sat", "a cat"), 2.0 / 3.0);
+        assert_eq!(word_error_rate("", ""), 0.0);
+        assert_eq!(word_error_rate("", "noise"), 1.0);
+    }
+
+    #[test]
+    fn test_character_error_rate() {
+        assert_eq!(character_error_rate("你好世界", "你好世界"), 0.0);
+        assert_eq!(character_error_rate("你好世界", "你好"), 0.5);
+        assert_eq!(character_error_rate("abcd", "abed"), 0.25);
+    }
+
     #[test]
     fn test_empty_custom_words() {
         let text = "hello world";
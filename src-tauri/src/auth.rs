@@ -0,0 +1,447 @@
+//! Sign-in to the credits backend with the device authorization flow:
+//! the app shows a short code, the user approves it in the system browser
+//! and the app polls until tokens are issued. Tokens live in the OS
+//! credential store and the access token is refreshed when it is rejected.
+
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use crate::http::{self, ErrorKind, Operation, RequestError};
+use crate::secure_store;
+
+const ACCESS_TOKEN_KEY: &str = "account_access_token";
+const REFRESH_TOKEN_KEY: &str = "account_refresh_token";
+
+/// Added to the polling interval each time the server asks us to slow down
+const SLOW_DOWN_STEP: Duration = Duration::from_secs(5);
+
+/// Held while refreshing so concurrent 401s trade the refresh token once
+static REFRESH_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// A pending sign-in, returned by `/api/auth/device`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceLogin {
+    pub device_code: String,
+    /// Code the user confirms in the browser
+    pub user_code: String,
+    pub verification_uri: String,
+    /// The verification page with the code already filled in
+    #[serde(default)]
+    pub verification_uri_complete: Option<String>,
+    /// Seconds until the device code expires
+    pub expires_in: u64,
+    /// Seconds to wait between polls
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+}
+
+fn default_poll_interval() -> u64 {
+    5
+}
+
+impl DeviceLogin {
+    /// The page to open in the browser.
+    pub fn browser_url(&self) -> &str {
+        self.verification_uri_complete
+            .as_deref()
+            .unwrap_or(&self.verification_uri)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tokens {
+    pub access_token: String,
+    /// Servers may keep the previous refresh token when refreshing
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+}
+
+/// The signed-in account, returned by `/api/me`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    pub user_id: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub minutes_remaining: Option<u32>,
+}
+
+/// OAuth-style error body, e.g. `{"error": "authorization_pending"}`.
+#[derive(Debug, Default, Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    error_description: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+enum PollStatus {
+    Pending,
+    SlowDown,
+    Complete(Tokens),
+}
+
+fn endpoint(base_url: &str, path: &str) -> String {
+    format!("{}{}", base_url.trim_end_matches('/'), path)
+}
+
+fn session_expired() -> RequestError {
+    RequestError::new(ErrorKind::Auth, "Your session has expired, sign in again")
+}
+
+pub fn not_signed_in() -> RequestError {
+    RequestError::new(ErrorKind::Auth, "Sign in to use account credits")
+}
+
+async fn read_error(response: Response, context: &str) -> (StatusCode, ErrorBody, String) {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    let body: ErrorBody = serde_json::from_str(&text).unwrap_or_default();
+    let detail = body
+        .error_description
+        .clone()
+        .or_else(|| body.message.clone())
+        .or_else(|| body.error.clone())
+        .unwrap_or_else(|| status.to_string());
+    (status, body, format!("{}: {}", context, detail))
+}
+
+async fn parse_json<T: serde::de::DeserializeOwned>(
+    response: Response,
+    context: &str,
+) -> Result<T, RequestError> {
+    if !response.status().is_success() {
+        let (status, _, message) = read_error(response, context).await;
+        return Err(RequestError::from_status(status, message));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| RequestError::from_reqwest(&e, context))
+}
+
+/// Starts a sign-in. Show `user_code` and open `browser_url()`.
+pub async fn start_device_login(base_url: &str) -> Result<DeviceLogin, RequestError> {
+    let url = endpoint(base_url, "/api/auth/device");
+    let response = http::send(Operation::VALIDATION, || {
+        http::client().post(&url).json(&serde_json::json!({}))
+    })
+    .await?;
+    parse_json(response, "Failed to start sign-in").await
+}
+
+async fn poll_device_login(base_url: &str, device_code: &str) -> Result<PollStatus, RequestError> {
+    let url = endpoint(base_url, "/api/auth/token");
+    let response = http::send(Operation::VALIDATION, || {
+        http::client().post(&url).json(&serde_json::json!({
+            "grant_type": "urn:ietf:params:oauth:grant-type:device_code",
+            "device_code": device_code,
+        }))
+    })
+    .await?;
+
+    if response.status().is_success() {
+        return parse_json(response, "Failed to complete sign-in")
+            .await
+            .map(PollStatus::Complete);
+    }
+
+    let (status, body, message) = read_error(response, "Sign-in failed").await;
+    match body.error.as_deref() {
+        Some("authorization_pending") => Ok(PollStatus::Pending),
+        Some("slow_down") => Ok(PollStatus::SlowDown),
+        Some("expired_token") => Err(RequestError::new(
+            ErrorKind::Auth,
+            "The sign-in code expired, start again",
+        )),
+        Some("access_denied") => Err(RequestError::new(ErrorKind::Auth, "Sign-in was declined")),
+        _ => Err(RequestError::from_status(status, message)),
+    }
+}
+
+/// Polls until the user approves the sign-in in the browser, declines it
+/// or the code expires.
+pub async fn wait_for_device_login(
+    base_url: &str,
+    login: &DeviceLogin,
+) -> Result<Tokens, RequestError> {
+    let deadline = Instant::now() + Duration::from_secs(login.expires_in);
+    let mut interval = Duration::from_secs(login.interval.max(1));
+
+    loop {
+        tokio::time::sleep(interval).await;
+        if Instant::now() >= deadline {
+            return Err(RequestError::new(
+                ErrorKind::Auth,
+                "The sign-in code expired, start again",
+            ));
+        }
+
+        match poll_device_login(base_url, &login.device_code).await? {
+            PollStatus::Complete(tokens) => return Ok(tokens),
+            PollStatus::Pending => {}
+            PollStatus::SlowDown => {
+                interval += SLOW_DOWN_STEP;
+                debug!(
+                    "Sign-in server asked to slow down, polling every {:?}",
+                    interval
+                );
+            }
+        }
+    }
+}
+
+/// Trades a refresh token for a new access token.
+pub async fn refresh_tokens(base_url: &str, refresh_token: &str) -> Result<Tokens, RequestError> {
+    let url = endpoint(base_url, "/api/auth/refresh");
+    let response = http::send(Operation::VALIDATION, || {
+        http::client()
+            .post(&url)
+            .json(&serde_json::json!({ "refresh_token": refresh_token }))
+    })
+    .await?;
+
+    // The server rejected the refresh token, so the session is over
+    if matches!(
+        response.status(),
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED
+    ) {
+        let (_, _, message) = read_error(response, "Failed to refresh session").await;
+        debug!("{}", message);
+        return Err(session_expired());
+    }
+    parse_json(response, "Failed to refresh session").await
+}
+
+/// Fetches the signed-in account, refreshing the access token if needed.
+pub async fn fetch_account(base_url: &str) -> Result<Account, RequestError> {
+    let url = endpoint(base_url, "/api/me");
+    let response = send_authorized(Operation::VALIDATION, base_url, |token| {
+        http::client().get(&url).bearer_auth(token)
+    })
+    .await?;
+    parse_json(response, "Failed to load account").await
+}
+
+pub fn access_token() -> Option<String> {
+    secure_store::fetch_api_key(ACCESS_TOKEN_KEY).filter(|token| !token.trim().is_empty())
+}
+
+pub fn is_signed_in() -> bool {
+    access_token().is_some()
+}
+
+pub fn save_tokens(tokens: &Tokens) -> Result<(), String> {
+    secure_store::store_api_key(ACCESS_TOKEN_KEY, &tokens.access_token)?;
+    if let Some(refresh_token) = &tokens.refresh_token {
+        secure_store::store_api_key(REFRESH_TOKEN_KEY, refresh_token)?;
+    }
+    Ok(())
+}
+
+pub fn clear_tokens() {
+    for key in [ACCESS_TOKEN_KEY, REFRESH_TOKEN_KEY] {
+        if let Err(e) = secure_store::delete_api_key(key) {
+            warn!("Failed to remove {} from the credential store: {}", key, e);
+        }
+    }
+}
+
+/// Refreshes the stored tokens after `stale` was rejected and returns the
+/// new access token. When another request already refreshed, its token is
+/// reused. A rejected refresh token signs the user out.
+async fn refresh_stored_tokens(base_url: &str, stale: String) -> Result<String, RequestError> {
+    let _guard = REFRESH_LOCK.lock().await;
+    match access_token() {
+        Some(current) if current != stale => return Ok(current),
+        None => return Err(not_signed_in()),
+        Some(_) => {}
+    }
+
+    let refresh_token = secure_store::fetch_api_key(REFRESH_TOKEN_KEY).ok_or_else(|| {
+        clear_tokens();
+        session_expired()
+    })?;
+    match refresh_tokens(base_url, &refresh_token).await {
+        Ok(tokens) => {
+            save_tokens(&tokens)?;
+            info!("Refreshed account session");
+            Ok(tokens.access_token)
+        }
+        Err(e) => {
+            if e.kind == ErrorKind::Auth {
+                warn!("Session refresh was rejected, signing out");
+                clear_tokens();
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Sends a request with the stored access token. When it comes back 401,
+/// the token is refreshed and the request sent once more.
+pub async fn send_authorized<F>(
+    operation: Operation,
+    base_url: &str,
+    build: F,
+) -> Result<Response, RequestError>
+where
+    F: Fn(&str) -> RequestBuilder,
+{
+    let token = access_token().ok_or_else(not_signed_in)?;
+    send_with_refresh(operation, build, token, |stale| {
+        refresh_stored_tokens(base_url, stale)
+    })
+    .await
+}
+
+async fn send_with_refresh<F, R, Fut>(
+    operation: Operation,
+    build: F,
+    token: String,
+    refresh: R,
+) -> Result<Response, RequestError>
+where
+    F: Fn(&str) -> RequestBuilder,
+    R: FnOnce(String) -> Fut,
+    Fut: Future<Output = Result<String, RequestError>>,
+{
+    let response = http::send(operation, || build(&token)).await?;
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+
+    debug!("Access token rejected, refreshing");
+    let token = refresh(token).await?;
+    http::send(operation, || build(&token)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Stands in for the credits backend: answers each connection with the
+    /// next canned `(status, body)` and records the request lines and
+    /// authorization headers it saw.
+    async fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 8192];
+                let read = socket.read(&mut buffer).await.unwrap();
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                let request_line = request.lines().next().unwrap_or_default().to_string();
+                let auth = request
+                    .lines()
+                    .find(|line| line.to_ascii_lowercase().starts_with("authorization:"))
+                    .map(|line| line[14..].trim().to_string())
+                    .unwrap_or_default();
+                log.lock()
+                    .unwrap()
+                    .push(format!("{} {}", request_line, auth).trim().to_string());
+
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.shutdown().await.ok();
+            }
+        });
+
+        (base_url, seen)
+    }
+
+    #[tokio::test]
+    async fn device_login_polls_until_approved() {
+        let (base_url, seen) = mock_server(vec![
+            (
+                200,
+                r#"{"device_code":"dev","user_code":"ABCD-EFGH","verification_uri":"https://example.com/device","expires_in":60,"interval":1}"#,
+            ),
+            (400, r#"{"error":"authorization_pending"}"#),
+            (200, r#"{"access_token":"at-1","refresh_token":"rt-1"}"#),
+        ])
+        .await;
+
+        let login = start_device_login(&base_url).await.unwrap();
+        assert_eq!(login.user_code, "ABCD-EFGH");
+        assert_eq!(login.browser_url(), "https://example.com/device");
+
+        let tokens = wait_for_device_login(&base_url, &login).await.unwrap();
+        assert_eq!(tokens.access_token, "at-1");
+        assert_eq!(tokens.refresh_token.as_deref(), Some("rt-1"));
+
+        let seen = seen.lock().unwrap();
+        assert!(seen[0].starts_with("POST /api/auth/device"));
+        assert!(seen[1].starts_with("POST /api/auth/token"));
+        assert_eq!(seen.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn declined_login_is_an_auth_error() {
+        let (base_url, _) = mock_server(vec![(400, r#"{"error":"access_denied"}"#)]).await;
+        let login = DeviceLogin {
+            device_code: "dev".to_string(),
+            user_code: "ABCD".to_string(),
+            verification_uri: "https://example.com/device".to_string(),
+            verification_uri_complete: None,
+            expires_in: 60,
+            interval: 1,
+        };
+
+        let error = wait_for_device_login(&base_url, &login).await.unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Auth);
+    }
+
+    #[tokio::test]
+    async fn rejected_refresh_token_expires_the_session() {
+        let (base_url, _) = mock_server(vec![(401, r#"{"error":"invalid_grant"}"#)]).await;
+        let error = refresh_tokens(&base_url, "rt-old").await.unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Auth);
+    }
+
+    #[tokio::test]
+    async fn retries_once_with_a_refreshed_token_on_401() {
+        let (base_url, seen) = mock_server(vec![
+            (401, r#"{"error":"token_expired"}"#),
+            (200, r#"{"user_id":"u1","minutes_remaining":42}"#),
+        ])
+        .await;
+
+        let url = endpoint(&base_url, "/api/me");
+        let response = send_with_refresh(
+            Operation::VALIDATION,
+            |token| http::client().get(&url).bearer_auth(token),
+            "at-old".to_string(),
+            |stale| async move {
+                assert_eq!(stale, "at-old");
+                Ok("at-new".to_string())
+            },
+        )
+        .await
+        .unwrap();
+        let account: Account = parse_json(response, "me").await.unwrap();
+        assert_eq!(account.minutes_remaining, Some(42));
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0], "GET /api/me HTTP/1.1 Bearer at-old");
+        assert_eq!(seen[1], "GET /api/me HTTP/1.1 Bearer at-new");
+    }
+}
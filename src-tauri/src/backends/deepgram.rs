@@ -0,0 +1,258 @@
+use super::upload::EncodedAudio;
+use super::{Transcript, TranscriptWord};
+use crate::http::{self, Operation, RequestError};
+use crate::secure_store;
+use crate::settings::AppSettings;
+use log::{debug, warn};
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+pub(super) fn deepgram_api_key(settings: &AppSettings) -> Option<String> {
+    if settings.use_secure_key_storage {
+        secure_store::fetch_api_key("deepgram")
+    } else {
+        settings
+            .deepgram_api_key
+            .as_ref()
+            .filter(|k| !k.trim().is_empty())
+            .cloned()
+    }
+}
+
+/// Nova-3 caps prompting at 500 tokens across all key terms, so long
+/// custom word lists are cut off rather than rejected.
+const MAX_KEY_TERMS: usize = 100;
+
+/// Query parameters for `/v1/listen` that come from settings. Serialized
+/// as-is into requests relayed through the backend, which passes them on.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DeepgramOptions {
+    smart_format: bool,
+    punctuate: bool,
+    numerals: bool,
+    filler_words: bool,
+    diarize: bool,
+    #[serde(rename = "keyterms", skip_serializing_if = "Vec::is_empty")]
+    key_terms: Vec<String>,
+}
+
+impl DeepgramOptions {
+    pub(super) fn from_settings(settings: &AppSettings) -> Self {
+        let key_terms = settings
+            .custom_words
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .take(MAX_KEY_TERMS)
+            .map(|word| word.to_string())
+            .collect();
+
+        Self {
+            smart_format: settings.deepgram_smart_format,
+            punctuate: settings.deepgram_punctuate,
+            numerals: settings.deepgram_numerals,
+            filler_words: settings.deepgram_filler_words,
+            diarize: settings.diarization_enabled,
+            key_terms,
+        }
+    }
+
+    /// Nova-3 takes custom vocabulary as `keyterm` and rejects `keywords`,
+    /// which only the older models understand.
+    pub(super) fn append_to(&self, url: &mut reqwest::Url, model: &str) {
+        let mut pairs = url.query_pairs_mut();
+        let flag = |enabled: bool| if enabled { "true" } else { "false" };
+        pairs.append_pair("smart_format", flag(self.smart_format));
+        pairs.append_pair("punctuate", flag(self.punctuate));
+        pairs.append_pair("numerals", flag(self.numerals));
+        pairs.append_pair("filler_words", flag(self.filler_words));
+        pairs.append_pair("diarize", flag(self.diarize));
+
+        let key_param = if model.starts_with("nova-3") {
+            "keyterm"
+        } else {
+            "keywords"
+        };
+        for term in &self.key_terms {
+            pairs.append_pair(key_param, term);
+        }
+    }
+}
+
+fn first_alternative(json: &serde_json::Value) -> Option<&serde_json::Value> {
+    json.get("results")
+        .and_then(|r| r.get("channels"))
+        .and_then(|c| c.get(0))
+        .and_then(|ch| ch.get("alternatives"))
+        .and_then(|alts| alts.get(0))
+}
+
+/// Reads one entry of an alternative's `words`, preferring the punctuated
+/// form that smart formatting and punctuation produce.
+fn parse_deepgram_word(word: &serde_json::Value) -> Option<TranscriptWord> {
+    let text = word
+        .get("punctuated_word")
+        .or_else(|| word.get("word"))
+        .and_then(|w| w.as_str())?;
+
+    Some(TranscriptWord {
+        word: text.to_string(),
+        start: word.get("start").and_then(|s| s.as_f64()).unwrap_or(0.0),
+        end: word.get("end").and_then(|e| e.as_f64()).unwrap_or(0.0),
+        confidence: word
+            .get("confidence")
+            .and_then(|c| c.as_f64())
+            .map(|c| c as f32),
+        speaker: word
+            .get("speaker")
+            .and_then(|s| s.as_u64())
+            .map(|s| s as u32),
+    })
+}
+
+pub(super) fn parse_deepgram_alternative(alternative: &serde_json::Value) -> Option<Transcript> {
+    let text = alternative.get("transcript").and_then(|t| t.as_str())?;
+    let words = alternative
+        .get("words")
+        .and_then(|words| words.as_array())
+        .map(|words| words.iter().filter_map(parse_deepgram_word).collect())
+        .unwrap_or_default();
+
+    Some(Transcript {
+        text: text.to_string(),
+        confidence: alternative
+            .get("confidence")
+            .and_then(|c| c.as_f64())
+            .map(|c| c as f32),
+        words,
+        segments: Vec::new(),
+        translated: false,
+        source_text: None,
+    })
+}
+
+pub(super) async fn transcribe_deepgram_direct(
+    audio: &EncodedAudio,
+    api_key: String,
+    model: Option<String>,
+    language: Option<String>,
+    detect_languages: Vec<String>,
+    options: DeepgramOptions,
+    timeout: Duration,
+) -> Result<Transcript, RequestError> {
+    // Prefer provided model; default to nova-3, but fall back to nova-2 on 403
+    let primary_model = model.unwrap_or_else(|| "nova-3".to_string());
+    let mut models_to_try = vec![primary_model.clone()];
+    if primary_model.starts_with("nova-3") {
+        models_to_try.push("nova-2".to_string());
+    }
+
+    let mut last_error = None;
+
+    for model_name in models_to_try {
+        let mut url = reqwest::Url::parse("https://api.deepgram.com/v1/listen")
+            .map_err(|e| format!("Failed to parse Deepgram URL: {}", e))?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            if let Some(lang) = language.as_ref() {
+                pairs.append_pair("language", lang);
+            }
+            for lang in &detect_languages {
+                pairs.append_pair("detect_language", lang);
+            }
+            pairs.append_pair("model", &model_name);
+        }
+        options.append_to(&mut url, &model_name);
+
+        let started = Instant::now();
+        let response = http::send(Operation::transcription(timeout), || {
+            http::client()
+                .post(url.clone())
+                .header("Authorization", format!("Token {}", api_key))
+                .header("Content-Type", audio.format.mime())
+                .body(audio.bytes.clone())
+        })
+        .await?;
+        debug!(
+            "Uploaded {:.1} KB of {} to Deepgram in {:?}",
+            audio.size_kb(),
+            audio.format.extension(),
+            started.elapsed()
+        );
+
+        let status = response.status();
+        let text_body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read Deepgram response: {}", e))?;
+
+        if status.is_success() {
+            let json: serde_json::Value = serde_json::from_str(&text_body)
+                .map_err(|e| format!("Failed to parse Deepgram response JSON: {}", e))?;
+            let transcript = first_alternative(&json)
+                .and_then(parse_deepgram_alternative)
+                .ok_or_else(|| "Deepgram response missing transcript".to_string())?;
+
+            debug!(
+                "Deepgram direct transcript length: {}, duration: {:?}, confidence: {:?}, speakers: {} (model: {})",
+                transcript.text.len(),
+                transcript.words.last().map(|word| word.end),
+                transcript.confidence,
+                transcript.speaker_count(),
+                model_name
+            );
+
+            return Ok(transcript);
+        }
+
+        // Gateways in front of the API may answer with plain text or HTML
+        let err_msg = serde_json::from_str::<serde_json::Value>(&text_body)
+            .ok()
+            .and_then(|json| {
+                json.get("err_msg")
+                    .or_else(|| json.get("error"))
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+            })
+            .unwrap_or_else(|| "Unknown Deepgram error".to_string());
+
+        if status == StatusCode::FORBIDDEN && model_name.starts_with("nova-3") {
+            warn!(
+                "Deepgram denied access to model '{}', retrying with nova-2",
+                model_name
+            );
+            last_error = Some(RequestError::from_status(
+                status,
+                format!("Deepgram transcription failed ({}): {}", status, err_msg),
+            ));
+            continue;
+        } else {
+            return Err(RequestError::from_status(
+                status,
+                format!("Deepgram transcription failed ({}): {}", status, err_msg),
+            ));
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| "Deepgram transcription failed".into()))
+}
+
+pub(super) fn deepgram_model_from_settings(settings: &AppSettings) -> String {
+    let selected = settings.deepgram_model.trim();
+    if selected.is_empty() {
+        return "nova-3".to_string();
+    }
+    let lowered = selected.to_lowercase();
+
+    if lowered.starts_with("nova") || lowered.starts_with("general") {
+        selected.to_string()
+    } else {
+        warn!(
+            "Selected model '{}' is not a Deepgram model; defaulting to nova-3",
+            selected
+        );
+        "nova-3".to_string()
+    }
+}
@@ -0,0 +1,450 @@
+//! Live transcription over Deepgram's streaming `/v1/listen` WebSocket, so
+//! the transcript is ready moments after the recording stops instead of
+//! after a full upload.
+
+use super::deepgram::{
+    deepgram_api_key, deepgram_model_from_settings, parse_deepgram_alternative, DeepgramOptions,
+};
+use super::Transcript;
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::settings::{AppSettings, TranscriptionProvider};
+use futures_util::{future, SinkExt, StreamExt};
+use log::{debug, warn};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+
+const DEEPGRAM_LIVE_URL: &str = "wss://api.deepgram.com/v1/listen";
+
+/// Asks Deepgram to flush the remaining audio and close the socket once
+/// the last results are sent.
+const CLOSE_STREAM: &str = r#"{"type":"CloseStream"}"#;
+
+/// Feeds 16 kHz frames to a live session. The session ends once every
+/// sender is dropped.
+pub type FrameSender = mpsc::UnboundedSender<Vec<f32>>;
+
+/// A running live session, to be finished when the recording stops.
+/// Dropping it closes the session, e.g. when the recording is cancelled.
+pub struct LiveTranscription {
+    model: String,
+    url: reqwest::Url,
+    task: tauri::async_runtime::JoinHandle<Result<Transcript, String>>,
+}
+
+impl LiveTranscription {
+    /// Recorded in history like `TranscriptionBackend::id`.
+    pub fn backend_id(&self) -> String {
+        format!("deepgram-live:{}", self.model)
+    }
+
+    /// Whether `settings` would stream to the same model with the same
+    /// language and options, e.g. after an app profile was applied.
+    pub fn matches(&self, settings: &AppSettings) -> bool {
+        session_url(settings).is_some_and(|(_, url)| url == self.url)
+    }
+
+    /// Waits for Deepgram's final results, which arrive shortly after the
+    /// frame senders are dropped.
+    pub async fn finish(mut self) -> Result<Transcript, String> {
+        (&mut self.task)
+            .await
+            .map_err(|e| format!("Live transcription task failed: {}", e))?
+    }
+}
+
+impl Drop for LiveTranscription {
+    fn drop(&mut self) {
+        // Closes the socket if the session is still running, so a cancelled
+        // recording stops streaming and sends no more interim text
+        self.task.abort();
+    }
+}
+
+/// Opens a live session when streaming is enabled for Deepgram and a key
+/// is stored for direct requests. `on_interim` gets the transcript so far
+/// each time Deepgram revises it.
+pub fn start_live_transcription<F>(
+    settings: &AppSettings,
+    on_interim: F,
+) -> Option<(FrameSender, LiveTranscription)>
+where
+    F: Fn(&str) + Send + Sync + 'static,
+{
+    let (model, url) = session_url(settings)?;
+    let Some(api_key) = deepgram_api_key(settings) else {
+        debug!("Deepgram streaming skipped because no API key is stored");
+        return None;
+    };
+
+    let (frames_tx, frames_rx) = mpsc::unbounded_channel();
+    let task = tauri::async_runtime::spawn(run_live_session(
+        url.clone(),
+        api_key,
+        frames_rx,
+        on_interim,
+    ));
+    Some((frames_tx, LiveTranscription { model, url, task }))
+}
+
+/// The model and streaming URL for these settings, or `None` when they
+/// don't stream.
+fn session_url(settings: &AppSettings) -> Option<(String, reqwest::Url)> {
+    if settings.provider != TranscriptionProvider::Deepgram
+        || !settings.deepgram_streaming
+        || settings.uses_backend_relay()
+    {
+        return None;
+    }
+
+    let model = deepgram_model_from_settings(settings);
+    match live_url(
+        DEEPGRAM_LIVE_URL,
+        &model,
+        settings.forced_language(),
+        &DeepgramOptions::from_settings(settings),
+    ) {
+        Ok(url) => Some((model, url)),
+        Err(e) => {
+            warn!("{}", e);
+            None
+        }
+    }
+}
+
+fn live_url(
+    base: &str,
+    model: &str,
+    language: Option<&str>,
+    options: &DeepgramOptions,
+) -> Result<reqwest::Url, String> {
+    let mut url = reqwest::Url::parse(base)
+        .map_err(|e| format!("Failed to parse Deepgram streaming URL: {}", e))?;
+    {
+        let sample_rate = WHISPER_SAMPLE_RATE.to_string();
+        let mut pairs = url.query_pairs_mut();
+        pairs.append_pair("model", model);
+        pairs.append_pair("encoding", "linear16");
+        pairs.append_pair("sample_rate", &sample_rate);
+        pairs.append_pair("channels", "1");
+        pairs.append_pair("interim_results", "true");
+        if let Some(language) = language {
+            pairs.append_pair("language", language);
+        }
+    }
+    options.append_to(&mut url, model);
+    Ok(url)
+}
+
+fn pcm16_bytes(frame: &[f32]) -> Vec<u8> {
+    frame
+        .iter()
+        .flat_map(|sample| {
+            let clamped = (sample * i16::MAX as f32).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+            clamped.to_le_bytes()
+        })
+        .collect()
+}
+
+/// Joins the finalized segments of a session into one transcript.
+fn join_segments(segments: Vec<Transcript>) -> Transcript {
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let confidences: Vec<f32> = segments.iter().filter_map(|s| s.confidence).collect();
+    let confidence = (!confidences.is_empty())
+        .then(|| confidences.iter().sum::<f32>() / confidences.len() as f32);
+    let words = segments.into_iter().flat_map(|s| s.words).collect();
+
+    Transcript {
+        text,
+        confidence,
+        words,
+        segments: Vec::new(),
+        translated: false,
+        source_text: None,
+    }
+}
+
+/// Streams frames as linear16 PCM until `frames` closes, then collects the
+/// finalized results Deepgram sends before closing the socket.
+async fn run_live_session<F>(
+    url: reqwest::Url,
+    api_key: String,
+    mut frames: mpsc::UnboundedReceiver<Vec<f32>>,
+    on_interim: F,
+) -> Result<Transcript, String>
+where
+    F: Fn(&str),
+{
+    let mut request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| format!("Failed to build Deepgram streaming request: {}", e))?;
+    let auth = format!("Token {}", api_key)
+        .parse()
+        .map_err(|_| "Deepgram API key is not a valid header value".to_string())?;
+    request.headers_mut().insert("Authorization", auth);
+
+    let (socket, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .map_err(|e| format!("Deepgram streaming connection failed: {}", e))?;
+    let (mut sink, mut stream) = socket.split();
+
+    let send = async {
+        while let Some(frame) = frames.recv().await {
+            sink.send(Message::Binary(pcm16_bytes(&frame)))
+                .await
+                .map_err(|e| format!("Failed to send audio to Deepgram: {}", e))?;
+        }
+        sink.send(Message::Text(CLOSE_STREAM.to_string()))
+            .await
+            .map_err(|e| format!("Failed to close Deepgram stream: {}", e))
+    };
+
+    let receive = async {
+        let mut finals = Vec::new();
+        while let Some(message) = stream.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => return Err(format!("Deepgram stream failed: {}", e)),
+            };
+            let json: serde_json::Value = match serde_json::from_str(&text) {
+                Ok(json) => json,
+                Err(e) => {
+                    warn!("Ignoring unparseable Deepgram stream message: {}", e);
+                    continue;
+                }
+            };
+            if json.get("type").and_then(|t| t.as_str()) != Some("Results") {
+                continue;
+            }
+            let Some(segment) = json
+                .get("channel")
+                .and_then(|c| c.get("alternatives"))
+                .and_then(|alts| alts.get(0))
+                .and_then(parse_deepgram_alternative)
+            else {
+                continue;
+            };
+
+            let is_final = json
+                .get("is_final")
+                .and_then(|f| f.as_bool())
+                .unwrap_or(false);
+            let interim = if is_final {
+                finals.push(segment);
+                None
+            } else {
+                Some(segment)
+            };
+
+            let so_far = join_segments(finals.iter().cloned().chain(interim).collect());
+            if !so_far.text.is_empty() {
+                on_interim(&so_far.text);
+            }
+        }
+        Ok(finals)
+    };
+
+    let (sent, received) = future::join(send, receive).await;
+    let finals = received?;
+    if let Err(e) = sent {
+        // Results that arrived before the socket broke are still usable
+        if finals.is_empty() {
+            return Err(e);
+        }
+        warn!("{}", e);
+    }
+
+    let transcript = join_segments(finals);
+    debug!(
+        "Deepgram live transcript length: {}, confidence: {:?}, speakers: {}",
+        transcript.text.len(),
+        transcript.confidence,
+        transcript.speaker_count()
+    );
+    Ok(transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    fn results(transcript: &str, is_final: bool) -> Message {
+        Message::Text(
+            serde_json::json!({
+                "type": "Results",
+                "is_final": is_final,
+                "channel": {
+                    "alternatives": [{
+                        "transcript": transcript,
+                        "confidence": 0.9,
+                        "words": [],
+                    }],
+                },
+            })
+            .to_string(),
+        )
+    }
+
+    /// Stands in for Deepgram: answers the first audio frame with an interim
+    /// result and sends the final one when the client closes the stream.
+    /// Returns the number of audio frames and the authorization header seen.
+    #[allow(clippy::result_large_err)] // the handshake callback's signature
+    async fn serve_once(listener: TcpListener) -> (usize, Option<String>) {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut auth = None;
+        let mut socket =
+            tokio_tungstenite::accept_hdr_async(tcp, |request: &Request, response: Response| {
+                auth = request
+                    .headers()
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| value.to_string());
+                Ok(response)
+            })
+            .await
+            .unwrap();
+
+        let mut frames = 0;
+        while let Some(Ok(message)) = socket.next().await {
+            match message {
+                Message::Binary(_) => {
+                    frames += 1;
+                    if frames == 1 {
+                        socket.send(results("hello", false)).await.unwrap();
+                    }
+                }
+                Message::Text(text) if text == CLOSE_STREAM => {
+                    socket.send(results("hello world.", true)).await.unwrap();
+                    socket.close(None).await.unwrap();
+                    break;
+                }
+                _ => {}
+            }
+        }
+        (frames, auth)
+    }
+
+    #[tokio::test]
+    async fn streams_frames_and_returns_final_results() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = reqwest::Url::parse(&format!(
+            "ws://{}/v1/listen",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        let server = tokio::spawn(serve_once(listener));
+
+        let (frames_tx, frames_rx) = mpsc::unbounded_channel();
+        for _ in 0..3 {
+            frames_tx.send(vec![0.1; 480]).unwrap();
+        }
+        drop(frames_tx);
+
+        let interims = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&interims);
+        let transcript = run_live_session(url, "test-key".to_string(), frames_rx, move |text| {
+            seen.lock().unwrap().push(text.to_string())
+        })
+        .await
+        .unwrap();
+
+        let (frames, auth) = server.await.unwrap();
+        assert_eq!(frames, 3);
+        assert_eq!(auth.as_deref(), Some("Token test-key"));
+        assert_eq!(transcript.text, "hello world.");
+        assert_eq!(transcript.confidence, Some(0.9));
+        assert_eq!(*interims.lock().unwrap(), vec!["hello", "hello world."]);
+    }
+
+    #[tokio::test]
+    async fn dropping_the_session_closes_the_socket() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = reqwest::Url::parse(&format!(
+            "ws://{}/v1/listen",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        let server = tokio::spawn(serve_once(listener));
+
+        // The sender stays open, so only dropping the session can end it
+        let (frames_tx, frames_rx) = mpsc::unbounded_channel();
+        frames_tx.send(vec![0.1; 480]).unwrap();
+        let (interim_tx, mut interim_rx) = mpsc::unbounded_channel();
+        let live = LiveTranscription {
+            model: "nova-3".to_string(),
+            url: url.clone(),
+            task: tauri::async_runtime::spawn(run_live_session(
+                url,
+                "test-key".to_string(),
+                frames_rx,
+                move |text: &str| {
+                    let _ = interim_tx.send(text.to_string());
+                },
+            )),
+        };
+        assert_eq!(interim_rx.recv().await.as_deref(), Some("hello"));
+
+        drop(live);
+        let (frames, _) = tokio::time::timeout(std::time::Duration::from_secs(5), server)
+            .await
+            .expect("the socket stayed open")
+            .unwrap();
+        assert_eq!(frames, 1);
+        assert!(interim_rx.recv().await.is_none());
+        drop(frames_tx);
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let url = reqwest::Url::parse(&format!("ws://{}/v1/listen", addr)).unwrap();
+        let (_frames_tx, frames_rx) = mpsc::unbounded_channel();
+        let result = run_live_session(url, "test-key".to_string(), frames_rx, |_| {}).await;
+
+        assert!(result.unwrap_err().contains("connection failed"));
+    }
+
+    #[test]
+    fn matches_settings_that_stream_the_same_way() {
+        let mut settings = crate::settings::get_default_settings();
+        settings.provider = TranscriptionProvider::Deepgram;
+        settings.deepgram_streaming = true;
+        let (model, url) = session_url(&settings).unwrap();
+        let live = LiveTranscription {
+            model,
+            url,
+            task: tauri::async_runtime::spawn(async { Err(String::new()) }),
+        };
+        assert!(live.matches(&settings));
+
+        let mut french = settings.clone();
+        french.allowed_languages = vec!["fr".to_string()];
+        assert!(!live.matches(&french));
+
+        let mut local = settings.clone();
+        local.provider = TranscriptionProvider::Local;
+        assert!(!live.matches(&local));
+    }
+
+    #[test]
+    fn encodes_frames_as_little_endian_pcm16() {
+        assert_eq!(
+            pcm16_bytes(&[0.0, 1.0, -2.0]),
+            vec![0, 0, 0xff, 0x7f, 0, 0x80]
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use super::{Transcript, TranscriptionBackend, TranscriptionRequest};
+use crate::audio_toolkit::diarization::{self, SpeakerSegment};
+use crate::cancellation::CancellationToken;
+use crate::http::RequestError;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{AppSettings, ModelUnloadTimeout};
+use futures_util::future::BoxFuture;
+use log::debug;
+use std::sync::Arc;
+
+/// On-device transcription through the shared `TranscriptionManager`.
+pub struct LocalBackend {
+    tm: Arc<TranscriptionManager>,
+    model: Option<String>,
+}
+
+impl LocalBackend {
+    /// `model` replaces the selected model, e.g. to fall back to a smaller
+    /// local model than the one used for dictation.
+    pub fn new(tm: Arc<TranscriptionManager>, model: Option<String>) -> Self {
+        Self { tm, model }
+    }
+
+    fn model_id<'a>(&'a self, settings: &'a AppSettings) -> &'a str {
+        self.model.as_deref().unwrap_or(&settings.selected_model)
+    }
+}
+
+impl TranscriptionBackend for LocalBackend {
+    fn id(&self, settings: &AppSettings) -> String {
+        format!("local:{}", self.model_id(settings))
+    }
+
+    fn is_remote(&self) -> bool {
+        false
+    }
+
+    /// Runs inference on a blocking thread so a cancel can return right
+    /// away instead of waiting for the model to finish.
+    fn transcribe<'a>(
+        &'a self,
+        request: TranscriptionRequest<'a>,
+    ) -> BoxFuture<'a, Result<Transcript, RequestError>> {
+        Box::pin(async move {
+            let tm = Arc::clone(&self.tm);
+            let model_id = self.model_id(request.settings).to_string();
+            let samples = request.samples.to_vec();
+            let settings = request.settings.clone();
+            let task_cancel = request.cancel.clone();
+
+            // A fallback model stands in for this recording only
+            let restore = self
+                .model
+                .as_ref()
+                .and_then(|_| tm.get_current_model())
+                .filter(|current| *current != model_id);
+
+            let task = tauri::async_runtime::spawn_blocking(move || {
+                let result = transcribe_loaded(&*tm, &model_id, samples, &settings, &task_cancel);
+                let unloaded = settings.model_unload_timeout == ModelUnloadTimeout::Immediately;
+                if let Some(previous) = restore.filter(|_| !unloaded) {
+                    debug!("Reloading {} after falling back to {}", previous, model_id);
+                    tm.initiate_model_load(&previous);
+                }
+                result
+            });
+
+            request
+                .cancel
+                .run_until_cancelled(task)
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string().into())
+        })
+    }
+}
+
+/// The parts of `TranscriptionManager` a local transcription uses.
+trait LocalModel {
+    fn ensure_model(&self, model_id: &str, cancel: &CancellationToken) -> anyhow::Result<()>;
+    fn supports_translation(&self, model_id: &str) -> bool;
+    fn transcribe_with_settings(
+        &self,
+        audio: Vec<f32>,
+        settings: &AppSettings,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<String>;
+    fn release_after_transcription(&self, settings: &AppSettings);
+}
+
+impl LocalModel for TranscriptionManager {
+    fn ensure_model(&self, model_id: &str, cancel: &CancellationToken) -> anyhow::Result<()> {
+        TranscriptionManager::ensure_model(self, model_id, cancel)
+    }
+
+    fn supports_translation(&self, model_id: &str) -> bool {
+        TranscriptionManager::supports_translation(self, model_id)
+    }
+
+    fn transcribe_with_settings(
+        &self,
+        audio: Vec<f32>,
+        settings: &AppSettings,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<String> {
+        TranscriptionManager::transcribe_with_settings(self, audio, settings, cancel)
+    }
+
+    fn release_after_transcription(&self, settings: &AppSettings) {
+        TranscriptionManager::release_after_transcription(self, settings)
+    }
+}
+
+/// Loads `model_id`, runs every pass the recording needs, then lets the
+/// model go if it is set to unload immediately.
+fn transcribe_loaded(
+    tm: &impl LocalModel,
+    model_id: &str,
+    samples: Vec<f32>,
+    settings: &AppSettings,
+    cancel: &CancellationToken,
+) -> anyhow::Result<Transcript> {
+    tm.ensure_model(model_id, cancel)?;
+    let result = transcribe_blocking(tm, model_id, samples, settings, cancel);
+    tm.release_after_transcription(settings);
+    result
+}
+
+/// Transcribes with the loaded model, splitting speaker turns when
+/// diarization is on and keeping the spoken text when Whisper translated.
+fn transcribe_blocking(
+    tm: &impl LocalModel,
+    model_id: &str,
+    samples: Vec<f32>,
+    settings: &AppSettings,
+    cancel: &CancellationToken,
+) -> anyhow::Result<Transcript> {
+    let translated = settings.whisper_translate() && tm.supports_translation(model_id);
+    let turns = if settings.diarization_enabled {
+        transcribe_turns(tm, &samples, settings, cancel)?
+    } else {
+        None
+    };
+    let transcript = match turns {
+        Some(transcript) => transcript,
+        None => Transcript::from(tm.transcribe_with_settings(samples.clone(), settings, cancel)?),
+    };
+
+    // Whisper either translates or transcribes, so the spoken text takes a
+    // second pass, made only when asked for since it delays the paste
+    let source_text = if translated && settings.translation_keep_source_text {
+        Some(tm.transcribe_with_settings(samples, &settings.without_translation(), cancel)?)
+    } else {
+        None
+    };
+    Ok(Transcript {
+        translated,
+        source_text,
+        ..transcript
+    })
+}
+
+/// Splits the recording into speaker turns and transcribes each on its
+/// own. Returns `None` when only one speaker is found, so the recording is
+/// transcribed whole.
+fn transcribe_turns(
+    tm: &impl LocalModel,
+    samples: &[f32],
+    settings: &AppSettings,
+    cancel: &CancellationToken,
+) -> anyhow::Result<Option<Transcript>> {
+    let turns = diarization::diarize(samples, settings.diarization_max_speakers as usize);
+    if turns.len() < 2 {
+        return Ok(None);
+    }
+    debug!("Transcribing {} speaker turns separately", turns.len());
+
+    let mut segments = Vec::with_capacity(turns.len());
+    for turn in turns {
+        let text =
+            tm.transcribe_with_settings(samples[turn.start..turn.end].to_vec(), settings, cancel)?;
+        if text.trim().is_empty() {
+            continue;
+        }
+        segments.push(SpeakerSegment {
+            speaker: turn.speaker,
+            start: turn.start_secs(),
+            end: turn.end_secs(),
+            text: text.trim().to_string(),
+        });
+    }
+
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(Some(Transcript {
+        text,
+        segments,
+        ..Default::default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+    use std::sync::Mutex;
+
+    /// Stands in for the manager: transcribes only while "loaded" and
+    /// records each pass.
+    #[derive(Default)]
+    struct FakeModel {
+        loaded: Mutex<bool>,
+        passes: Mutex<Vec<bool>>,
+    }
+
+    impl LocalModel for FakeModel {
+        fn ensure_model(&self, _model_id: &str, _cancel: &CancellationToken) -> anyhow::Result<()> {
+            *self.loaded.lock().unwrap() = true;
+            Ok(())
+        }
+
+        fn supports_translation(&self, _model_id: &str) -> bool {
+            true
+        }
+
+        fn transcribe_with_settings(
+            &self,
+            _audio: Vec<f32>,
+            settings: &AppSettings,
+            _cancel: &CancellationToken,
+        ) -> anyhow::Result<String> {
+            if !*self.loaded.lock().unwrap() {
+                anyhow::bail!("Model is not loaded for transcription.");
+            }
+            let translate = settings.whisper_translate();
+            self.passes.lock().unwrap().push(translate);
+            Ok(if translate { "hello" } else { "hallo" }.to_string())
+        }
+
+        fn release_after_transcription(&self, settings: &AppSettings) {
+            if settings.model_unload_timeout == ModelUnloadTimeout::Immediately {
+                *self.loaded.lock().unwrap() = false;
+            }
+        }
+    }
+
+    #[test]
+    fn unloads_immediately_only_after_every_pass() {
+        let mut settings = get_default_settings();
+        settings.model_unload_timeout = ModelUnloadTimeout::Immediately;
+        settings.translation_target_language = "en".to_string();
+        settings.translation_keep_source_text = true;
+        let model = FakeModel::default();
+
+        let transcript = transcribe_loaded(
+            &model,
+            "small",
+            vec![0.0; 16000],
+            &settings,
+            &CancellationToken::new(),
+        )
+        .unwrap();
+
+        assert_eq!(*model.passes.lock().unwrap(), vec![true, false]);
+        assert_eq!(transcript.text, "hello");
+        assert_eq!(transcript.source_text.as_deref(), Some("hallo"));
+        assert!(!*model.loaded.lock().unwrap());
+    }
+
+    #[test]
+    fn keeps_the_spoken_text_only_when_asked() {
+        let mut settings = get_default_settings();
+        settings.translation_target_language = "en".to_string();
+        let model = FakeModel::default();
+
+        let transcript = transcribe_loaded(
+            &model,
+            "small",
+            vec![0.0; 16000],
+            &settings,
+            &CancellationToken::new(),
+        )
+        .unwrap();
+
+        assert_eq!(*model.passes.lock().unwrap(), vec![true]);
+        assert!(transcript.translated);
+        assert_eq!(transcript.source_text, None);
+    }
+}
@@ -0,0 +1,473 @@
+//! Transcription backends. Local engines and cloud providers share one
+//! interface so a recording can fall through an ordered chain of them.
+
+mod deepgram;
+mod deepgram_live;
+mod local;
+mod openai;
+mod remote;
+mod upload;
+
+use crate::audio_toolkit::diarization::{self, SpeakerSegment};
+use crate::cancellation::CancellationToken;
+use crate::http::{ErrorKind, RequestError};
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{AppSettings, TranscriptionProvider};
+use futures_util::future::BoxFuture;
+use log::{debug, warn};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+pub use deepgram_live::{start_live_transcription, FrameSender, LiveTranscription};
+pub use local::LocalBackend;
+pub use remote::RemoteBackend;
+
+/// Everything a backend needs to transcribe one recording.
+#[derive(Clone, Copy)]
+pub struct TranscriptionRequest<'a> {
+    pub samples: &'a [f32],
+    pub settings: &'a AppSettings,
+    pub cancel: &'a CancellationToken,
+}
+
+/// A recognised word with its timing, for providers that report them.
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptWord {
+    pub word: String,
+    /// Seconds from the start of the recording
+    pub start: f64,
+    pub end: f64,
+    pub confidence: Option<f32>,
+    /// Zero-based speaker index when diarization is enabled
+    pub speaker: Option<u32>,
+}
+
+/// What a backend recognised. Only `text` is always present; local engines
+/// and OpenAI-compatible servers leave the rest empty.
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    pub text: String,
+    pub confidence: Option<f32>,
+    pub words: Vec<TranscriptWord>,
+    /// Speaker turns from local diarization. Providers that label words
+    /// leave this empty and the turns are built from the words.
+    pub segments: Vec<SpeakerSegment>,
+    /// Set when the engine already translated the speech into English
+    pub translated: bool,
+    /// The transcription in the spoken language when `text` is a
+    /// translation and the backend could produce both
+    pub source_text: Option<String>,
+}
+
+impl Transcript {
+    /// Speaker turns, or none when the transcript isn't diarized.
+    pub fn speaker_segments(&self) -> Vec<SpeakerSegment> {
+        if !self.segments.is_empty() {
+            return self.segments.clone();
+        }
+        if self.words.iter().all(|w| w.speaker.is_none()) {
+            return Vec::new();
+        }
+        diarization::segments_from_words(
+            self.words
+                .iter()
+                .map(|w| (w.word.as_str(), w.start, w.end, w.speaker)),
+        )
+    }
+
+    /// Number of distinct speakers, zero without diarization.
+    pub fn speaker_count(&self) -> usize {
+        diarization::speaker_count(&self.speaker_segments())
+    }
+}
+
+impl From<String> for Transcript {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            ..Default::default()
+        }
+    }
+}
+
+pub trait TranscriptionBackend: Send + Sync {
+    /// Label recorded with the history entry, e.g. `local:parakeet-tdt-0.6b-v3`
+    /// or `deepgram:nova-3`.
+    fn id(&self, settings: &AppSettings) -> String;
+
+    /// Remote backends are abandoned after `remote_transcription_timeout_secs`
+    /// so a hanging connection falls through to the next backend.
+    fn is_remote(&self) -> bool;
+
+    fn transcribe<'a>(
+        &'a self,
+        request: TranscriptionRequest<'a>,
+    ) -> BoxFuture<'a, Result<Transcript, RequestError>>;
+}
+
+/// Result of the first backend in the chain that succeeded.
+pub struct BackendTranscription {
+    pub transcript: Transcript,
+    /// `TranscriptionBackend::id` of the backend that produced `transcript`
+    pub backend: String,
+    /// Whether that backend was a remote service
+    pub remote: bool,
+}
+
+fn backend_for(
+    app: &AppHandle,
+    provider: TranscriptionProvider,
+    model: Option<String>,
+) -> Box<dyn TranscriptionBackend> {
+    match provider {
+        TranscriptionProvider::Local => {
+            let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+            Box::new(LocalBackend::new(tm, model))
+        }
+        _ => Box::new(RemoteBackend::new(provider, model)),
+    }
+}
+
+/// The selected provider followed by the configured fallbacks, skipping
+/// any that repeat an earlier backend.
+pub fn build_chain(app: &AppHandle, settings: &AppSettings) -> Vec<Box<dyn TranscriptionBackend>> {
+    chain_from(settings, |provider, model| {
+        backend_for(app, provider, model)
+    })
+}
+
+/// `build_chain` with the backends made by `make`.
+fn chain_from(
+    settings: &AppSettings,
+    make: impl Fn(TranscriptionProvider, Option<String>) -> Box<dyn TranscriptionBackend>,
+) -> Vec<Box<dyn TranscriptionBackend>> {
+    let mut chain = vec![make(settings.provider, None)];
+    let mut ids = vec![chain[0].id(settings)];
+
+    for fallback in &settings.transcription_fallbacks {
+        let model = fallback
+            .model
+            .clone()
+            .filter(|model| !model.trim().is_empty());
+        let backend = make(fallback.provider, model);
+        let id = backend.id(settings);
+        if !ids.contains(&id) {
+            ids.push(id);
+            chain.push(backend);
+        }
+    }
+    chain
+}
+
+/// Tries each backend in turn until one returns text. Errors, timeouts and
+/// unreachable servers move on to the next backend; a cancel stops the chain.
+pub async fn transcribe_with_fallback(
+    chain: &[Box<dyn TranscriptionBackend>],
+    request: TranscriptionRequest<'_>,
+) -> Result<BackendTranscription, RequestError> {
+    let timeout = request.settings.remote_transcription_timeout();
+    let mut errors = Vec::new();
+
+    for backend in chain {
+        request.cancel.check().map_err(|e| e.to_string())?;
+
+        let id = backend.id(request.settings);
+        let attempt = backend.transcribe(request);
+        let result = if backend.is_remote() {
+            tokio::time::timeout(timeout, attempt)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(RequestError::new(
+                        ErrorKind::Timeout,
+                        format!("Timed out after {}s", timeout.as_secs()),
+                    ))
+                })
+        } else {
+            attempt.await
+        };
+
+        match result {
+            Ok(transcript) => {
+                if !errors.is_empty() {
+                    debug!("Transcribed via fallback backend {}", id);
+                }
+                return Ok(BackendTranscription {
+                    transcript,
+                    backend: id,
+                    remote: backend.is_remote(),
+                });
+            }
+            Err(e) if request.cancel.is_cancelled() => return Err(e),
+            Err(e) => {
+                warn!("Transcription via {} failed: {}", id, e);
+                errors.push((id, e));
+            }
+        }
+    }
+
+    // The overlay names the first failure it can explain, such as the
+    // network being down, rather than a backend's unclassified error
+    let kind = errors
+        .iter()
+        .map(|(_, error)| error.kind)
+        .find(|kind| *kind != ErrorKind::Other)
+        .unwrap_or(ErrorKind::Other);
+    Err(match errors.as_slice() {
+        [] => "No transcription backend is configured".into(),
+        [(_, error)] => error.clone(),
+        _ => RequestError::new(
+            kind,
+            errors
+                .iter()
+                .map(|(id, error)| format!("{}: {}", id, error))
+                .collect::<Vec<_>>()
+                .join("; "),
+        ),
+    })
+}
+
+/// Finishes a live session started with the recording, falling back to the
+/// chain with the recorded samples if the stream failed or stalled.
+pub async fn finish_live_with_fallback(
+    live: LiveTranscription,
+    chain: &[Box<dyn TranscriptionBackend>],
+    request: TranscriptionRequest<'_>,
+) -> Result<BackendTranscription, RequestError> {
+    let timeout = request.settings.remote_transcription_timeout();
+    let backend = live.backend_id();
+
+    let result = request
+        .cancel
+        .run_until_cancelled(tokio::time::timeout(timeout, live.finish()))
+        .await
+        .map_err(|e| e.to_string())?
+        .unwrap_or_else(|_| Err(format!("Timed out after {}s", timeout.as_secs())));
+
+    match result {
+        Ok(transcript) => Ok(BackendTranscription {
+            transcript,
+            backend,
+            remote: true,
+        }),
+        Err(e) => {
+            warn!(
+                "Live transcription via {} failed, transcribing the recording instead: {}",
+                backend, e
+            );
+            transcribe_with_fallback(chain, request).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancellation::CancellationToken;
+    use crate::settings::{get_default_settings, FallbackBackend};
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    /// Stands in for a provider: records that it was called, waits
+    /// `delay` and returns `result`.
+    struct FakeBackend {
+        id: String,
+        remote: bool,
+        delay: Duration,
+        result: Result<String, RequestError>,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl FakeBackend {
+        fn boxed(
+            id: &str,
+            result: Result<&str, RequestError>,
+            calls: &Arc<Mutex<Vec<String>>>,
+        ) -> Box<dyn TranscriptionBackend> {
+            Box::new(Self {
+                id: id.to_string(),
+                remote: true,
+                delay: Duration::ZERO,
+                result: result.map(str::to_string),
+                calls: Arc::clone(calls),
+            })
+        }
+    }
+
+    impl TranscriptionBackend for FakeBackend {
+        fn id(&self, _settings: &AppSettings) -> String {
+            self.id.clone()
+        }
+
+        fn is_remote(&self) -> bool {
+            self.remote
+        }
+
+        fn transcribe<'a>(
+            &'a self,
+            _request: TranscriptionRequest<'a>,
+        ) -> BoxFuture<'a, Result<Transcript, RequestError>> {
+            Box::pin(async move {
+                self.calls.lock().unwrap().push(self.id.clone());
+                tokio::time::sleep(self.delay).await;
+                self.result.clone().map(Transcript::from)
+            })
+        }
+    }
+
+    fn fallback(provider: TranscriptionProvider, model: Option<&str>) -> FallbackBackend {
+        FallbackBackend {
+            provider,
+            model: model.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn chain_keeps_order_and_skips_repeated_backends() {
+        let mut settings = get_default_settings();
+        settings.provider = TranscriptionProvider::Deepgram;
+        settings.deepgram_model = "nova-3".to_string();
+        settings.transcription_fallbacks = vec![
+            fallback(TranscriptionProvider::Deepgram, None),
+            fallback(TranscriptionProvider::Deepgram, Some("nova-3")),
+            fallback(TranscriptionProvider::Deepgram, Some("  ")),
+            fallback(TranscriptionProvider::Local, Some("small")),
+            fallback(TranscriptionProvider::Deepgram, Some("nova-2")),
+            fallback(TranscriptionProvider::Local, Some("small")),
+        ];
+
+        // Ids follow `RemoteBackend` and `LocalBackend`: the model falls
+        // back to the one selected in settings
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let chain = chain_from(&settings, |provider, model| {
+            let id = match provider {
+                TranscriptionProvider::Local => format!("local:{}", model.unwrap_or_default()),
+                _ => format!(
+                    "deepgram:{}",
+                    model.unwrap_or_else(|| settings.deepgram_model.clone())
+                ),
+            };
+            FakeBackend::boxed(&id, Ok(""), &calls)
+        });
+
+        let ids: Vec<String> = chain.iter().map(|backend| backend.id(&settings)).collect();
+        assert_eq!(ids, ["deepgram:nova-3", "local:small", "deepgram:nova-2"]);
+    }
+
+    #[tokio::test]
+    async fn fallback_stops_at_the_first_backend_that_succeeds() {
+        let settings = get_default_settings();
+        let cancel = CancellationToken::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let chain = vec![
+            FakeBackend::boxed("a", Err(RequestError::offline()), &calls),
+            FakeBackend::boxed("b", Err("bad response".into()), &calls),
+            FakeBackend::boxed("c", Ok("hello"), &calls),
+            FakeBackend::boxed("d", Ok("unused"), &calls),
+        ];
+
+        let result = transcribe_with_fallback(
+            &chain,
+            TranscriptionRequest {
+                samples: &[],
+                settings: &settings,
+                cancel: &cancel,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.transcript.text, "hello");
+        assert_eq!(result.backend, "c");
+        assert_eq!(*calls.lock().unwrap(), ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn fallback_reports_whether_the_answer_came_from_a_remote_backend() {
+        let settings = get_default_settings();
+        let cancel = CancellationToken::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let chain: Vec<Box<dyn TranscriptionBackend>> = vec![
+            FakeBackend::boxed("remote", Err(RequestError::offline()), &calls),
+            Box::new(FakeBackend {
+                id: "local".to_string(),
+                remote: false,
+                delay: Duration::ZERO,
+                result: Ok("hello".to_string()),
+                calls: Arc::clone(&calls),
+            }),
+        ];
+
+        let result = transcribe_with_fallback(
+            &chain,
+            TranscriptionRequest {
+                samples: &[],
+                settings: &settings,
+                cancel: &cancel,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.backend, "local");
+        assert!(!result.remote);
+    }
+
+    #[tokio::test]
+    async fn fallback_reports_the_first_explainable_error() {
+        let settings = get_default_settings();
+        let cancel = CancellationToken::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let chain = vec![
+            FakeBackend::boxed("a", Err("bad response".into()), &calls),
+            FakeBackend::boxed("b", Err(RequestError::offline()), &calls),
+        ];
+
+        let error = transcribe_with_fallback(
+            &chain,
+            TranscriptionRequest {
+                samples: &[],
+                settings: &settings,
+                cancel: &cancel,
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::Network);
+        assert_eq!(error.message, "a: bad response; b: No internet connection");
+    }
+
+    #[tokio::test]
+    async fn fallback_abandons_a_remote_backend_after_the_timeout() {
+        let mut settings = get_default_settings();
+        settings.remote_transcription_timeout_secs = 1;
+        let cancel = CancellationToken::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let chain: Vec<Box<dyn TranscriptionBackend>> = vec![
+            Box::new(FakeBackend {
+                id: "slow".to_string(),
+                remote: true,
+                delay: Duration::from_secs(30),
+                result: Ok("too late".to_string()),
+                calls: Arc::clone(&calls),
+            }),
+            FakeBackend::boxed("fast", Ok("hello"), &calls),
+        ];
+
+        let started = Instant::now();
+        let result = transcribe_with_fallback(
+            &chain,
+            TranscriptionRequest {
+                samples: &[],
+                settings: &settings,
+                cancel: &cancel,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.backend, "fast");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,124 @@
+use super::upload::EncodedAudio;
+use crate::http::{self, Operation, RequestError};
+use crate::secure_store;
+use crate::settings::{AppSettings, OpenAiResponseFormat};
+use log::debug;
+use std::time::Instant;
+
+pub(super) fn openai_api_key(settings: &AppSettings) -> Option<String> {
+    if settings.use_secure_key_storage {
+        secure_store::fetch_api_key("openai")
+    } else {
+        settings
+            .openai_api_key
+            .as_ref()
+            .filter(|k| !k.trim().is_empty())
+            .cloned()
+    }
+}
+
+/// Posts the recording to an OpenAI-compatible `/audio/transcriptions`
+/// endpoint, or `/audio/translations` when translating to English. Works
+/// with OpenAI, Groq and self-hosted faster-whisper or whisper.cpp servers;
+/// the key is optional since local servers usually don't require one.
+pub(super) async fn transcribe_openai_direct(
+    audio: &EncodedAudio,
+    settings: &AppSettings,
+    api_key: Option<String>,
+    language: Option<String>,
+) -> Result<String, RequestError> {
+    let base_url = settings
+        .openai_transcription_base_url
+        .trim()
+        .trim_end_matches('/');
+    if base_url.is_empty() {
+        return Err("No OpenAI-compatible transcription URL is configured".into());
+    }
+
+    let translate = settings.whisper_translate();
+    let endpoint = if translate {
+        format!("{}/audio/translations", base_url)
+    } else {
+        format!("{}/audio/transcriptions", base_url)
+    };
+    let response_format = settings.openai_transcription_response_format;
+
+    // The API takes ISO 639-1 codes, so zh-Hans and zh-Hant become zh
+    let language = language
+        .filter(|_| !translate)
+        .map(|language| language.split('-').next().unwrap_or(&language).to_string());
+    let prompt = settings.openai_transcription_prompt.trim();
+    // Built per attempt since multipart bodies can't be resent
+    let form = || {
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", audio.multipart_part())
+            .text(
+                "model",
+                settings.openai_transcription_model.trim().to_string(),
+            )
+            .text("response_format", response_format.as_str());
+        if let Some(code) = &language {
+            form = form.text("language", code.clone());
+        }
+        if !prompt.is_empty() {
+            form = form.text("prompt", prompt.to_string());
+        }
+        form
+    };
+
+    debug!(
+        "Sending OpenAI-compatible transcription request to {} (model: {})",
+        endpoint, settings.openai_transcription_model
+    );
+
+    let operation = Operation::transcription(settings.remote_transcription_timeout());
+    let started = Instant::now();
+    let response = http::send(operation, || {
+        let builder = http::client().post(&endpoint).multipart(form());
+        match &api_key {
+            Some(key) => builder.bearer_auth(key.trim()),
+            None => builder,
+        }
+    })
+    .await?;
+    debug!(
+        "Uploaded {:.1} KB of {} to {} in {:?}",
+        audio.size_kb(),
+        audio.format.extension(),
+        endpoint,
+        started.elapsed()
+    );
+    let status = response.status();
+    let text_body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read transcription response: {}", e))?;
+
+    if !status.is_success() {
+        let err_msg = serde_json::from_str::<serde_json::Value>(&text_body)
+            .ok()
+            .and_then(|json| {
+                json.get("error")
+                    .and_then(|error| error.get("message").or(Some(error)))
+                    .and_then(|message| message.as_str())
+                    .map(|message| message.to_string())
+            })
+            .unwrap_or(text_body);
+        return Err(RequestError::from_status(
+            status,
+            format!("Transcription failed ({}): {}", status, err_msg),
+        ));
+    }
+
+    match response_format {
+        OpenAiResponseFormat::Text => Ok(text_body.trim().to_string()),
+        OpenAiResponseFormat::Json | OpenAiResponseFormat::VerboseJson => {
+            let json: serde_json::Value = serde_json::from_str(&text_body)
+                .map_err(|e| format!("Failed to parse transcription response JSON: {}", e))?;
+            json.get("text")
+                .and_then(|text| text.as_str())
+                .map(|text| text.trim().to_string())
+                .ok_or_else(|| "Transcription response missing text".into())
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use super::deepgram::{
+    deepgram_api_key, deepgram_model_from_settings, transcribe_deepgram_direct, DeepgramOptions,
+};
+use super::openai::{openai_api_key, transcribe_openai_direct};
+use super::upload::{upload_format, EncodedAudio, UploadTarget};
+use super::{Transcript, TranscriptionBackend, TranscriptionRequest};
+use crate::auth;
+use crate::cancellation::CancellationToken;
+use crate::http::{self, Operation, RequestError};
+use crate::secure_store;
+use crate::settings::{AppSettings, TranscriptionProvider, UsageMode};
+use futures_util::future::BoxFuture;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// A cloud provider, reached through the configured backend or, without
+/// one, directly with the user's own key.
+pub struct RemoteBackend {
+    provider: TranscriptionProvider,
+    model: Option<String>,
+}
+
+impl RemoteBackend {
+    /// `model` replaces the model selected for `provider` in settings.
+    pub fn new(provider: TranscriptionProvider, model: Option<String>) -> Self {
+        Self { provider, model }
+    }
+
+    /// The settings with this backend's provider, and model if it has one,
+    /// selected, since the request code reads both from settings.
+    fn effective_settings(&self, settings: &AppSettings) -> AppSettings {
+        let mut settings = settings.clone();
+        settings.provider = self.provider;
+        if let Some(model) = &self.model {
+            match self.provider {
+                TranscriptionProvider::Deepgram => settings.deepgram_model = model.clone(),
+                TranscriptionProvider::Openai => {
+                    settings.openai_transcription_model = model.clone()
+                }
+                TranscriptionProvider::Local => {}
+            }
+        }
+        settings
+    }
+}
+
+impl TranscriptionBackend for RemoteBackend {
+    fn id(&self, settings: &AppSettings) -> String {
+        let settings = self.effective_settings(settings);
+        match self.provider {
+            TranscriptionProvider::Deepgram => {
+                format!("deepgram:{}", deepgram_model_from_settings(&settings))
+            }
+            TranscriptionProvider::Openai => {
+                format!("openai:{}", settings.openai_transcription_model)
+            }
+            TranscriptionProvider::Local => "local".to_string(),
+        }
+    }
+
+    fn is_remote(&self) -> bool {
+        true
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        request: TranscriptionRequest<'a>,
+    ) -> BoxFuture<'a, Result<Transcript, RequestError>> {
+        Box::pin(async move {
+            let settings = self.effective_settings(request.settings);
+            transcribe_remote(&settings, request.samples, request.cancel).await
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct RemoteTranscriptionResponse {
+    text: Option<String>,
+    success: Option<bool>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeepgramTranscriptionRequest {
+    audio_blob: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(flatten)]
+    options: DeepgramOptions,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UnifiedTranscriptionRequest {
+    audio: String,
+    provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+}
+
+/// The recording as a data URL for the configured backend.
+fn relay_audio(samples: &[f32], settings: &AppSettings) -> Result<String, String> {
+    EncodedAudio::encode(samples, upload_format(settings, UploadTarget::Backend))
+        .map(|audio| audio.data_url())
+}
+
+pub(super) async fn transcribe_remote(
+    settings: &AppSettings,
+    samples: &[f32],
+    cancel: &CancellationToken,
+) -> Result<Transcript, RequestError> {
+    let base_url = settings
+        .api_base_url
+        .as_ref()
+        .map(|url| url.trim_end_matches('/'))
+        .unwrap_or("");
+    let can_use_direct_deepgram = settings.provider == TranscriptionProvider::Deepgram
+        && secure_store::fetch_api_key("deepgram").is_some();
+    // Without a backend, OpenAI-compatible servers are always called directly
+    let can_use_direct_openai = settings.provider == TranscriptionProvider::Openai;
+    if base_url.is_empty() && !can_use_direct_deepgram && !can_use_direct_openai {
+        return Err(
+            "Remote transcription selected but no API base URL is configured in settings".into(),
+        );
+    }
+
+    let language = settings
+        .forced_language()
+        .map(|language| language.to_string());
+
+    let model = match settings.provider {
+        TranscriptionProvider::Deepgram => Some(deepgram_model_from_settings(settings)),
+        _ if settings.selected_model.is_empty() => None,
+        _ => Some(settings.selected_model.clone()),
+    };
+
+    let (endpoint, body, provider_id) = match settings.provider {
+        TranscriptionProvider::Local => {
+            return Err("Remote transcription requested while provider is set to local".into())
+        }
+        TranscriptionProvider::Deepgram => {
+            // If no backend is configured, try direct Deepgram API using the user's key
+            if base_url.is_empty() {
+                let api_key = deepgram_api_key(settings).ok_or_else(|| {
+                    "Deepgram provider requires a backend base URL or a stored Deepgram API key"
+                        .to_string()
+                })?;
+
+                // With several allowed languages, let Deepgram detect among just those
+                let detect_languages = if language.is_none() {
+                    settings.allowed_languages.clone()
+                } else {
+                    Vec::new()
+                };
+
+                let audio =
+                    EncodedAudio::encode(samples, upload_format(settings, UploadTarget::Deepgram))?;
+                return cancel
+                    .run_until_cancelled(transcribe_deepgram_direct(
+                        &audio,
+                        api_key,
+                        model.clone(),
+                        language.clone(),
+                        detect_languages,
+                        DeepgramOptions::from_settings(settings),
+                        settings.remote_transcription_timeout(),
+                    ))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+
+            let api_key = if settings.usage_mode == UsageMode::OwnKeys {
+                deepgram_api_key(settings)
+            } else {
+                None
+            };
+
+            (
+                format!("{}/api/transcribe/deepgram", base_url),
+                serde_json::to_value(DeepgramTranscriptionRequest {
+                    audio_blob: relay_audio(samples, settings)?,
+                    api_key,
+                    model: model.clone(),
+                    language,
+                    options: DeepgramOptions::from_settings(settings),
+                })
+                .map_err(|e| format!("Failed to serialize Deepgram request: {}", e))?,
+                "deepgram".to_string(),
+            )
+        }
+        TranscriptionProvider::Openai if base_url.is_empty() => {
+            let language = settings
+                .openai_transcription_language
+                .clone()
+                .filter(|language| !language.trim().is_empty())
+                .or(language);
+            let audio = EncodedAudio::encode(
+                samples,
+                upload_format(
+                    settings,
+                    UploadTarget::OpenAiCompatible {
+                        base_url: &settings.openai_transcription_base_url,
+                    },
+                ),
+            )?;
+            let api_key = openai_api_key(settings);
+            if !settings.whisper_translate() {
+                return cancel
+                    .run_until_cancelled(transcribe_openai_direct(
+                        &audio, settings, api_key, language,
+                    ))
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .map(Transcript::from);
+            }
+            if !settings.translation_keep_source_text {
+                return cancel
+                    .run_until_cancelled(transcribe_openai_direct(&audio, settings, api_key, None))
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .map(|text| Transcript {
+                        text,
+                        translated: true,
+                        ..Default::default()
+                    });
+            }
+
+            // The translations endpoint drops the spoken text, so fetch it
+            // alongside; the translation still succeeds without it
+            let source_settings = settings.without_translation();
+            let (translation, source) = cancel
+                .run_until_cancelled(futures_util::future::join(
+                    transcribe_openai_direct(&audio, settings, api_key.clone(), None),
+                    transcribe_openai_direct(&audio, &source_settings, api_key, language),
+                ))
+                .await
+                .map_err(|e| e.to_string())?;
+            let source_text = source
+                .inspect_err(|e| warn!("Couldn't transcribe the spoken text: {}", e))
+                .ok();
+            return translation.map(|text| Transcript {
+                text,
+                translated: true,
+                source_text,
+                ..Default::default()
+            });
+        }
+        TranscriptionProvider::Openai => (
+            format!("{}/api/transcribe", base_url),
+            serde_json::to_value(UnifiedTranscriptionRequest {
+                audio: relay_audio(samples, settings)?,
+                provider: "openai".to_string(),
+                model,
+                language,
+            })
+            .map_err(|e| format!("Failed to serialize unified request: {}", e))?,
+            "openai".to_string(),
+        ),
+    };
+
+    debug!(
+        "Sending remote transcription request to {} for provider '{}'",
+        endpoint, provider_id
+    );
+
+    let payload = serde_json::to_vec(&body)
+        .map_err(|e| format!("Failed to serialize remote transcription request: {}", e))?;
+    let payload_kb = payload.len() as f32 / 1024.0;
+    let build = || {
+        http::client()
+            .post(&endpoint)
+            .header("Content-Type", "application/json")
+            .body(payload.clone())
+    };
+    if settings.usage_mode == UsageMode::Credits && !auth::is_signed_in() {
+        return Err(auth::not_signed_in());
+    }
+
+    let operation = Operation::transcription(settings.remote_transcription_timeout());
+    let started = Instant::now();
+    let request = async {
+        if auth::is_signed_in() {
+            auth::send_authorized(operation, base_url, |token| build().bearer_auth(token)).await
+        } else {
+            http::send(operation, &build).await
+        }
+    };
+    let response = cancel
+        .run_until_cancelled(request)
+        .await
+        .map_err(|e| e.to_string())??;
+    debug!(
+        "Uploaded {:.1} KB request to the backend in {:?}",
+        payload_kb,
+        started.elapsed()
+    );
+
+    let status = response.status();
+    let parsed: RemoteTranscriptionResponse = cancel
+        .run_until_cancelled(response.json())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("Failed to parse remote transcription response: {}", e))?;
+
+    if parsed.success == Some(false) {
+        if let Some(error) = parsed.error {
+            return Err(RequestError::from_status(status, error));
+        }
+    }
+
+    if !status.is_success() {
+        return Err(RequestError::from_status(
+            status,
+            parsed
+                .error
+                .unwrap_or_else(|| format!("Remote transcription failed with status {}", status)),
+        ));
+    }
+
+    parsed
+        .text
+        .map(Transcript::from)
+        .ok_or_else(|| "Remote transcription returned no text".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    #[test]
+    fn relayed_deepgram_request_carries_options() {
+        let mut settings = get_default_settings();
+        settings.deepgram_smart_format = true;
+        settings.deepgram_filler_words = true;
+        settings.custom_words = vec!["Kubernetes".to_string(), " ".to_string()];
+
+        let body = serde_json::to_value(DeepgramTranscriptionRequest {
+            audio_blob: "data:audio/wav;base64,".to_string(),
+            api_key: None,
+            model: Some("nova-3".to_string()),
+            language: None,
+            options: DeepgramOptions::from_settings(&settings),
+        })
+        .unwrap();
+
+        assert_eq!(body["smartFormat"], true);
+        assert_eq!(body["fillerWords"], true);
+        assert_eq!(body["keyterms"], serde_json::json!(["Kubernetes"]));
+        assert!(body.get("apiKey").is_none());
+    }
+
+    #[test]
+    fn relayed_deepgram_request_omits_empty_key_terms() {
+        let mut settings = get_default_settings();
+        settings.custom_words.clear();
+
+        let body = serde_json::to_value(DeepgramTranscriptionRequest {
+            audio_blob: String::new(),
+            api_key: None,
+            model: None,
+            language: None,
+            options: DeepgramOptions::from_settings(&settings),
+        })
+        .unwrap();
+
+        assert!(body.get("keyterms").is_none());
+        assert!(body.get("diarize").is_some());
+    }
+}
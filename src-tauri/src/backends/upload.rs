@@ -0,0 +1,123 @@
+//! Encodes recordings for upload in the most compact format the receiving
+//! server accepts.
+
+use crate::audio_toolkit::{
+    constants::WHISPER_SAMPLE_RATE, encode_flac, encode_ogg_opus, encode_wav,
+};
+use crate::settings::{AppSettings, RemoteAudioFormat};
+use base64::Engine;
+use log::debug;
+use std::time::Instant;
+
+/// Plenty for recognising speech, at roughly a tenth of the FLAC size
+const OPUS_BITRATE: i32 = 24_000;
+
+/// Hosted OpenAI-compatible APIs known to decode Ogg Opus
+const OGG_OPUS_HOSTS: &[&str] = &["api.openai.com", "api.groq.com"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum UploadFormat {
+    Wav,
+    Flac,
+    OggOpus,
+}
+
+impl UploadFormat {
+    pub(super) fn mime(self) -> &'static str {
+        match self {
+            UploadFormat::Wav => "audio/wav",
+            UploadFormat::Flac => "audio/flac",
+            UploadFormat::OggOpus => "audio/ogg",
+        }
+    }
+
+    pub(super) fn extension(self) -> &'static str {
+        match self {
+            UploadFormat::Wav => "wav",
+            UploadFormat::Flac => "flac",
+            UploadFormat::OggOpus => "ogg",
+        }
+    }
+}
+
+/// Where a recording is sent, which decides the formats it can take.
+#[derive(Clone, Copy)]
+pub(super) enum UploadTarget<'a> {
+    Deepgram,
+    OpenAiCompatible {
+        base_url: &'a str,
+    },
+    /// The configured transcription backend, which has only ever been sent WAV
+    Backend,
+}
+
+/// The format from settings, or with `Auto` the most compact one `target`
+/// is known to accept.
+pub(super) fn upload_format(settings: &AppSettings, target: UploadTarget<'_>) -> UploadFormat {
+    match settings.remote_audio_format {
+        RemoteAudioFormat::Wav => UploadFormat::Wav,
+        RemoteAudioFormat::Flac => UploadFormat::Flac,
+        RemoteAudioFormat::Opus => UploadFormat::OggOpus,
+        RemoteAudioFormat::Auto => match target {
+            UploadTarget::Deepgram => UploadFormat::OggOpus,
+            UploadTarget::OpenAiCompatible { base_url } if accepts_ogg_opus(base_url) => {
+                UploadFormat::OggOpus
+            }
+            // Self-hosted servers often decode nothing but WAV without ffmpeg
+            UploadTarget::OpenAiCompatible { .. } | UploadTarget::Backend => UploadFormat::Wav,
+        },
+    }
+}
+
+fn accepts_ogg_opus(base_url: &str) -> bool {
+    reqwest::Url::parse(base_url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| OGG_OPUS_HOSTS.contains(&host)))
+        .unwrap_or(false)
+}
+
+/// A recording encoded for upload.
+pub(super) struct EncodedAudio {
+    pub bytes: Vec<u8>,
+    pub format: UploadFormat,
+}
+
+impl EncodedAudio {
+    pub(super) fn encode(samples: &[f32], format: UploadFormat) -> Result<Self, String> {
+        let started = Instant::now();
+        let bytes = match format {
+            UploadFormat::Wav => encode_wav(samples),
+            UploadFormat::Flac => encode_flac(samples),
+            UploadFormat::OggOpus => encode_ogg_opus(samples, OPUS_BITRATE),
+        }
+        .map_err(|e| format!("Failed to encode audio as {}: {}", format.extension(), e))?;
+
+        debug!(
+            "Encoded {:.1}s of audio as {} ({:.1} KB) in {:?}",
+            samples.len() as f32 / WHISPER_SAMPLE_RATE as f32,
+            format.extension(),
+            bytes.len() as f32 / 1024.0,
+            started.elapsed()
+        );
+        Ok(Self { bytes, format })
+    }
+
+    /// Size for upload logs, in KB.
+    pub(super) fn size_kb(&self) -> f32 {
+        self.bytes.len() as f32 / 1024.0
+    }
+
+    /// The audio as a multipart file field.
+    pub(super) fn multipart_part(&self) -> reqwest::multipart::Part {
+        reqwest::multipart::Part::bytes(self.bytes.clone())
+            .file_name(format!("audio.{}", self.format.extension()))
+            .mime_str(self.format.mime())
+            .expect("upload MIME types are valid")
+    }
+
+    /// The audio as a base64 data URL, for JSON request bodies.
+    pub(super) fn data_url(&self) -> String {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&self.bytes);
+        format!("data:{};base64,{}", self.format.mime(), encoded)
+    }
+}
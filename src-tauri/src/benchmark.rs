@@ -0,0 +1,353 @@
+use crate::audio_toolkit::{
+    character_error_rate, normalize_for_scoring, read_wav_file, word_error_rate,
+};
+use crate::helpers::memory::resident_memory_bytes;
+use crate::managers::model::{ModelBenchmark, ModelInfo, ModelManager};
+use crate::managers::transcription::LoadedEngine;
+use anyhow::Result;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+const CLIPS_MANIFEST_FILE: &str = "clips.json";
+const SAMPLE_RATE: f32 = 16000.0;
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+static BENCHMARK_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// One reference recording listed in a benchmark `clips.json`.
+#[derive(Debug, Clone, Deserialize)]
+struct ClipManifestEntry {
+    /// WAV file, relative to the manifest
+    file: String,
+    transcript: String,
+    /// Whisper language code to force, if any
+    #[serde(default)]
+    language: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ClipManifest {
+    #[serde(default)]
+    clips: Vec<ClipManifestEntry>,
+}
+
+pub struct ReferenceClip {
+    pub name: String,
+    pub audio: Vec<f32>,
+    pub transcript: String,
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkProgress {
+    pub model_id: String,
+    pub model_index: usize,
+    pub model_count: usize,
+    pub clip_index: usize,
+    pub clip_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkOutcome {
+    pub model_id: String,
+    pub benchmark: Option<ModelBenchmark>,
+    pub error: Option<String>,
+}
+
+/// Directories searched for reference clips: the bundled set, then any
+/// clips the user added under the app data directory.
+fn clip_directories(app: &AppHandle) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(dir) = app
+        .path()
+        .resolve("resources/benchmark", tauri::path::BaseDirectory::Resource)
+    {
+        dirs.push(dir);
+    }
+    if let Ok(dir) = app.path().app_data_dir() {
+        dirs.push(dir.join("benchmark"));
+    }
+    dirs
+}
+
+/// Loads every clip listed in a `clips.json` inside `dir`.
+pub fn load_clips_from(dir: &Path) -> Result<Vec<ReferenceClip>> {
+    let manifest_path = dir.join(CLIPS_MANIFEST_FILE);
+    if !manifest_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let manifest: ClipManifest = serde_json::from_str(&fs::read_to_string(&manifest_path)?)
+        .map_err(|e| anyhow::anyhow!("Invalid {:?}: {}", manifest_path, e))?;
+
+    let mut clips = Vec::with_capacity(manifest.clips.len());
+    for entry in manifest.clips {
+        let path = dir.join(&entry.file);
+        match read_wav_file(&path) {
+            Ok(audio) => clips.push(ReferenceClip {
+                name: entry.file,
+                audio,
+                transcript: entry.transcript,
+                language: entry.language,
+            }),
+            Err(e) => warn!("Skipping benchmark clip {:?}: {}", path, e),
+        }
+    }
+    Ok(clips)
+}
+
+fn load_reference_clips(app: &AppHandle) -> Result<Vec<ReferenceClip>> {
+    let mut clips = Vec::new();
+    for dir in clip_directories(app) {
+        clips.extend(load_clips_from(&dir)?);
+    }
+    Ok(clips)
+}
+
+/// Polls resident memory on a background thread and keeps the highest value seen.
+struct PeakMemorySampler {
+    stop: Arc<AtomicBool>,
+    peak: Arc<AtomicU64>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl PeakMemorySampler {
+    fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let peak = Arc::new(AtomicU64::new(resident_memory_bytes().unwrap_or(0)));
+
+        let handle = {
+            let stop = stop.clone();
+            let peak = peak.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    if let Some(rss) = resident_memory_bytes() {
+                        peak.fetch_max(rss, Ordering::Relaxed);
+                    }
+                    thread::sleep(MEMORY_SAMPLE_INTERVAL);
+                }
+            })
+        };
+
+        Self {
+            stop,
+            peak,
+            handle: Some(handle),
+        }
+    }
+
+    fn finish(mut self) -> u64 {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+/// Loads a model into a fresh engine, separate from the one used for
+/// dictation, and transcribes every clip with it.
+pub fn benchmark_model(
+    model: &ModelInfo,
+    model_path: &Path,
+    clips: &[ReferenceClip],
+    mut on_clip: impl FnMut(usize),
+) -> Result<ModelBenchmark> {
+    if clips.is_empty() {
+        return Err(anyhow::anyhow!("No benchmark clips available"));
+    }
+
+    let baseline_memory = resident_memory_bytes();
+    let sampler = PeakMemorySampler::start();
+
+    let load_start = Instant::now();
+    let mut engine = LoadedEngine::load(model.engine_type, model_path)?;
+    let load_time = load_start.elapsed();
+
+    // The first inference pays one-off setup costs that would skew the real-time factor
+    let _ = engine.transcribe(vec![0.0; SAMPLE_RATE as usize], None, false);
+
+    let mut audio_seconds = 0.0f32;
+    let mut processing = Duration::ZERO;
+    let mut word_errors = 0.0f64;
+    let mut reference_words = 0usize;
+    let mut char_errors = 0.0f64;
+    let mut reference_chars = 0usize;
+
+    for (index, clip) in clips.iter().enumerate() {
+        on_clip(index);
+
+        let clip_start = Instant::now();
+        let text = engine.transcribe(clip.audio.clone(), clip.language.clone(), false)?;
+        processing += clip_start.elapsed();
+        audio_seconds += clip.audio.len() as f32 / SAMPLE_RATE;
+
+        // Weight each clip by its length so the totals equal corpus-level rates
+        let words = normalize_for_scoring(&clip.transcript);
+        let chars = words.concat().chars().count();
+        let wer = word_error_rate(&clip.transcript, &text);
+        let cer = character_error_rate(&clip.transcript, &text);
+        word_errors += wer * words.len() as f64;
+        reference_words += words.len();
+        char_errors += cer * chars as f64;
+        reference_chars += chars;
+
+        debug!(
+            "Benchmark {} on {}: WER {:.3}, CER {:.3}, {:?}",
+            model.id,
+            clip.name,
+            wer,
+            cer,
+            clip_start.elapsed()
+        );
+    }
+
+    drop(engine);
+    let peak_memory = sampler.finish();
+
+    Ok(ModelBenchmark {
+        load_time_ms: load_time.as_millis() as u64,
+        real_time_factor: processing.as_secs_f32() / audio_seconds.max(f32::EPSILON),
+        peak_memory_mb: baseline_memory
+            .map(|baseline| peak_memory.saturating_sub(baseline) as f32 / (1024.0 * 1024.0)),
+        word_error_rate: (word_errors / reference_words.max(1) as f64) as f32,
+        character_error_rate: (char_errors / reference_chars.max(1) as f64) as f32,
+        clip_count: clips.len(),
+        audio_seconds,
+        benchmarked_at: chrono::Utc::now().timestamp(),
+    })
+}
+
+/// Benchmarks the given models, or every downloaded model when `model_ids`
+/// is empty, and stores the results with the model catalog. Blocks for as
+/// long as the models take to run, so call it off the async runtime.
+pub fn run_benchmarks(
+    app: &AppHandle,
+    model_manager: &ModelManager,
+    model_ids: &[String],
+) -> Result<Vec<BenchmarkOutcome>> {
+    if BENCHMARK_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(anyhow::anyhow!("A benchmark is already running"));
+    }
+
+    let result = (|| {
+        let clips = load_reference_clips(app)?;
+        if clips.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No benchmark clips found. Add WAV files and a clips.json to the benchmark folder in the app data directory."
+            ));
+        }
+
+        let models: Vec<ModelInfo> = model_manager
+            .get_available_models()
+            .into_iter()
+            .filter(|model| model.is_downloaded)
+            .filter(|model| model_ids.is_empty() || model_ids.contains(&model.id))
+            .collect();
+        if models.is_empty() {
+            return Err(anyhow::anyhow!("No downloaded models to benchmark"));
+        }
+
+        let mut outcomes = Vec::with_capacity(models.len());
+        for (model_index, model) in models.iter().enumerate() {
+            info!("Benchmarking model {} on {} clips", model.id, clips.len());
+
+            let outcome = model_manager
+                .get_model_path(&model.id)
+                .and_then(|path| {
+                    benchmark_model(model, &path, &clips, |clip_index| {
+                        let _ = app.emit(
+                            "model-benchmark-progress",
+                            BenchmarkProgress {
+                                model_id: model.id.clone(),
+                                model_index,
+                                model_count: models.len(),
+                                clip_index,
+                                clip_count: clips.len(),
+                            },
+                        );
+                    })
+                })
+                .and_then(|benchmark| {
+                    model_manager.record_benchmark(&model.id, benchmark.clone())?;
+                    Ok(benchmark)
+                });
+
+            outcomes.push(match outcome {
+                Ok(benchmark) => {
+                    info!(
+                        "Benchmark {}: load {}ms, RTF {:.3}, WER {:.3}, CER {:.3}",
+                        model.id,
+                        benchmark.load_time_ms,
+                        benchmark.real_time_factor,
+                        benchmark.word_error_rate,
+                        benchmark.character_error_rate
+                    );
+                    BenchmarkOutcome {
+                        model_id: model.id.clone(),
+                        benchmark: Some(benchmark),
+                        error: None,
+                    }
+                }
+                Err(e) => {
+                    warn!("Benchmark of {} failed: {}", model.id, e);
+                    BenchmarkOutcome {
+                        model_id: model.id.clone(),
+                        benchmark: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            });
+        }
+
+        // A run where nothing could be measured is a failure, not an empty result
+        if outcomes.iter().all(|outcome| outcome.benchmark.is_none()) {
+            let errors: Vec<String> = outcomes
+                .iter()
+                .filter_map(|outcome| {
+                    let error = outcome.error.as_ref()?;
+                    Some(format!("{}: {}", outcome.model_id, error))
+                })
+                .collect();
+            return Err(anyhow::anyhow!(
+                "Every benchmark failed. {}",
+                errors.join("; ")
+            ));
+        }
+
+        Ok(outcomes)
+    })();
+
+    BENCHMARK_RUNNING.store(false, Ordering::SeqCst);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_clips_load() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/benchmark");
+        let clips = load_clips_from(&dir).unwrap();
+        assert!(
+            !clips.is_empty(),
+            "No bundled benchmark clips; run scripts/fetch_benchmark_clips.py"
+        );
+        for clip in clips {
+            assert!(!clip.audio.is_empty(), "{} is empty", clip.name);
+            assert!(
+                !clip.transcript.trim().is_empty(),
+                "{} has no transcript",
+                clip.name
+            );
+        }
+    }
+}
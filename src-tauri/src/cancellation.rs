@@ -0,0 +1,108 @@
+use futures_util::future::{self, Either};
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Error returned when an operation stops because its token was cancelled.
+#[derive(Debug, Clone, Copy)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Operation cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+#[derive(Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// Shared flag that lets a cancel request reach work already in flight.
+/// Clones observe the same state.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<TokenState>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        self.state.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns `Err(Cancelled)` once the token has been cancelled.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Resolves when the token is cancelled.
+    pub async fn cancelled(&self) {
+        loop {
+            // Register before checking so a cancel in between is not missed
+            let notified = self.state.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Drives `work` until it finishes or the token is cancelled, whichever
+    /// comes first. Cancelling drops `work`.
+    pub async fn run_until_cancelled<F: Future>(&self, work: F) -> Result<F::Output, Cancelled> {
+        let cancelled = self.cancelled();
+        futures_util::pin_mut!(work, cancelled);
+        match future::select(work, cancelled).await {
+            Either::Left((output, _)) if !self.is_cancelled() => Ok(output),
+            _ => Err(Cancelled),
+        }
+    }
+}
+
+/// Tokens for operations that are still running, so one cancel reaches all
+/// of them. Tokens no task holds any more are pruned as new ones start.
+#[derive(Default)]
+pub struct InFlightOperations {
+    tokens: Mutex<Vec<CancellationToken>>,
+}
+
+impl InFlightOperations {
+    pub fn begin(&self) -> CancellationToken {
+        let token = CancellationToken::new();
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|token| Arc::strong_count(&token.state) > 1);
+        tokens.push(token.clone());
+        token
+    }
+
+    /// Cancels every operation in flight and returns how many there were.
+    pub fn cancel_all(&self) -> usize {
+        let tokens = std::mem::take(&mut *self.tokens.lock().unwrap());
+        let mut count = 0;
+        for token in tokens {
+            if Arc::strong_count(&token.state) > 1 && !token.is_cancelled() {
+                count += 1;
+            }
+            token.cancel();
+        }
+        count
+    }
+}
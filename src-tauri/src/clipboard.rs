@@ -1,33 +1,31 @@
 use crate::settings::{get_settings, ClipboardHandling, PasteMethod};
+use crate::shortcut::wait_for_shortcuts_released;
 use enigo::Enigo;
 use enigo::Key;
 use enigo::Keyboard;
 use enigo::Settings;
-use log::info;
+use log::{info, warn};
+use std::time::{Duration, Instant};
+use tauri::image::Image;
 use tauri::AppHandle;
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
-/// Sends a Ctrl+V or Cmd+V paste command using platform-specific virtual key codes.
-/// This ensures the paste works regardless of keyboard layout (e.g., Russian, AZERTY, DVORAK).
-fn send_paste_ctrl_v() -> Result<(), String> {
-    // Platform-specific key definitions
+/// Presses `key` while holding Ctrl, or Cmd on macOS.
+fn send_with_command_modifier(key: Key) -> Result<(), String> {
     #[cfg(target_os = "macos")]
-    let (modifier_key, v_key_code) = (Key::Meta, Key::Other(9));
-    #[cfg(target_os = "windows")]
-    let (modifier_key, v_key_code) = (Key::Control, Key::Other(0x56)); // VK_V
-    #[cfg(target_os = "linux")]
-    let (modifier_key, v_key_code) = (Key::Control, Key::Unicode('v'));
+    let modifier_key = Key::Meta;
+    #[cfg(not(target_os = "macos"))]
+    let modifier_key = Key::Control;
 
     let mut enigo = Enigo::new(&Settings::default())
         .map_err(|e| format!("Failed to initialize Enigo: {}", e))?;
 
-    // Press modifier + V
     enigo
         .key(modifier_key, enigo::Direction::Press)
         .map_err(|e| format!("Failed to press modifier key: {}", e))?;
     enigo
-        .key(v_key_code, enigo::Direction::Click)
-        .map_err(|e| format!("Failed to click V key: {}", e))?;
+        .key(key, enigo::Direction::Click)
+        .map_err(|e| format!("Failed to click key: {}", e))?;
 
     std::thread::sleep(std::time::Duration::from_millis(100));
 
@@ -38,6 +36,32 @@ fn send_paste_ctrl_v() -> Result<(), String> {
     Ok(())
 }
 
+/// Sends a Ctrl+V or Cmd+V paste command using platform-specific virtual key codes.
+/// This ensures the paste works regardless of keyboard layout (e.g., Russian, AZERTY, DVORAK).
+fn send_paste_ctrl_v() -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let v_key_code = Key::Other(9);
+    #[cfg(target_os = "windows")]
+    let v_key_code = Key::Other(0x56); // VK_V
+    #[cfg(target_os = "linux")]
+    let v_key_code = Key::Unicode('v');
+
+    send_with_command_modifier(v_key_code)
+}
+
+/// Sends a Ctrl+C or Cmd+C copy command, independent of keyboard layout
+/// like the paste command.
+fn send_copy() -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let c_key_code = Key::Other(8);
+    #[cfg(target_os = "windows")]
+    let c_key_code = Key::Other(0x43); // VK_C
+    #[cfg(target_os = "linux")]
+    let c_key_code = Key::Unicode('c');
+
+    send_with_command_modifier(c_key_code)
+}
+
 /// Sends a Shift+Insert paste command (Windows and Linux only).
 /// This is more universal for terminal applications and legacy software.
 #[cfg(not(target_os = "macos"))]
@@ -135,9 +159,8 @@ fn paste_via_clipboard_shift_insert(text: &str, app_handle: &AppHandle) -> Resul
     Ok(())
 }
 
-pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
+pub fn paste(text: String, paste_method: PasteMethod, app_handle: AppHandle) -> Result<(), String> {
     let settings = get_settings(&app_handle);
-    let paste_method = settings.paste_method;
 
     info!("Using paste method: {:?}", paste_method);
 
@@ -159,3 +182,125 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
 
     Ok(())
 }
+
+/// The text selected in the focused application, if any. Only call it when
+/// the selection is actually needed, and off the shortcut thread: it can
+/// block until the shortcut is released.
+///
+/// On Linux this is the PRIMARY selection, read with wl-paste, xclip or
+/// xsel. Elsewhere, or when none of them is installed, a copy is simulated
+/// and the clipboard restored afterwards, see `copy_selection`.
+pub fn read_selection(app_handle: &AppHandle) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    if let Some(selection) = primary_selection() {
+        return Some(selection).filter(|selection| !selection.trim().is_empty());
+    }
+
+    copy_selection(app_handle)
+}
+
+/// The PRIMARY selection, or `None` when no tool could read it.
+#[cfg(target_os = "linux")]
+fn primary_selection() -> Option<String> {
+    let commands: [(&str, &[&str]); 3] = [
+        ("wl-paste", &["--primary", "--no-newline"]),
+        ("xclip", &["-o", "-selection", "primary"]),
+        ("xsel", &["-o", "-p"]),
+    ];
+    commands.iter().find_map(|(program, args)| {
+        let output = std::process::Command::new(program)
+            .args(*args)
+            .output()
+            .ok()?;
+        // wl-paste and xclip fail when the selection is empty
+        Some(if output.status.success() {
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        } else {
+            String::new()
+        })
+    })
+}
+
+/// How long a push-to-talk shortcut may be held before the selection is
+/// given up on
+const SHORTCUT_RELEASE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long the focused application gets to put the selection on the
+/// clipboard
+const COPY_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// What was on the clipboard before a simulated copy.
+enum SavedClipboard {
+    Text(String),
+    Image(Image<'static>),
+    Empty,
+}
+
+impl SavedClipboard {
+    fn read(app_handle: &AppHandle) -> Self {
+        let clipboard = app_handle.clipboard();
+        if let Ok(text) = clipboard.read_text() {
+            if !text.is_empty() {
+                return SavedClipboard::Text(text);
+            }
+        }
+        match clipboard.read_image() {
+            Ok(image) => SavedClipboard::Image(image.to_owned()),
+            Err(_) => SavedClipboard::Empty,
+        }
+    }
+
+    fn restore(&self, app_handle: &AppHandle) -> Result<(), String> {
+        let clipboard = app_handle.clipboard();
+        let result = match self {
+            SavedClipboard::Text(text) => clipboard.write_text(text),
+            SavedClipboard::Image(image) => clipboard.write_image(image),
+            SavedClipboard::Empty => clipboard.clear(),
+        };
+        result.map_err(|e| e.to_string())
+    }
+}
+
+/// Copies the selection through the clipboard, then puts back what was
+/// there.
+///
+/// This is destructive in ways the caller should know about: it waits for
+/// the shortcut to be released and then presses Ctrl+C (Cmd+C) in the
+/// focused application, which in a terminal without a selection sends an
+/// interrupt. Only text and images are restored; rich text keeps just its
+/// plain text and copied files are lost. An application that writes the
+/// clipboard later than `COPY_TIMEOUT` overwrites the restored content.
+fn copy_selection(app_handle: &AppHandle) -> Option<String> {
+    if !wait_for_shortcuts_released(SHORTCUT_RELEASE_TIMEOUT) {
+        warn!("Shortcut still held; not reading the selection");
+        return None;
+    }
+
+    let clipboard = app_handle.clipboard();
+    let previous = SavedClipboard::read(app_handle);
+
+    // Cleared first so an empty selection doesn't read back as the old content
+    clipboard.write_text("").ok()?;
+    let copied = send_copy();
+    let started = Instant::now();
+    let mut selection = String::new();
+    while copied.is_ok() && started.elapsed() < COPY_TIMEOUT {
+        std::thread::sleep(Duration::from_millis(20));
+        selection = clipboard.read_text().unwrap_or_default();
+        if !selection.is_empty() {
+            break;
+        }
+    }
+
+    if let Err(e) = previous.restore(app_handle) {
+        warn!(
+            "Failed to restore clipboard after reading the selection: {}",
+            e
+        );
+    }
+    if let Err(e) = copied {
+        warn!("Failed to copy the selection: {}", e);
+        return None;
+    }
+    Some(selection).filter(|selection| !selection.trim().is_empty())
+}
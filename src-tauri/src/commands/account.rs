@@ -0,0 +1,49 @@
+use crate::auth::DeviceLogin;
+use crate::managers::account::{AccountManager, AccountStatus};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+#[tauri::command]
+pub async fn get_account_status(
+    _app: AppHandle,
+    account_manager: State<'_, Arc<AccountManager>>,
+) -> Result<AccountStatus, String> {
+    Ok(account_manager.status())
+}
+
+/// Opens the browser to approve the sign-in and returns the code to show.
+/// The outcome arrives as `account-updated` or `account-login-failed`.
+#[tauri::command]
+pub async fn start_account_login(
+    _app: AppHandle,
+    account_manager: State<'_, Arc<AccountManager>>,
+) -> Result<DeviceLogin, String> {
+    account_manager.start_login().await.map_err(String::from)
+}
+
+#[tauri::command]
+pub async fn cancel_account_login(
+    _app: AppHandle,
+    account_manager: State<'_, Arc<AccountManager>>,
+) -> Result<(), String> {
+    account_manager.cancel_login();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn logout_account(
+    _app: AppHandle,
+    account_manager: State<'_, Arc<AccountManager>>,
+) -> Result<(), String> {
+    account_manager.logout();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn refresh_account(
+    _app: AppHandle,
+    account_manager: State<'_, Arc<AccountManager>>,
+) -> Result<AccountStatus, String> {
+    account_manager.sync().await;
+    Ok(account_manager.status())
+}
@@ -1,6 +1,6 @@
 use crate::managers::history::{HistoryEntry, HistoryManager};
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 
 #[tauri::command]
 pub async fn get_history_entries(
@@ -93,3 +93,32 @@ pub async fn update_recording_retention_period(
 
     Ok(())
 }
+
+/// Writes the entry to a text file in the downloads folder, with speaker
+/// turns when the recording was diarized, and returns the file path.
+#[tauri::command]
+pub async fn export_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<String, String> {
+    let entry = history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+
+    let settings = crate::settings::get_settings(&app);
+    let dir = app.path().download_dir().map_err(|e| e.to_string())?;
+    let path = dir.join(format!("handy-{}.txt", entry.timestamp));
+    let contents = format!(
+        "{}\n\n{}\n",
+        entry.title,
+        entry.export_text(settings.speaker_labels)
+    );
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to export: {}", e))?;
+
+    path.to_str()
+        .ok_or_else(|| "Invalid file path".to_string())
+        .map(|s| s.to_string())
+}
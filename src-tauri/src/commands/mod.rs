@@ -1,7 +1,9 @@
+pub mod account;
 pub mod audio;
 pub mod history;
 pub mod models;
 pub mod transcription;
+pub mod usage;
 
 use crate::{settings, utils::cancel_current_operation};
 use std::sync::Arc;
@@ -13,7 +15,7 @@ use tauri_plugin_opener::OpenerExt;
 pub fn finish_operation(app: AppHandle) {
     let rm = app.state::<Arc<crate::managers::audio::AudioRecordingManager>>();
     if let Some(binding_id) = rm.get_active_binding() {
-        if let Some(action) = crate::actions::ACTION_MAP.get("transcribe") {
+        if let Some(action) = crate::actions::action_for_binding(&binding_id) {
             action.stop(&app, &binding_id, "");
         }
     }
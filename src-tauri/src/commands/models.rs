@@ -1,6 +1,9 @@
-use crate::managers::model::{ModelInfo, ModelManager};
+use crate::benchmark::{self, BenchmarkOutcome};
+use crate::managers::download::{DownloadManager, DownloadPriority, DownloadQueueProgress};
+use crate::managers::model::{EngineType, ModelInfo, ModelManager, ModelVerification};
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -21,12 +24,17 @@ pub async fn get_model_info(
 
 #[tauri::command]
 pub async fn download_model(
-    model_manager: State<'_, Arc<ModelManager>>,
+    download_manager: State<'_, Arc<DownloadManager>>,
     model_id: String,
+    priority: Option<DownloadPriority>,
+    bandwidth_limit_kbps: Option<u64>,
 ) -> Result<(), String> {
-    model_manager
-        .download_model(&model_id)
-        .await
+    download_manager
+        .enqueue(
+            &model_id,
+            priority.unwrap_or_default(),
+            bandwidth_limit_kbps,
+        )
         .map_err(|e| e.to_string())
 }
 
@@ -40,6 +48,55 @@ pub async fn delete_model(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn register_custom_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    name: String,
+    path: String,
+    engine_type: EngineType,
+    description: Option<String>,
+    accuracy_score: Option<f32>,
+    speed_score: Option<f32>,
+) -> Result<ModelInfo, String> {
+    model_manager
+        .register_custom_model(
+            &name,
+            &PathBuf::from(path),
+            engine_type,
+            description,
+            accuracy_score,
+            speed_score,
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_custom_model(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    model_id: String,
+) -> Result<(), String> {
+    // Don't leave a removed model loaded or selected
+    if transcription_manager.get_current_model().as_deref() == Some(model_id.as_str()) {
+        transcription_manager
+            .unload_model()
+            .map_err(|e| e.to_string())?;
+    }
+
+    model_manager
+        .remove_custom_model(&model_id)
+        .map_err(|e| e.to_string())?;
+
+    let mut settings = get_settings(&app_handle);
+    if settings.selected_model == model_id {
+        settings.selected_model = String::new();
+        write_settings(&app_handle, settings);
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn set_active_model(
     app_handle: AppHandle,
@@ -110,16 +167,118 @@ pub async fn has_any_models_or_downloads(
 
 #[tauri::command]
 pub async fn cancel_download(
+    download_manager: State<'_, Arc<DownloadManager>>,
+    model_id: String,
+) -> Result<(), String> {
+    download_manager
+        .cancel(&model_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pause_download(
+    download_manager: State<'_, Arc<DownloadManager>>,
+    model_id: String,
+) -> Result<(), String> {
+    download_manager.pause(&model_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resume_download(
+    download_manager: State<'_, Arc<DownloadManager>>,
+    model_id: String,
+) -> Result<(), String> {
+    download_manager
+        .resume(&model_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_download_priority(
+    download_manager: State<'_, Arc<DownloadManager>>,
+    model_id: String,
+    priority: DownloadPriority,
+) -> Result<(), String> {
+    download_manager
+        .set_priority(&model_id, priority)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_download_bandwidth_limit(
+    download_manager: State<'_, Arc<DownloadManager>>,
+    model_id: String,
+    kbps: u64,
+) -> Result<(), String> {
+    download_manager
+        .set_bandwidth_limit(&model_id, kbps)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_download_queue(
+    download_manager: State<'_, Arc<DownloadManager>>,
+) -> Result<DownloadQueueProgress, String> {
+    Ok(download_manager.get_queue())
+}
+
+#[tauri::command]
+pub async fn verify_models(
     model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<ModelVerification>, String> {
+    // Hashing multi-gigabyte models would stall the async runtime
+    let model_manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || model_manager.verify_models())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn redownload_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    download_manager: State<'_, Arc<DownloadManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
     model_id: String,
 ) -> Result<(), String> {
+    // A loaded model keeps its files in use, so let go of it first
+    if transcription_manager.get_current_model().as_deref() == Some(model_id.as_str()) {
+        transcription_manager
+            .unload_model()
+            .map_err(|e| e.to_string())?;
+    }
+
     model_manager
-        .cancel_download(&model_id)
+        .discard_model_files(&model_id)
+        .map_err(|e| e.to_string())?;
+
+    // Repairs jump the queue, a broken model is likely blocking transcription
+    download_manager
+        .enqueue(&model_id, DownloadPriority::High, None)
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn get_recommended_first_model() -> Result<String, String> {
-    // Recommend Parakeet V3 model for first-time users - fastest and most accurate
-    Ok("parakeet-tdt-0.6b-v3".to_string())
+pub async fn get_recommended_first_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<String, String> {
+    // Prefer what benchmarks measured on this machine, otherwise Parakeet V3
+    // is the fastest and most accurate choice for most first-time users
+    Ok(model_manager
+        .recommended_model()
+        .unwrap_or_else(|| "parakeet-tdt-0.6b-v3".to_string()))
+}
+
+#[tauri::command]
+pub async fn benchmark_models(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_ids: Option<Vec<String>>,
+) -> Result<Vec<BenchmarkOutcome>, String> {
+    let model_manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        benchmark::run_benchmarks(&app_handle, &model_manager, &model_ids.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
 }
@@ -1,5 +1,7 @@
+use crate::evaluation::{self, EvaluationResult};
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings, ModelUnloadTimeout};
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, State};
 
 #[tauri::command]
@@ -15,10 +17,12 @@ pub fn get_model_load_status(
 ) -> Result<serde_json::Value, String> {
     let is_loaded = transcription_manager.is_model_loaded();
     let current_model = transcription_manager.get_current_model();
+    let model_memory_bytes = transcription_manager.model_memory_bytes();
 
     Ok(serde_json::json!({
         "is_loaded": is_loaded,
-        "current_model": current_model
+        "current_model": current_model,
+        "model_memory_bytes": model_memory_bytes
     }))
 }
 
@@ -30,3 +34,28 @@ pub fn unload_model_manually(
         .unload_model()
         .map_err(|e| format!("Failed to unload model: {}", e))
 }
+
+#[tauri::command]
+pub async fn evaluate_dataset(
+    app: AppHandle,
+    dataset_dir: String,
+    config_paths: Option<Vec<String>>,
+    output_dir: Option<String>,
+) -> Result<EvaluationResult, String> {
+    let config_paths: Vec<PathBuf> = config_paths
+        .unwrap_or_default()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    tauri::async_runtime::spawn_blocking(move || {
+        evaluation::evaluate_and_report(
+            &app,
+            Path::new(&dataset_dir),
+            &config_paths,
+            output_dir.map(PathBuf::from),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
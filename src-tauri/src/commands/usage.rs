@@ -0,0 +1,20 @@
+use crate::managers::usage::{DailyUsage, UsageManager, UsageSummary};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+#[tauri::command]
+pub async fn get_usage_by_day(
+    _app: AppHandle,
+    usage_manager: State<'_, Arc<UsageManager>>,
+    days: u32,
+) -> Result<Vec<DailyUsage>, String> {
+    usage_manager.usage_by_day(days).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_usage_summary(
+    _app: AppHandle,
+    usage_manager: State<'_, Arc<UsageManager>>,
+) -> Result<UsageSummary, String> {
+    usage_manager.summary().map_err(|e| e.to_string())
+}
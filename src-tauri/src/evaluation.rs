@@ -0,0 +1,620 @@
+use crate::audio_toolkit::{
+    character_error_rate, normalize_for_scoring, read_wav_file, word_error_rate,
+};
+use crate::cancellation::CancellationToken;
+use crate::managers::transcription::TranscriptionManager;
+use crate::managers::usage::UsageManager;
+use crate::post_process::{run_pipeline, PipelineInput, PromptContext};
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout};
+use anyhow::Result;
+use log::{error, info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+const SAMPLE_RATE: f32 = 16000.0;
+const REFERENCE_EXTENSION: &str = "txt";
+const JSON_REPORT_FILE: &str = "evaluation.json";
+const MARKDOWN_REPORT_FILE: &str = "evaluation.md";
+
+static EVALUATION_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// A recording from the dataset directory and its reference transcript.
+pub struct EvaluationSample {
+    pub name: String,
+    pub audio: Vec<f32>,
+    pub reference: String,
+}
+
+/// Settings to evaluate. Config files are partial settings JSON layered
+/// over the user's current settings, with an optional `name` for reports.
+pub struct EvaluationConfig {
+    pub name: String,
+    pub settings: AppSettings,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileEvaluation {
+    pub file: String,
+    pub reference: String,
+    /// Raw model output
+    pub transcription: String,
+    /// Output of the post-processing pipeline
+    pub final_text: String,
+    pub word_error_rate: f32,
+    pub character_error_rate: f32,
+    pub processing_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigEvaluation {
+    pub name: String,
+    pub model_id: String,
+    /// Corpus-level rates, weighted by reference length
+    pub word_error_rate: f32,
+    pub character_error_rate: f32,
+    pub real_time_factor: f32,
+    pub file_count: usize,
+    pub failed_count: usize,
+    pub files: Vec<FileEvaluation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EvaluationReport {
+    pub dataset: String,
+    pub created_at: i64,
+    pub configs: Vec<ConfigEvaluation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EvaluationProgress {
+    pub config: String,
+    pub config_index: usize,
+    pub config_count: usize,
+    pub file_index: usize,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EvaluationResult {
+    pub report: EvaluationReport,
+    pub json_report: String,
+    pub markdown_report: String,
+}
+
+impl EvaluationConfig {
+    pub fn current(base: &AppSettings) -> Self {
+        Self {
+            name: "current".to_string(),
+            settings: base.clone(),
+        }
+    }
+
+    pub fn load(path: &Path, base: &AppSettings) -> Result<Self> {
+        let overrides: Value = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| anyhow::anyhow!("Invalid config {:?}: {}", path, e))?;
+        let Value::Object(mut overrides) = overrides else {
+            return Err(anyhow::anyhow!("Config {:?} must be a JSON object", path));
+        };
+
+        let name = overrides
+            .remove("name")
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_else(|| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "config".to_string())
+            });
+
+        let mut merged = serde_json::to_value(base)?;
+        if let Value::Object(fields) = &mut merged {
+            fields.extend(overrides);
+        }
+        let settings = serde_json::from_value(merged)
+            .map_err(|e| anyhow::anyhow!("Invalid settings in {:?}: {}", path, e))?;
+
+        Ok(Self { name, settings })
+    }
+}
+
+/// Loads every WAV file in `dir` that has a same-named `.txt` reference next to it.
+pub fn load_dataset(dir: &Path) -> Result<Vec<EvaluationSample>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| anyhow::anyhow!("Cannot read dataset {:?}: {}", dir, e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .map(|ext| ext.eq_ignore_ascii_case("wav"))
+                .unwrap_or(false)
+        })
+        .collect();
+    paths.sort();
+
+    let mut samples = Vec::with_capacity(paths.len());
+    for path in paths {
+        let reference_path = path.with_extension(REFERENCE_EXTENSION);
+        let reference = match fs::read_to_string(&reference_path) {
+            Ok(reference) => reference.trim().to_string(),
+            Err(_) => {
+                warn!("Skipping {:?}: no reference transcript", path);
+                continue;
+            }
+        };
+
+        match read_wav_file(&path) {
+            Ok(audio) => samples.push(EvaluationSample {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                audio,
+                reference,
+            }),
+            Err(e) => warn!("Skipping {:?}: {}", path, e),
+        }
+    }
+    Ok(samples)
+}
+
+/// Runs every sample through the dictation pipeline with the given
+/// settings. Files that fail to transcribe are scored as empty output.
+pub fn evaluate_config(
+    transcription_manager: &TranscriptionManager,
+    usage_manager: &UsageManager,
+    samples: &[EvaluationSample],
+    config: &EvaluationConfig,
+    mut on_file: impl FnMut(usize),
+) -> Result<ConfigEvaluation> {
+    let mut settings = config.settings.clone();
+    // The model has to stay resident between files
+    settings.model_unload_timeout = ModelUnloadTimeout::Never;
+
+    if settings.selected_model.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Config '{}' does not select a model",
+            config.name
+        ));
+    }
+    if transcription_manager.get_current_model().as_deref()
+        != Some(settings.selected_model.as_str())
+    {
+        transcription_manager.load_model(&settings.selected_model)?;
+    }
+
+    // Evaluations are not cancellable; this token is never fired
+    let cancel = CancellationToken::new();
+    let mut files = Vec::with_capacity(samples.len());
+    let mut audio_seconds = 0.0f32;
+    let mut processing = Duration::ZERO;
+    let mut word_errors = 0.0f64;
+    let mut reference_words = 0usize;
+    let mut char_errors = 0.0f64;
+    let mut reference_chars = 0usize;
+
+    for (index, sample) in samples.iter().enumerate() {
+        on_file(index);
+
+        let start = Instant::now();
+        let (transcription, final_text, error) = match transcription_manager
+            .transcribe_with_settings(sample.audio.clone(), &settings, &cancel)
+        {
+            Ok(transcription) => {
+                let output = tauri::async_runtime::block_on(run_pipeline(PipelineInput {
+                    settings: &settings,
+                    text: &transcription,
+                    speech_ratio: None,
+                    translated: false,
+                    cancel: &cancel,
+                    usage_manager,
+                    context: &PromptContext::default(),
+                }));
+                (transcription, output.text, None)
+            }
+            Err(e) => {
+                warn!("Evaluation of {} failed: {}", sample.name, e);
+                (String::new(), String::new(), Some(e.to_string()))
+            }
+        };
+        let elapsed = start.elapsed();
+        processing += elapsed;
+        audio_seconds += sample.audio.len() as f32 / SAMPLE_RATE;
+
+        let wer = word_error_rate(&sample.reference, &final_text);
+        let cer = character_error_rate(&sample.reference, &final_text);
+        let words = normalize_for_scoring(&sample.reference);
+        let chars = words.concat().chars().count();
+        word_errors += wer * words.len() as f64;
+        reference_words += words.len();
+        char_errors += cer * chars as f64;
+        reference_chars += chars;
+
+        files.push(FileEvaluation {
+            file: sample.name.clone(),
+            reference: sample.reference.clone(),
+            transcription,
+            final_text,
+            word_error_rate: wer as f32,
+            character_error_rate: cer as f32,
+            processing_ms: elapsed.as_millis() as u64,
+            error,
+        });
+    }
+
+    Ok(ConfigEvaluation {
+        name: config.name.clone(),
+        model_id: settings.selected_model.clone(),
+        word_error_rate: (word_errors / reference_words.max(1) as f64) as f32,
+        character_error_rate: (char_errors / reference_chars.max(1) as f64) as f32,
+        real_time_factor: processing.as_secs_f32() / audio_seconds.max(f32::EPSILON),
+        file_count: files.len(),
+        failed_count: files.iter().filter(|file| file.error.is_some()).count(),
+        files,
+    })
+}
+
+/// Evaluates a dataset against each config file, or against the current
+/// settings when none are given. Blocks until every config has run, so
+/// call it off the async runtime.
+pub fn run_evaluation(
+    app: &AppHandle,
+    dataset_dir: &Path,
+    config_paths: &[PathBuf],
+) -> Result<EvaluationReport> {
+    if EVALUATION_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(anyhow::anyhow!("An evaluation is already running"));
+    }
+
+    let transcription_manager = app.state::<Arc<TranscriptionManager>>().inner().clone();
+    let usage_manager = app.state::<Arc<UsageManager>>().inner().clone();
+    let previous_model = transcription_manager.get_current_model();
+
+    let result = (|| {
+        let base = get_settings(app);
+        let configs = if config_paths.is_empty() {
+            vec![EvaluationConfig::current(&base)]
+        } else {
+            config_paths
+                .iter()
+                .map(|path| EvaluationConfig::load(path, &base))
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        let samples = load_dataset(dataset_dir)?;
+        if samples.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No WAV files with a matching .txt reference found in {:?}",
+                dataset_dir
+            ));
+        }
+
+        let mut evaluations = Vec::with_capacity(configs.len());
+        for (config_index, config) in configs.iter().enumerate() {
+            info!(
+                "Evaluating config '{}' on {} files",
+                config.name,
+                samples.len()
+            );
+            let evaluation = evaluate_config(
+                &transcription_manager,
+                &usage_manager,
+                &samples,
+                config,
+                |file_index| {
+                    let _ = app.emit(
+                        "evaluation-progress",
+                        EvaluationProgress {
+                            config: config.name.clone(),
+                            config_index,
+                            config_count: configs.len(),
+                            file_index,
+                            file_count: samples.len(),
+                        },
+                    );
+                },
+            )?;
+            info!(
+                "Config '{}': WER {:.3}, CER {:.3}, RTF {:.3}",
+                evaluation.name,
+                evaluation.word_error_rate,
+                evaluation.character_error_rate,
+                evaluation.real_time_factor
+            );
+            evaluations.push(evaluation);
+        }
+
+        Ok(EvaluationReport {
+            dataset: dataset_dir.to_string_lossy().to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            configs: evaluations,
+        })
+    })();
+
+    // Put back whatever the user had loaded for dictation
+    let restored = match previous_model {
+        Some(model_id) if transcription_manager.get_current_model() != Some(model_id.clone()) => {
+            transcription_manager.load_model(&model_id)
+        }
+        Some(_) => Ok(()),
+        None => transcription_manager.unload_model(),
+    };
+    if let Err(e) = restored {
+        warn!("Failed to restore model after evaluation: {}", e);
+    }
+
+    EVALUATION_RUNNING.store(false, Ordering::SeqCst);
+    result
+}
+
+fn percent(rate: f32) -> String {
+    format!("{:.2}%", rate * 100.0)
+}
+
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Renders the report as Markdown, comparing every config against the first.
+pub fn markdown_report(report: &EvaluationReport) -> String {
+    let mut md = String::from("# Transcription Evaluation\n\n");
+    let file_count = report.configs.first().map_or(0, |c| c.file_count);
+    md.push_str(&format!(
+        "Dataset: `{}` ({} files)\n\n",
+        report.dataset, file_count
+    ));
+
+    md.push_str("## Summary\n\n");
+    md.push_str("| Configuration | Model | WER | CER | RTF | Failed |\n");
+    md.push_str("|---|---|---|---|---|---|\n");
+    for config in &report.configs {
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {:.3} | {} |\n",
+            table_cell(&config.name),
+            table_cell(&config.model_id),
+            percent(config.word_error_rate),
+            percent(config.character_error_rate),
+            config.real_time_factor,
+            config.failed_count
+        ));
+    }
+
+    let Some(baseline) = report.configs.first() else {
+        return md;
+    };
+    let others = &report.configs[1..];
+
+    md.push_str("\n## Per-file WER\n\n| File |");
+    for config in &report.configs {
+        md.push_str(&format!(" {} |", table_cell(&config.name)));
+    }
+    for config in others {
+        md.push_str(&format!(" Δ {} |", table_cell(&config.name)));
+    }
+    md.push_str("\n|---|");
+    md.push_str(&"---|".repeat(report.configs.len() + others.len()));
+    md.push('\n');
+
+    for (index, file) in baseline.files.iter().enumerate() {
+        md.push_str(&format!("| {} |", table_cell(&file.file)));
+        for config in &report.configs {
+            md.push_str(&format!(
+                " {} |",
+                percent(config.files[index].word_error_rate)
+            ));
+        }
+        for config in others {
+            let delta = config.files[index].word_error_rate - file.word_error_rate;
+            md.push_str(&format!(" {:+.2} |", delta * 100.0));
+        }
+        md.push('\n');
+    }
+
+    md.push_str("\n## Transcripts\n");
+    for (index, file) in baseline.files.iter().enumerate() {
+        md.push_str(&format!("\n### {}\n\n", file.file));
+        md.push_str(&format!("- **Reference:** {}\n", file.reference));
+        for config in &report.configs {
+            let result = &config.files[index];
+            match &result.error {
+                Some(error) => md.push_str(&format!("- **{}:** _error: {}_\n", config.name, error)),
+                None => md.push_str(&format!("- **{}:** {}\n", config.name, result.final_text)),
+            }
+        }
+    }
+
+    md
+}
+
+/// Writes `evaluation.json` and `evaluation.md` into `output_dir`.
+pub fn write_reports(report: &EvaluationReport, output_dir: &Path) -> Result<(PathBuf, PathBuf)> {
+    fs::create_dir_all(output_dir)?;
+
+    let json_path = output_dir.join(JSON_REPORT_FILE);
+    fs::write(&json_path, serde_json::to_string_pretty(report)?)?;
+
+    let markdown_path = output_dir.join(MARKDOWN_REPORT_FILE);
+    fs::write(&markdown_path, markdown_report(report))?;
+
+    Ok((json_path, markdown_path))
+}
+
+/// Timestamped folder under the app data directory for a new report.
+pub fn default_output_dir(app: &AppHandle) -> Result<PathBuf> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| anyhow::anyhow!("Failed to get app data directory: {}", e))?;
+    Ok(dir
+        .join("evaluations")
+        .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string()))
+}
+
+/// Runs an evaluation and writes both reports.
+pub fn evaluate_and_report(
+    app: &AppHandle,
+    dataset_dir: &Path,
+    config_paths: &[PathBuf],
+    output_dir: Option<PathBuf>,
+) -> Result<EvaluationResult> {
+    let report = run_evaluation(app, dataset_dir, config_paths)?;
+    let output_dir = match output_dir {
+        Some(dir) => dir,
+        None => default_output_dir(app)?,
+    };
+    let (json_path, markdown_path) = write_reports(&report, &output_dir)?;
+
+    Ok(EvaluationResult {
+        report,
+        json_report: json_path.to_string_lossy().to_string(),
+        markdown_report: markdown_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Command-line request: `--evaluate <dataset> [--config <file>]... [--output <dir>]`.
+#[derive(Debug, Clone)]
+pub struct EvaluationArgs {
+    pub dataset: PathBuf,
+    pub configs: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+}
+
+impl EvaluationArgs {
+    /// Returns `Ok(None)` when `--evaluate` is absent. Relative paths are
+    /// resolved against `cwd`.
+    pub fn from_args(args: &[String], cwd: &Path) -> Result<Option<Self>, String> {
+        let mut dataset = None;
+        let mut configs = Vec::new();
+        let mut output = None;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if !matches!(arg.as_str(), "--evaluate" | "--config" | "--output") {
+                continue;
+            }
+            let value = cwd.join(
+                iter.next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?,
+            );
+            match arg.as_str() {
+                "--evaluate" => dataset = Some(value),
+                "--config" => configs.push(value),
+                _ => output = Some(value),
+            }
+        }
+
+        match dataset {
+            Some(dataset) => Ok(Some(Self {
+                dataset,
+                configs,
+                output,
+            })),
+            None if !configs.is_empty() || output.is_some() => {
+                Err("--config and --output require --evaluate <dataset>".to_string())
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Runs a command-line evaluation on a background thread, printing the
+/// scores and where the reports went, then quits the app with a non-zero
+/// code on failure.
+pub fn run_cli(app: AppHandle, args: EvaluationArgs) {
+    std::thread::spawn(move || {
+        let code =
+            match evaluate_and_report(&app, &args.dataset, &args.configs, args.output.clone()) {
+                Ok(result) => {
+                    for config in &result.report.configs {
+                        println!(
+                            "{}: WER {} CER {} ({} files, {} failed)",
+                            config.name,
+                            percent(config.word_error_rate),
+                            percent(config.character_error_rate),
+                            config.file_count,
+                            config.failed_count
+                        );
+                    }
+                    println!("JSON report: {}", result.json_report);
+                    println!("Markdown report: {}", result.markdown_report);
+                    0
+                }
+                Err(e) => {
+                    error!("Evaluation failed: {}", e);
+                    eprintln!("Evaluation failed: {}", e);
+                    1
+                }
+            };
+
+        app.exit(code);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn from_args_resolves_paths_against_the_working_directory() {
+        let cwd = Path::new("/work");
+        let parsed = EvaluationArgs::from_args(
+            &args(&[
+                "handy",
+                "--evaluate",
+                "dataset",
+                "--config",
+                "baseline.json",
+                "--config",
+                "/abs/candidate.json",
+                "--output",
+                "report",
+            ]),
+            cwd,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(parsed.dataset, PathBuf::from("/work/dataset"));
+        assert_eq!(
+            parsed.configs,
+            vec![
+                PathBuf::from("/work/baseline.json"),
+                PathBuf::from("/abs/candidate.json")
+            ]
+        );
+        assert_eq!(parsed.output, Some(PathBuf::from("/work/report")));
+    }
+
+    #[test]
+    fn from_args_ignores_a_normal_launch() {
+        let cwd = Path::new("/work");
+        assert!(EvaluationArgs::from_args(&args(&["handy"]), cwd)
+            .unwrap()
+            .is_none());
+        assert!(
+            EvaluationArgs::from_args(&args(&["handy", "--start-hidden"]), cwd)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn from_args_rejects_incomplete_commands() {
+        let cwd = Path::new("/work");
+        assert!(EvaluationArgs::from_args(&args(&["handy", "--evaluate"]), cwd).is_err());
+        assert!(EvaluationArgs::from_args(
+            &args(&["handy", "--evaluate", "dataset", "--output"]),
+            cwd
+        )
+        .is_err());
+        assert!(EvaluationArgs::from_args(&args(&["handy", "--config", "a.json"]), cwd).is_err());
+    }
+}
@@ -0,0 +1,282 @@
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::Command;
+
+/// The application the user is typing into.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActiveWindow {
+    /// Application name, e.g. "Slack" or "firefox"
+    pub app_name: String,
+    pub title: String,
+    /// Name of the owning process where it differs from `app_name`, which
+    /// on Linux is the window class
+    pub process_name: Option<String>,
+}
+
+impl ActiveWindow {
+    /// The names app profiles can match on.
+    pub fn names(&self) -> Vec<&str> {
+        std::iter::once(self.app_name.as_str())
+            .chain(self.process_name.as_deref())
+            .collect()
+    }
+}
+
+/// The focused window
+///
+/// Follows `_NET_ACTIVE_WINDOW` with xprop and reads its WM_CLASS, title
+/// and owning process. Only works under X11 or XWayland.
+#[cfg(target_os = "linux")]
+pub fn active_window() -> Option<ActiveWindow> {
+    let root = xprop(&["-root", "_NET_ACTIVE_WINDOW"])?;
+    let window_id = parse_window_id(&root)?;
+    let properties = xprop(&[
+        "-id",
+        &window_id,
+        "WM_CLASS",
+        "_NET_WM_NAME",
+        "WM_NAME",
+        "_NET_WM_PID",
+    ])?;
+    let mut window = parse_window_properties(&properties)?;
+    window.process_name = parse_window_pid(&properties)
+        .and_then(|pid| std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok())
+        .map(|comm| comm.trim().to_string())
+        .filter(|comm| !comm.is_empty());
+    Some(window)
+}
+
+#[cfg(target_os = "linux")]
+fn xprop(args: &[&str]) -> Option<String> {
+    let output = Command::new("xprop").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parses `_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007`. A zero id
+/// means nothing has focus.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_window_id(output: &str) -> Option<String> {
+    let id = output.rsplit('#').next()?.trim();
+    let id = id.split(',').next()?.trim();
+    let digits = id.strip_prefix("0x")?;
+    if digits.is_empty() || u64::from_str_radix(digits, 16).ok()? == 0 {
+        return None;
+    }
+    Some(id.to_string())
+}
+
+/// Reads the application from the class part of WM_CLASS and the title
+/// from `_NET_WM_NAME`, falling back to `WM_NAME`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_window_properties(output: &str) -> Option<ActiveWindow> {
+    let mut window = ActiveWindow::default();
+    let mut legacy_title = None;
+
+    for line in output.lines() {
+        let Some((name, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        let strings = quoted_strings(value);
+        if name.starts_with("WM_CLASS") {
+            // Instance first, then class; the class is the readable one
+            if let Some(class) = strings.last() {
+                window.app_name = class.clone();
+            }
+        } else if name.starts_with("_NET_WM_NAME") {
+            window.title = strings.into_iter().next().unwrap_or_default();
+        } else if name.starts_with("WM_NAME") {
+            legacy_title = strings.into_iter().next();
+        }
+    }
+
+    if window.title.is_empty() {
+        window.title = legacy_title.unwrap_or_default();
+    }
+    if window.app_name.is_empty() && window.title.is_empty() {
+        return None;
+    }
+    Some(window)
+}
+
+/// Parses `_NET_WM_PID(CARDINAL) = 4242`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_window_pid(output: &str) -> Option<u32> {
+    output.lines().find_map(|line| {
+        let (name, value) = line.split_once(" = ")?;
+        if !name.starts_with("_NET_WM_PID") {
+            return None;
+        }
+        value.trim().parse().ok()
+    })
+}
+
+/// The double-quoted strings of an xprop value, unescaped.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn quoted_strings(value: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = value.chars();
+    while chars.by_ref().any(|c| c == '"') {
+        let mut current = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => current.extend(chars.next()),
+                '"' => break,
+                c => current.push(c),
+            }
+        }
+        strings.push(current);
+    }
+    strings
+}
+
+/// The focused window
+///
+/// Asks System Events through osascript, which needs the accessibility
+/// permission the app already uses for pasting.
+#[cfg(target_os = "macos")]
+pub fn active_window() -> Option<ActiveWindow> {
+    let script = r#"tell application "System Events"
+    set frontApp to first application process whose frontmost is true
+    set appName to name of frontApp
+    set windowTitle to ""
+    try
+        set windowTitle to name of front window of frontApp
+    end try
+end tell
+return appName & linefeed & windowTitle"#;
+    let output = Command::new("osascript")
+        .args(["-e", script])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.trim_end().splitn(2, '\n');
+    let app_name = lines.next()?.trim().to_string();
+    if app_name.is_empty() {
+        return None;
+    }
+    Some(ActiveWindow {
+        app_name,
+        title: lines.next().unwrap_or_default().trim().to_string(),
+        process_name: None,
+    })
+}
+
+/// The focused window
+///
+/// Uses the foreground window's title and the file name, without
+/// extension, of the executable that owns it.
+#[cfg(target_os = "windows")]
+pub fn active_window() -> Option<ActiveWindow> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowTextW, GetWindowThreadProcessId,
+    };
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0.is_null() {
+            return None;
+        }
+
+        let mut title = [0u16; 512];
+        let title_len = GetWindowTextW(hwnd, &mut title).max(0) as usize;
+        let title = String::from_utf16_lossy(&title[..title_len]);
+
+        let mut process_id = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut process_id as *mut u32));
+        let mut app_name = String::new();
+        if let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id) {
+            let mut path = [0u16; 1024];
+            let mut path_len = path.len() as u32;
+            if QueryFullProcessImageNameW(
+                process,
+                PROCESS_NAME_WIN32,
+                PWSTR(path.as_mut_ptr()),
+                &mut path_len,
+            )
+            .is_ok()
+            {
+                let path = String::from_utf16_lossy(&path[..path_len as usize]);
+                app_name = std::path::Path::new(&path)
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+            }
+            let _ = CloseHandle(process);
+        }
+
+        Some(ActiveWindow {
+            app_name,
+            title,
+            process_name: None,
+        })
+    }
+}
+
+/// Stub implementation for platforms without a way to find the focused window
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn active_window() -> Option<ActiveWindow> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_window_id() {
+        assert_eq!(
+            parse_window_id("_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007\n"),
+            Some("0x3a00007".to_string())
+        );
+        assert_eq!(
+            parse_window_id("_NET_ACTIVE_WINDOW(WINDOW): window id # 0x0\n"),
+            None
+        );
+        assert_eq!(parse_window_id("_NET_ACTIVE_WINDOW:  not found.\n"), None);
+    }
+
+    #[test]
+    fn test_parse_window_properties() {
+        let output = "WM_CLASS(STRING) = \"slack\", \"Slack\"\n\
+            _NET_WM_NAME(UTF8_STRING) = \"general - \\\"Team\\\" - Slack\"\n\
+            WM_NAME(STRING) = \"general\"\n";
+        assert_eq!(
+            parse_window_properties(output),
+            Some(ActiveWindow {
+                app_name: "Slack".to_string(),
+                title: "general - \"Team\" - Slack".to_string(),
+                process_name: None,
+            })
+        );
+
+        let legacy = "WM_CLASS(STRING) = \"xterm\", \"XTerm\"\n\
+            _NET_WM_NAME:  not found.\n\
+            WM_NAME(STRING) = \"bash\"\n";
+        assert_eq!(
+            parse_window_properties(legacy).map(|window| window.title),
+            Some("bash".to_string())
+        );
+
+        assert_eq!(parse_window_properties("WM_CLASS:  not found.\n"), None);
+    }
+
+    #[test]
+    fn test_parse_window_pid() {
+        let output = "WM_CLASS(STRING) = \"gnome-terminal-server\", \"Gnome-terminal\"\n\
+            _NET_WM_PID(CARDINAL) = 4242\n";
+        assert_eq!(parse_window_pid(output), Some(4242));
+        assert_eq!(parse_window_pid("_NET_WM_PID:  not found.\n"), None);
+    }
+}
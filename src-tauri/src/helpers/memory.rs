@@ -0,0 +1,75 @@
+#[cfg(target_os = "macos")]
+use std::process::Command;
+
+/// Resident memory of this process in bytes
+///
+/// Reads VmRSS from /proc/self/status.
+#[cfg(target_os = "linux")]
+pub fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| {
+            value
+                .trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse::<u64>()
+                .ok()
+        })
+        .map(|kb| kb * 1024)
+}
+
+/// Resident memory of this process in bytes
+///
+/// Uses ps, which reports the resident set size in kilobytes.
+#[cfg(target_os = "macos")]
+pub fn resident_memory_bytes() -> Option<u64> {
+    let output = Command::new("ps")
+        .args(["-o", "rss=", "-p", &std::process::id().to_string()])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|kb| kb * 1024)
+}
+
+/// Stub implementation for platforms without a cheap way to query memory
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn resident_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Memory the system can still hand out without swapping, in bytes
+///
+/// Reads MemAvailable from /proc/meminfo.
+#[cfg(target_os = "linux")]
+pub fn available_memory_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))
+        .and_then(|value| {
+            value
+                .trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse::<u64>()
+                .ok()
+        })
+        .map(|kb| kb * 1024)
+}
+
+/// Stub implementation for platforms without /proc/meminfo
+#[cfg(not(target_os = "linux"))]
+pub fn available_memory_bytes() -> Option<u64> {
+    None
+}
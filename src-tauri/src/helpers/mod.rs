@@ -1 +1,3 @@
+pub mod active_window;
 pub mod clamshell;
+pub mod memory;
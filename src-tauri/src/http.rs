@@ -0,0 +1,362 @@
+//! Shared HTTP client for every remote call, with per-operation timeouts,
+//! retries with jittered backoff and errors classified for the overlay.
+
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::Serialize;
+use std::fmt;
+use std::net::{IpAddr, UdpSocket};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const BACKOFF_BASE: Duration = Duration::from_millis(500);
+const BACKOFF_MAX: Duration = Duration::from_secs(8);
+/// Longer `Retry-After` waits are reported as quota errors instead
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(20);
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    client_builder()
+        .build()
+        .expect("Failed to build shared HTTP client")
+});
+
+/// The shared client. Cloning is cheap and keeps the connection pool.
+pub fn client() -> reqwest::Client {
+    CLIENT.clone()
+}
+
+/// A builder with the shared connect timeout, for clients that need their
+/// own default headers.
+pub fn client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder().connect_timeout(CONNECT_TIMEOUT)
+}
+
+/// How long a kind of request may take and how often it is retried.
+#[derive(Debug, Clone, Copy)]
+pub struct Operation {
+    pub name: &'static str,
+    pub timeout: Duration,
+    pub attempts: u32,
+    /// Whether a request that may have reached the server can be sent
+    /// again. Requests that never connected are always retried.
+    pub idempotent: bool,
+}
+
+impl Operation {
+    /// Uploading a recording, within `budget`, the time the backend chain
+    /// gives each backend. An upload that reached the server is transcribed
+    /// and billed again if resent, so only failures that show it wasn't
+    /// processed are retried.
+    pub fn transcription(budget: Duration) -> Operation {
+        Operation {
+            name: "transcription",
+            timeout: budget,
+            attempts: 3,
+            idempotent: false,
+        }
+    }
+
+    pub const VALIDATION: Operation = Operation {
+        name: "key validation",
+        timeout: Duration::from_secs(10),
+        attempts: 2,
+        idempotent: true,
+    };
+    pub const MODEL_LIST: Operation = Operation {
+        name: "model list",
+        timeout: Duration::from_secs(15),
+        attempts: 3,
+        idempotent: true,
+    };
+    /// Chat completions, which the OpenAI client already retries on 429
+    pub const POST_PROCESS: Operation = Operation {
+        name: "post-processing",
+        timeout: Duration::from_secs(45),
+        attempts: 1,
+        idempotent: false,
+    };
+}
+
+/// Why a remote call failed, shown as the overlay's error headline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Timeout,
+    Auth,
+    Quota,
+    Network,
+    Other,
+}
+
+/// An error from a remote call or a transcription backend.
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl RequestError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn offline() -> Self {
+        Self::new(ErrorKind::Network, "No internet connection")
+    }
+
+    /// A failed response, classified by its status.
+    pub fn from_status(status: StatusCode, message: impl Into<String>) -> Self {
+        Self::new(classify_status(status), message)
+    }
+
+    /// A failed request, with `context` naming what was attempted.
+    pub fn from_reqwest(error: &reqwest::Error, context: &str) -> Self {
+        let kind = if error.is_timeout() {
+            ErrorKind::Timeout
+        } else if error.is_connect() || error.is_request() || error.is_body() {
+            ErrorKind::Network
+        } else {
+            error
+                .status()
+                .map(classify_status)
+                .unwrap_or(ErrorKind::Other)
+        };
+        Self::new(kind, format!("{}: {}", context, error))
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+impl From<String> for RequestError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<&str> for RequestError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<RequestError> for String {
+    fn from(error: RequestError) -> Self {
+        error.message
+    }
+}
+
+pub fn classify_status(status: StatusCode) -> ErrorKind {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorKind::Auth,
+        StatusCode::PAYMENT_REQUIRED | StatusCode::TOO_MANY_REQUESTS => ErrorKind::Quota,
+        StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => ErrorKind::Timeout,
+        _ => ErrorKind::Other,
+    }
+}
+
+/// Sends the request built by `build` with the operation's timeout,
+/// retrying transient failures. Responses with an error status are still
+/// returned once retries run out, so callers can read the error body.
+///
+/// `build` runs once per attempt because multipart bodies can't be cloned.
+pub async fn send<F>(operation: Operation, build: F) -> Result<Response, RequestError>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut attempt = 1;
+    loop {
+        let (client, request) = build().timeout(operation.timeout).build_split();
+        let request =
+            request.map_err(|e| RequestError::from_reqwest(&e, "Failed to build request"))?;
+        let is_local = is_local_host(request.url());
+        if attempt == 1 && !is_local && is_offline() {
+            return Err(RequestError::offline());
+        }
+
+        let context = format!("{} request failed", operation.name);
+        let retry_in = match client.execute(request).await {
+            Ok(response) => {
+                if !is_retryable_status(operation, response.status())
+                    || attempt >= operation.attempts
+                {
+                    return Ok(response);
+                }
+                match retry_after(&response) {
+                    Some(wait) if wait > MAX_RETRY_AFTER => return Ok(response),
+                    Some(wait) => wait,
+                    None => backoff(attempt),
+                }
+            }
+            Err(e) => {
+                let retryable = e.is_connect() || (operation.idempotent && !e.is_builder());
+                if !retryable || attempt >= operation.attempts {
+                    return Err(RequestError::from_reqwest(&e, &context));
+                }
+                if e.is_connect() && !is_local && is_offline() {
+                    return Err(RequestError::offline());
+                }
+                debug!("{}: {}", context, e);
+                backoff(attempt)
+            }
+        };
+
+        warn!(
+            "Retrying {} request in {:?} (attempt {} of {})",
+            operation.name,
+            retry_in,
+            attempt + 1,
+            operation.attempts
+        );
+        tokio::time::sleep(retry_in).await;
+        attempt += 1;
+    }
+}
+
+/// 429 and 503 mean the server turned the request away without handling
+/// it, so even requests that aren't idempotent can be sent again.
+fn is_retryable_status(operation: Operation, status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::SERVICE_UNAVAILABLE
+        || (operation.idempotent && status.is_server_error())
+}
+
+/// Exponential backoff with jitter, between half and all of the step so
+/// clients that failed together don't retry together.
+fn backoff(attempt: u32) -> Duration {
+    let step = BACKOFF_BASE
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(BACKOFF_MAX);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let jitter = nanos as f64 / 1_000_000_000.0;
+    step.mul_f64(0.5 + jitter * 0.5)
+}
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+/// `Retry-After` is either a number of seconds or an HTTP date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Self-hosted servers stay reachable without internet access.
+fn is_local_host(url: &reqwest::Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    match host
+        .trim_matches(|c| c == '[' || c == ']')
+        .parse::<IpAddr>()
+    {
+        Ok(IpAddr::V4(ip)) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        Ok(IpAddr::V6(ip)) => ip.is_loopback(),
+        Err(_) => host == "localhost" || host.ends_with(".local"),
+    }
+}
+
+/// Whether there is no route to the internet. Connecting a UDP socket
+/// only consults the routing table, so this sends nothing and returns
+/// immediately.
+pub fn is_offline() -> bool {
+    let has_route = |target: IpAddr, bind: &str| {
+        UdpSocket::bind(bind)
+            .and_then(|socket| socket.connect((target, 53)))
+            .is_ok()
+    };
+    !has_route(IpAddr::from([1, 1, 1, 1]), "0.0.0.0:0")
+        && !has_route(
+            IpAddr::from([0x2606, 0x4700, 0x4700, 0, 0, 0, 0, 0x1111]),
+            "[::]:0",
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_retry_after_seconds_and_dates() {
+        assert_eq!(parse_retry_after("7"), Some(Duration::from_secs(7)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn backoff_grows_with_jitter_up_to_the_cap() {
+        for attempt in 1..10 {
+            let step = BACKOFF_BASE
+                .saturating_mul(1 << (attempt - 1))
+                .min(BACKOFF_MAX);
+            let wait = backoff(attempt);
+            assert!(wait >= step / 2 && wait <= step, "{:?}", wait);
+        }
+    }
+
+    #[test]
+    fn retries_transcriptions_only_when_the_server_turned_them_away() {
+        let operation = Operation::transcription(Duration::from_secs(30));
+        let retryable = |status| is_retryable_status(operation, status);
+        assert!(retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(retryable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!retryable(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!retryable(StatusCode::GATEWAY_TIMEOUT));
+        assert!(is_retryable_status(
+            Operation::MODEL_LIST,
+            StatusCode::INTERNAL_SERVER_ERROR
+        ));
+    }
+
+    #[test]
+    fn classifies_statuses() {
+        assert_eq!(classify_status(StatusCode::UNAUTHORIZED), ErrorKind::Auth);
+        assert_eq!(
+            classify_status(StatusCode::TOO_MANY_REQUESTS),
+            ErrorKind::Quota
+        );
+        assert_eq!(
+            classify_status(StatusCode::GATEWAY_TIMEOUT),
+            ErrorKind::Timeout
+        );
+        assert_eq!(
+            classify_status(StatusCode::INTERNAL_SERVER_ERROR),
+            ErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn recognises_local_hosts() {
+        let local = |url: &str| is_local_host(&reqwest::Url::parse(url).unwrap());
+        assert!(local("http://localhost:8000/v1"));
+        assert!(local("http://192.168.1.20:9000"));
+        assert!(local("http://[::1]:8080"));
+        assert!(!local("https://api.openai.com/v1"));
+    }
+}
@@ -1,12 +1,19 @@
 mod actions;
 mod audio_feedback;
 pub mod audio_toolkit;
+mod auth;
+mod backends;
+mod benchmark;
+mod cancellation;
 mod clipboard;
 mod commands;
+mod evaluation;
 mod helpers;
+mod http;
 mod llm_client;
 mod managers;
 mod overlay;
+mod post_process;
 mod secure_store;
 mod settings;
 mod shortcut;
@@ -15,10 +22,14 @@ mod tray;
 mod utils;
 
 use env_filter::Builder as EnvFilterBuilder;
+use managers::account::AccountManager;
 use managers::audio::AudioRecordingManager;
+use managers::download::DownloadManager;
 use managers::history::HistoryManager;
 use managers::model::ModelManager;
 use managers::transcription::TranscriptionManager;
+use managers::transcription_queue::TranscriptionQueue;
+use managers::usage::UsageManager;
 #[cfg(unix)]
 use signal_hook::consts::SIGUSR2;
 #[cfg(unix)]
@@ -102,25 +113,59 @@ fn show_main_window(app: &AppHandle) {
     }
 }
 
-fn initialize_core_logic(app_handle: &AppHandle) {
-    // First, initialize the managers
-    let recording_manager = Arc::new(
-        AudioRecordingManager::new(app_handle).expect("Failed to initialize recording manager"),
-    );
+/// Creates the managers a command-line evaluation needs and adds them to
+/// Tauri's managed state.
+fn initialize_managers(app_handle: &AppHandle) {
     let model_manager =
         Arc::new(ModelManager::new(app_handle).expect("Failed to initialize model manager"));
+    let download_manager = Arc::new(
+        DownloadManager::new(app_handle, model_manager.clone())
+            .expect("Failed to initialize download manager"),
+    );
     let transcription_manager = Arc::new(
         TranscriptionManager::new(app_handle, model_manager.clone())
             .expect("Failed to initialize transcription manager"),
     );
     let history_manager =
         Arc::new(HistoryManager::new(app_handle).expect("Failed to initialize history manager"));
+    let transcription_queue = Arc::new(TranscriptionQueue::new(app_handle));
+    let usage_manager =
+        Arc::new(UsageManager::new(app_handle).expect("Failed to initialize usage manager"));
+    let account_manager = Arc::new(AccountManager::new(app_handle));
 
     // Add managers to Tauri's managed state
-    app_handle.manage(recording_manager.clone());
-    app_handle.manage(model_manager.clone());
-    app_handle.manage(transcription_manager.clone());
-    app_handle.manage(history_manager.clone());
+    app_handle.manage(model_manager);
+    app_handle.manage(download_manager);
+    app_handle.manage(transcription_manager);
+    app_handle.manage(history_manager);
+    app_handle.manage(transcription_queue);
+    app_handle.manage(usage_manager);
+    app_handle.manage(account_manager);
+}
+
+fn initialize_core_logic(app_handle: &AppHandle) {
+    // First, initialize the managers. The recording manager may open the
+    // microphone, so it is left out of command-line evaluations.
+    initialize_managers(app_handle);
+    let recording_manager = Arc::new(
+        AudioRecordingManager::new(app_handle).expect("Failed to initialize recording manager"),
+    );
+    app_handle.manage(recording_manager);
+
+    // Pick up downloads that were still queued when the app last closed
+    app_handle.state::<Arc<DownloadManager>>().start();
+
+    // Refresh the remaining credit minutes if signed in
+    let account_manager = Arc::clone(&app_handle.state::<Arc<AccountManager>>());
+    tauri::async_runtime::spawn(async move {
+        account_manager.sync().await;
+    });
+
+    if settings::get_settings(app_handle).preload_model_on_startup {
+        app_handle
+            .state::<Arc<TranscriptionManager>>()
+            .preload_model("startup");
+    }
 
     // Initialize the shortcuts
     shortcut::init_shortcuts(app_handle);
@@ -212,6 +257,17 @@ pub fn run() {
     // when the variable is unset
     let console_filter = build_console_filter();
 
+    // `--evaluate <dataset>` runs an offline evaluation instead of showing the UI
+    let cli_args: Vec<String> = std::env::args().collect();
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let mut cli_evaluation = match evaluation::EvaluationArgs::from_args(&cli_args, &cwd) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
     let mut builder = tauri::Builder::default()
         .plugin(
             LogBuilder::new()
@@ -242,10 +298,15 @@ pub fn run() {
         builder = builder.plugin(tauri_nspanel::init());
     }
 
-    builder
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+    // An evaluation runs in its own headless process instead of being handed
+    // to a running instance, so its results and exit code reach the terminal
+    if cli_evaluation.is_none() {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             show_main_window(app);
-        }))
+        }));
+    }
+
+    builder
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
@@ -268,6 +329,7 @@ pub fn run() {
             Some(vec![]),
         ))
         .manage(Mutex::new(ShortcutToggleStates::default()))
+        .manage(cancellation::InFlightOperations::default())
         .setup(move |app| {
             let settings = settings::get_settings(&app.handle());
             let file_log_level: log::Level = settings.log_level.clone().into();
@@ -275,6 +337,12 @@ pub fn run() {
             FILE_LOG_LEVEL.store(file_log_level.to_level_filter() as u8, Ordering::Relaxed);
             let app_handle = app.handle().clone();
 
+            if let Some(evaluation_args) = cli_evaluation.take() {
+                initialize_managers(&app_handle);
+                evaluation::run_cli(app_handle, evaluation_args);
+                return Ok(());
+            }
+
             initialize_core_logic(&app_handle);
 
             // Show main window only if not starting hidden
@@ -311,23 +379,57 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             shortcut::change_binding,
             shortcut::reset_binding,
+            shortcut::add_binding,
+            shortcut::remove_binding,
             shortcut::change_ptt_setting,
             shortcut::change_audio_feedback_setting,
             shortcut::change_audio_feedback_volume_setting,
             shortcut::change_sound_theme_setting,
             shortcut::change_start_hidden_setting,
             shortcut::change_autostart_setting,
-            shortcut::change_translate_to_english_setting,
-            shortcut::change_selected_language_setting,
+            shortcut::change_translation_target_language_setting,
+            shortcut::change_translation_keep_source_text_setting,
+            shortcut::change_binding_profile,
+            shortcut::change_allowed_languages_setting,
             shortcut::change_overlay_position_setting,
             shortcut::change_debug_mode_setting,
             shortcut::change_transcription_provider,
+            shortcut::change_transcription_fallbacks_setting,
+            shortcut::change_remote_transcription_timeout_setting,
+            shortcut::change_remote_audio_format_setting,
+            shortcut::change_usage_prices_setting,
+            shortcut::change_monthly_budget_setting,
+            shortcut::change_diarization_enabled_setting,
+            shortcut::change_diarization_max_speakers_setting,
+            shortcut::change_speaker_labels_setting,
             shortcut::change_deepgram_model,
+            shortcut::change_deepgram_smart_format_setting,
+            shortcut::change_deepgram_punctuate_setting,
+            shortcut::change_deepgram_numerals_setting,
+            shortcut::change_deepgram_filler_words_setting,
+            shortcut::change_deepgram_streaming_setting,
+            shortcut::change_openai_transcription_base_url,
+            shortcut::change_openai_transcription_model,
+            shortcut::change_openai_transcription_language,
+            shortcut::change_openai_transcription_prompt,
+            shortcut::change_openai_transcription_response_format,
+            shortcut::store_openai_api_key,
             shortcut::set_secure_key_storage,
             shortcut::set_usage_mode,
             shortcut::set_api_base_url,
             shortcut::validate_and_store_deepgram_key,
             shortcut::change_word_correction_threshold_setting,
+            shortcut::change_max_concurrent_downloads_setting,
+            shortcut::change_download_bandwidth_limit_setting,
+            shortcut::change_post_process_pipeline_setting,
+            shortcut::change_replacement_rules_setting,
+            shortcut::change_app_profiles_setting,
+            shortcut::change_min_speech_ratio_setting,
+            shortcut::change_max_parallel_transcriptions_setting,
+            shortcut::change_preload_model_on_startup_setting,
+            shortcut::change_preload_model_on_speech_setting,
+            shortcut::change_warm_up_model_setting,
+            shortcut::change_low_memory_unload_threshold_setting,
             shortcut::change_paste_method_setting,
             shortcut::change_clipboard_handling_setting,
             shortcut::change_post_process_enabled_setting,
@@ -340,6 +442,7 @@ pub fn run() {
             shortcut::update_post_process_prompt,
             shortcut::delete_post_process_prompt,
             shortcut::set_post_process_selected_prompt,
+            shortcut::change_edit_selection_prompt_setting,
             shortcut::update_custom_words,
             shortcut::suspend_binding,
             shortcut::resume_binding,
@@ -358,6 +461,15 @@ pub fn run() {
             commands::models::download_model,
             commands::models::delete_model,
             commands::models::cancel_download,
+            commands::models::pause_download,
+            commands::models::resume_download,
+            commands::models::set_download_priority,
+            commands::models::set_download_bandwidth_limit,
+            commands::models::get_download_queue,
+            commands::models::register_custom_model,
+            commands::models::remove_custom_model,
+            commands::models::verify_models,
+            commands::models::redownload_model,
             commands::models::set_active_model,
             commands::models::get_current_model,
             commands::models::get_transcription_model_status,
@@ -365,6 +477,7 @@ pub fn run() {
             commands::models::has_any_models_available,
             commands::models::has_any_models_or_downloads,
             commands::models::get_recommended_first_model,
+            commands::models::benchmark_models,
             commands::audio::update_microphone_mode,
             commands::audio::get_microphone_mode,
             commands::audio::get_available_microphones,
@@ -382,12 +495,21 @@ pub fn run() {
             commands::transcription::set_model_unload_timeout,
             commands::transcription::get_model_load_status,
             commands::transcription::unload_model_manually,
+            commands::transcription::evaluate_dataset,
             commands::history::get_history_entries,
             commands::history::toggle_history_entry_saved,
             commands::history::get_audio_file_path,
             commands::history::delete_history_entry,
             commands::history::update_history_limit,
-            commands::history::update_recording_retention_period
+            commands::history::update_recording_retention_period,
+            commands::history::export_history_entry,
+            commands::usage::get_usage_by_day,
+            commands::usage::get_usage_summary,
+            commands::account::get_account_status,
+            commands::account::start_account_login,
+            commands::account::cancel_account_login,
+            commands::account::logout_account,
+            commands::account::refresh_account
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,3 +1,4 @@
+use crate::http::{self, Operation};
 use crate::settings::PostProcessProvider;
 use async_openai::{config::OpenAIConfig, Client};
 
@@ -11,23 +12,20 @@ pub fn create_client(
         .with_api_base(base_url)
         .with_api_key(api_key);
 
-    // Create client with Anthropic-specific header if needed
-    let client = if provider.id == "anthropic" {
-        let mut headers = reqwest::header::HeaderMap::new();
+    // Add Anthropic-specific header if needed
+    let mut headers = reqwest::header::HeaderMap::new();
+    if provider.id == "anthropic" {
         headers.insert(
             "anthropic-version",
             reqwest::header::HeaderValue::from_static("2023-06-01"),
         );
+    }
 
-        let http_client = reqwest::Client::builder()
-            .default_headers(headers)
-            .build()
-            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let http_client = http::client_builder()
+        .timeout(Operation::POST_PROCESS.timeout)
+        .default_headers(headers)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
 
-        Client::with_config(config).with_http_client(http_client)
-    } else {
-        Client::with_config(config)
-    };
-
-    Ok(client)
+    Ok(Client::with_config(config).with_http_client(http_client))
 }
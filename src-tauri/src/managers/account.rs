@@ -0,0 +1,206 @@
+use log::{debug, error, info, warn};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::auth::{self, Account, DeviceLogin, Tokens};
+use crate::cancellation::CancellationToken;
+use crate::http::{ErrorKind, RequestError};
+use crate::settings::{get_settings, write_settings};
+
+/// Account state sent to the frontend with `account-updated`.
+#[derive(Clone, Debug, Serialize)]
+pub struct AccountStatus {
+    pub signed_in: bool,
+    pub user_id: Option<String>,
+    pub email: Option<String>,
+    pub minutes_remaining: Option<u32>,
+}
+
+/// Sign-in to the credits backend and the account details shown in the app.
+/// Tokens are kept by `auth` in the credential store; `user_id` and
+/// `minutes_remaining` are mirrored into the settings for the UI.
+pub struct AccountManager {
+    app_handle: AppHandle,
+    account: Mutex<Option<Account>>,
+    /// Cancels the sign-in that is waiting for browser approval
+    pending_login: Mutex<Option<CancellationToken>>,
+}
+
+impl AccountManager {
+    pub fn new(app_handle: &AppHandle) -> Self {
+        let manager = Self {
+            app_handle: app_handle.clone(),
+            account: Mutex::new(None),
+            pending_login: Mutex::new(None),
+        };
+        manager.migrate_plaintext_token();
+        manager
+    }
+
+    /// Older versions kept the access token in the settings file.
+    fn migrate_plaintext_token(&self) {
+        let mut settings = get_settings(&self.app_handle);
+        let Some(token) = settings.auth_token.take() else {
+            return;
+        };
+        if !token.trim().is_empty() && !auth::is_signed_in() {
+            let tokens = Tokens {
+                access_token: token,
+                refresh_token: None,
+            };
+            if let Err(e) = auth::save_tokens(&tokens) {
+                error!("Failed to move the account token to secure storage: {}", e);
+                return;
+            }
+        }
+        info!("Moved the account token from settings to secure storage");
+        write_settings(&self.app_handle, settings);
+    }
+
+    fn base_url(&self) -> Option<String> {
+        get_settings(&self.app_handle)
+            .api_base_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+    }
+
+    pub fn status(&self) -> AccountStatus {
+        let settings = get_settings(&self.app_handle);
+        let account = self.account.lock().unwrap().clone();
+        AccountStatus {
+            signed_in: auth::is_signed_in(),
+            user_id: settings.user_id,
+            email: account.and_then(|account| account.email),
+            minutes_remaining: settings.minutes_remaining,
+        }
+    }
+
+    fn emit_status(&self) {
+        if let Err(e) = self.app_handle.emit("account-updated", self.status()) {
+            error!("Failed to emit account-updated event: {}", e);
+        }
+    }
+
+    fn emit_login_failed(&self, error: &RequestError) {
+        if let Err(e) = self.app_handle.emit("account-login-failed", error) {
+            error!("Failed to emit account-login-failed event: {}", e);
+        }
+    }
+
+    /// Starts a sign-in, opens the verification page in the browser and
+    /// waits for approval in the background. The returned code is shown
+    /// so the user can check it matches the one in the browser.
+    pub async fn start_login(self: &Arc<Self>) -> Result<DeviceLogin, RequestError> {
+        let base_url = self
+            .base_url()
+            .ok_or_else(|| RequestError::from("Set the API base URL before signing in"))?;
+        let login = auth::start_device_login(&base_url).await?;
+
+        if let Err(e) = self
+            .app_handle
+            .opener()
+            .open_url(login.browser_url(), None::<String>)
+        {
+            warn!("Failed to open the sign-in page: {}", e);
+        }
+
+        let cancel = CancellationToken::new();
+        if let Some(previous) = self.pending_login.lock().unwrap().replace(cancel.clone()) {
+            previous.cancel();
+        }
+
+        let manager = self.clone();
+        let pending = login.clone();
+        tauri::async_runtime::spawn(async move {
+            let Ok(result) = cancel
+                .run_until_cancelled(auth::wait_for_device_login(&base_url, &pending))
+                .await
+            else {
+                debug!("Sign-in cancelled");
+                return;
+            };
+            // Not cancelled, so no newer sign-in replaced this one
+            manager.pending_login.lock().unwrap().take();
+            manager.finish_login(result).await;
+        });
+
+        Ok(login)
+    }
+
+    async fn finish_login(&self, result: Result<Tokens, RequestError>) {
+        let tokens = match result {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                warn!("Sign-in failed: {}", e);
+                self.emit_login_failed(&e);
+                return;
+            }
+        };
+
+        if let Err(e) = auth::save_tokens(&tokens) {
+            error!("Failed to store account tokens: {}", e);
+            self.emit_login_failed(&RequestError::from(e));
+            return;
+        }
+        info!("Signed in to the credits backend");
+        self.sync().await;
+    }
+
+    pub fn cancel_login(&self) {
+        if let Some(pending) = self.pending_login.lock().unwrap().take() {
+            pending.cancel();
+        }
+    }
+
+    pub fn logout(&self) {
+        self.cancel_login();
+        auth::clear_tokens();
+        *self.account.lock().unwrap() = None;
+
+        let mut settings = get_settings(&self.app_handle);
+        settings.user_id = None;
+        settings.minutes_remaining = None;
+        write_settings(&self.app_handle, settings);
+
+        info!("Signed out of the credits backend");
+        self.emit_status();
+    }
+
+    /// Reloads the account from `/api/me` and stores the remaining minutes.
+    /// Does nothing when signed out. A session that can no longer be
+    /// refreshed signs the user out.
+    pub async fn sync(&self) {
+        let Some(base_url) = self.base_url() else {
+            return;
+        };
+        if !auth::is_signed_in() {
+            return;
+        }
+
+        match auth::fetch_account(&base_url).await {
+            Ok(account) => {
+                let mut settings = get_settings(&self.app_handle);
+                if settings.user_id.as_deref() != Some(account.user_id.as_str())
+                    || settings.minutes_remaining != account.minutes_remaining
+                {
+                    settings.user_id = Some(account.user_id.clone());
+                    settings.minutes_remaining = account.minutes_remaining;
+                    write_settings(&self.app_handle, settings);
+                }
+                debug!(
+                    "Account {} has {:?} minutes remaining",
+                    account.user_id, account.minutes_remaining
+                );
+                *self.account.lock().unwrap() = Some(account);
+                self.emit_status();
+            }
+            Err(e) if e.kind == ErrorKind::Auth && !auth::is_signed_in() => {
+                warn!("Account session ended: {}", e);
+                self.logout();
+            }
+            Err(e) => warn!("Failed to sync account: {}", e),
+        }
+    }
+}
@@ -1,5 +1,8 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
+use crate::audio_toolkit::{
+    list_input_devices, vad::SmoothedVad, AudioRecorder, FrameSink, SileroVad,
+};
 use crate::helpers::clamshell;
+use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, AppSettings};
 use crate::utils;
 use log::{debug, error, info};
@@ -132,6 +135,15 @@ fn create_audio_recorder(
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        .with_speech_callback({
+            // Start loading the model while the user is still reaching for the shortcut
+            let app_handle = app_handle.clone();
+            move || {
+                if let Some(tm) = app_handle.try_state::<Arc<TranscriptionManager>>() {
+                    tm.preload_model("speech detected");
+                }
+            }
         });
 
     Ok(recorder)
@@ -139,6 +151,14 @@ fn create_audio_recorder(
 
 /* ──────────────────────────────────────────────────────────────── */
 
+/// The audio of a stopped recording.
+pub struct RecordedAudio {
+    pub samples: Vec<f32>,
+    /// Fraction of the recording that the VAD kept as speech, measured
+    /// before short clips are padded with silence
+    pub speech_ratio: Option<f32>,
+}
+
 #[derive(Clone)]
 pub struct AudioRecordingManager {
     state: Arc<Mutex<RecordingState>>,
@@ -278,6 +298,7 @@ impl AudioRecordingManager {
         if let Some(rec) = recorder_opt.as_mut() {
             rec.open(selected_device)
                 .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
+            let _ = rec.watch_speech(self.should_watch_speech(&settings));
         }
 
         *open_flag = true;
@@ -334,12 +355,35 @@ impl AudioRecordingManager {
         }
 
         *self.mode.lock().unwrap() = new_mode;
+        self.apply_speech_preload();
         Ok(())
     }
 
+    /// Speech only preloads the model in always-on mode, where the stream
+    /// is open between recordings.
+    fn should_watch_speech(&self, settings: &AppSettings) -> bool {
+        settings.preload_model_on_speech
+            && matches!(*self.mode.lock().unwrap(), MicrophoneMode::AlwaysOn)
+    }
+
+    /// Re-applies the preload-on-speech setting to the open stream.
+    pub fn apply_speech_preload(&self) {
+        let settings = get_settings(&self.app_handle);
+        let enabled = self.should_watch_speech(&settings);
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            let _ = rec.watch_speech(enabled);
+        }
+    }
+
     /* ---------- recording --------------------------------------------------- */
 
     pub fn try_start_recording(&self, binding_id: &str) -> bool {
+        self.try_start_recording_with_sink(binding_id, None)
+    }
+
+    /// Like `try_start_recording`, also handing each captured frame to
+    /// `sink` until the recording stops or is cancelled.
+    pub fn try_start_recording_with_sink(&self, binding_id: &str, sink: Option<FrameSink>) -> bool {
         let mut state = self.state.lock().unwrap();
 
         if let RecordingState::Idle = *state {
@@ -352,7 +396,7 @@ impl AudioRecordingManager {
             }
 
             if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
-                if rec.start().is_ok() {
+                if rec.start_with_sink(sink).is_ok() {
                     *self.is_recording.lock().unwrap() = true;
                     *state = RecordingState::Recording {
                         binding_id: binding_id.to_string(),
@@ -377,7 +421,7 @@ impl AudioRecordingManager {
         Ok(())
     }
 
-    pub fn stop_recording(&self, binding_id: &str) -> Option<Vec<f32>> {
+    pub fn stop_recording(&self, binding_id: &str) -> Option<RecordedAudio> {
         let mut state = self.state.lock().unwrap();
 
         match *state {
@@ -387,18 +431,19 @@ impl AudioRecordingManager {
                 *state = RecordingState::Idle;
                 drop(state);
 
-                let samples = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
-                    match rec.stop() {
-                        Ok(buf) => buf,
+                let (samples, stats) = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+                    match rec.stop_with_stats() {
+                        Ok((buf, stats)) => (buf, Some(stats)),
                         Err(e) => {
                             error!("stop() failed: {e}");
-                            Vec::new()
+                            (Vec::new(), None)
                         }
                     }
                 } else {
                     error!("Recorder not available");
-                    Vec::new()
+                    (Vec::new(), None)
                 };
+                let speech_ratio = stats.and_then(|stats| stats.speech_ratio());
 
                 *self.is_recording.lock().unwrap() = false;
 
@@ -410,18 +455,26 @@ impl AudioRecordingManager {
                 // Pad if very short
                 let s_len = samples.len();
                 // debug!("Got {} samples", s_len);
-                if s_len < WHISPER_SAMPLE_RATE && s_len > 0 {
+                let samples = if s_len < WHISPER_SAMPLE_RATE && s_len > 0 {
                     let mut padded = samples;
                     padded.resize(WHISPER_SAMPLE_RATE * 5 / 4, 0.0);
-                    Some(padded)
+                    padded
                 } else {
-                    Some(samples)
-                }
+                    samples
+                };
+                Some(RecordedAudio {
+                    samples,
+                    speech_ratio,
+                })
             }
             _ => None,
         }
     }
 
+    pub fn is_recording(&self) -> bool {
+        *self.is_recording.lock().unwrap()
+    }
+
     /// Cancel any ongoing recording without returning audio samples
     pub fn cancel_recording(&self) {
         let mut state = self.state.lock().unwrap();
@@ -0,0 +1,637 @@
+use crate::cancellation::CancellationToken;
+use crate::managers::model::ModelManager;
+use crate::settings::get_settings;
+use anyhow::Result;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+const DOWNLOAD_QUEUE_FILE: &str = "download_queue.json";
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    Queued,
+    Active,
+    Paused,
+}
+
+/// Returned by a download that stopped because it was paused or cancelled.
+/// The partial file is kept so the download can resume later.
+#[derive(Debug)]
+pub struct DownloadInterrupted;
+
+impl fmt::Display for DownloadInterrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Download interrupted")
+    }
+}
+
+impl std::error::Error for DownloadInterrupted {}
+
+/// Paces byte consumption to a target rate. Shared limiters split the rate
+/// between every download drawing from them.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    bytes_per_sec: AtomicU64,
+    next_free: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec: AtomicU64::new(bytes_per_sec),
+            next_free: Mutex::new(None),
+        }
+    }
+
+    /// 0 removes the limit.
+    pub fn set_limit(&self, bytes_per_sec: u64) {
+        self.bytes_per_sec.store(bytes_per_sec, Ordering::Relaxed);
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.bytes_per_sec.load(Ordering::Relaxed)
+    }
+
+    /// How long the caller has to wait before `bytes` fit within the limit.
+    fn reserve(&self, bytes: u64) -> Duration {
+        let limit = self.limit();
+        if limit == 0 {
+            return Duration::ZERO;
+        }
+
+        let now = Instant::now();
+        let mut next_free = self.next_free.lock().unwrap();
+        // Idle time doesn't build up credit, so a paused stream can't burst
+        let start = next_free.map_or(now, |next| next.max(now));
+        *next_free = Some(start + Duration::from_secs_f64(bytes as f64 / limit as f64));
+        start - now
+    }
+}
+
+/// Handle a running download checks between chunks.
+pub struct DownloadControl {
+    /// Fired by a pause or cancel; which one it was is up to the queue
+    pub interrupt: CancellationToken,
+    limiter: RateLimiter,
+    global_limiter: Arc<RateLimiter>,
+    downloaded: AtomicU64,
+    total: AtomicU64,
+}
+
+impl DownloadControl {
+    pub(crate) fn new(global_limiter: Arc<RateLimiter>, bytes_per_sec: u64) -> Self {
+        Self {
+            interrupt: CancellationToken::new(),
+            limiter: RateLimiter::new(bytes_per_sec),
+            global_limiter,
+            downloaded: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    pub fn set_progress(&self, downloaded: u64, total: u64) {
+        self.downloaded.store(downloaded, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    /// Waits until `bytes` more can be written without exceeding either limit.
+    pub async fn throttle(&self, bytes: u64) {
+        let wait = self
+            .limiter
+            .reserve(bytes)
+            .max(self.global_limiter.reserve(bytes));
+        if !wait.is_zero() {
+            // A low limit can mean long waits, which a pause shouldn't sit out
+            let _ = self
+                .interrupt
+                .run_until_cancelled(tokio::time::sleep(wait))
+                .await;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueuedDownload {
+    model_id: String,
+    priority: DownloadPriority,
+    state: DownloadState,
+    /// Per-download cap in KiB/s, 0 for none
+    #[serde(default)]
+    bandwidth_limit_kbps: u64,
+    /// Insertion order, used to keep FIFO order within a priority
+    #[serde(default)]
+    sequence: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadProgress {
+    pub model_id: String,
+    pub downloaded: u64,
+    pub total: u64,
+    pub percentage: f64,
+    pub state: DownloadState,
+    pub priority: DownloadPriority,
+    /// 1-based position among downloads waiting for a slot
+    pub queue_position: Option<usize>,
+    pub bytes_per_sec: u64,
+    pub eta_seconds: Option<u64>,
+}
+
+/// Payload of `model-download-progress`, covering every queued download.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadQueueProgress {
+    pub downloads: Vec<DownloadProgress>,
+    pub downloaded: u64,
+    pub total: u64,
+    pub bytes_per_sec: u64,
+    pub eta_seconds: Option<u64>,
+}
+
+#[derive(Default)]
+struct DownloadQueue {
+    entries: Vec<QueuedDownload>,
+    controls: HashMap<String, Arc<DownloadControl>>,
+    next_sequence: u64,
+}
+
+impl DownloadQueue {
+    fn find_mut(&mut self, model_id: &str) -> Option<&mut QueuedDownload> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.model_id == model_id)
+    }
+
+    /// Active downloads first, then waiting ones by priority and age.
+    fn sort(&mut self) {
+        self.entries.sort_by_key(|entry| {
+            (
+                entry.state != DownloadState::Active,
+                std::cmp::Reverse(entry.priority),
+                entry.sequence,
+            )
+        });
+    }
+}
+
+pub struct DownloadManager {
+    app_handle: AppHandle,
+    model_manager: Arc<ModelManager>,
+    queue_path: PathBuf,
+    queue: Mutex<DownloadQueue>,
+    global_limiter: Arc<RateLimiter>,
+}
+
+impl DownloadManager {
+    pub fn new(app_handle: &AppHandle, model_manager: Arc<ModelManager>) -> Result<Self> {
+        let app_data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?;
+
+        let settings = get_settings(app_handle);
+
+        Ok(Self {
+            app_handle: app_handle.clone(),
+            model_manager,
+            queue_path: app_data_dir.join(DOWNLOAD_QUEUE_FILE),
+            queue: Mutex::new(DownloadQueue::default()),
+            global_limiter: Arc::new(RateLimiter::new(
+                settings.download_bandwidth_limit_kbps * 1024,
+            )),
+        })
+    }
+
+    /// Restores downloads queued before the last shutdown and starts the
+    /// progress reporter.
+    pub fn start(self: &Arc<Self>) {
+        let restored: Vec<QueuedDownload> = fs::read_to_string(&self.queue_path)
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(entries) => Some(entries),
+                Err(e) => {
+                    warn!("Ignoring unreadable download queue: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        {
+            let mut queue = self.queue.lock().unwrap();
+            for mut entry in restored {
+                match self.model_manager.get_model_info(&entry.model_id) {
+                    Some(model) if !model.is_downloaded => {}
+                    _ => continue,
+                }
+                info!("Restoring queued download of {}", entry.model_id);
+                if entry.state == DownloadState::Active {
+                    entry.state = DownloadState::Queued;
+                }
+                entry.sequence = queue.next_sequence;
+                queue.next_sequence += 1;
+                queue.entries.push(entry);
+            }
+            queue.sort();
+        }
+        self.persist();
+        self.schedule();
+
+        let manager = self.clone();
+        tauri::async_runtime::spawn(async move {
+            manager.report_progress().await;
+        });
+    }
+
+    /// Adds a model to the queue. Re-queuing a model that is already waiting
+    /// only updates its priority.
+    pub fn enqueue(
+        self: &Arc<Self>,
+        model_id: &str,
+        priority: DownloadPriority,
+        bandwidth_limit_kbps: Option<u64>,
+    ) -> Result<()> {
+        let model = self
+            .model_manager
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        if model.is_downloaded {
+            return Ok(());
+        }
+
+        {
+            let mut queue = self.queue.lock().unwrap();
+            let sequence = queue.next_sequence;
+            match queue.find_mut(model_id) {
+                Some(entry) => {
+                    entry.priority = priority;
+                    if let Some(limit) = bandwidth_limit_kbps {
+                        entry.bandwidth_limit_kbps = limit;
+                    }
+                    if entry.state == DownloadState::Paused {
+                        entry.state = DownloadState::Queued;
+                    }
+                }
+                None => {
+                    queue.entries.push(QueuedDownload {
+                        model_id: model_id.to_string(),
+                        priority,
+                        state: DownloadState::Queued,
+                        bandwidth_limit_kbps: bandwidth_limit_kbps.unwrap_or(0),
+                        sequence,
+                    });
+                    queue.next_sequence += 1;
+                }
+            }
+            if let (Some(control), Some(limit)) =
+                (queue.controls.get(model_id), bandwidth_limit_kbps)
+            {
+                control.limiter.set_limit(limit * 1024);
+            }
+            queue.sort();
+        }
+
+        info!("Queued download of {} ({:?})", model_id, priority);
+        self.persist();
+        self.schedule();
+        Ok(())
+    }
+
+    /// Stops a download without discarding what was fetched so far. It
+    /// keeps its place in the queue but won't start until resumed.
+    pub fn pause(self: &Arc<Self>, model_id: &str) -> Result<()> {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            let entry = queue
+                .find_mut(model_id)
+                .ok_or_else(|| anyhow::anyhow!("Model is not queued: {}", model_id))?;
+            entry.state = DownloadState::Paused;
+            if let Some(control) = queue.controls.get(model_id) {
+                control.interrupt.cancel();
+            }
+            queue.sort();
+        }
+
+        info!("Paused download of {}", model_id);
+        self.persist();
+        self.schedule();
+        Ok(())
+    }
+
+    pub fn resume(self: &Arc<Self>, model_id: &str) -> Result<()> {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            let entry = queue
+                .find_mut(model_id)
+                .ok_or_else(|| anyhow::anyhow!("Model is not queued: {}", model_id))?;
+            if entry.state == DownloadState::Paused {
+                entry.state = DownloadState::Queued;
+            }
+            queue.sort();
+        }
+
+        info!("Resumed download of {}", model_id);
+        self.persist();
+        self.schedule();
+        Ok(())
+    }
+
+    /// Removes a download from the queue. The partial file stays on disk.
+    pub fn cancel(self: &Arc<Self>, model_id: &str) -> Result<()> {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            queue.entries.retain(|entry| entry.model_id != model_id);
+            if let Some(control) = queue.controls.get(model_id) {
+                control.interrupt.cancel();
+            }
+        }
+
+        self.model_manager.cancel_download(model_id)?;
+        self.persist();
+        self.schedule();
+        Ok(())
+    }
+
+    pub fn set_priority(
+        self: &Arc<Self>,
+        model_id: &str,
+        priority: DownloadPriority,
+    ) -> Result<()> {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            let entry = queue
+                .find_mut(model_id)
+                .ok_or_else(|| anyhow::anyhow!("Model is not queued: {}", model_id))?;
+            entry.priority = priority;
+            queue.sort();
+        }
+
+        self.persist();
+        self.schedule();
+        Ok(())
+    }
+
+    /// Caps a single download in KiB/s, 0 for no cap.
+    pub fn set_bandwidth_limit(&self, model_id: &str, kbps: u64) -> Result<()> {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            let entry = queue
+                .find_mut(model_id)
+                .ok_or_else(|| anyhow::anyhow!("Model is not queued: {}", model_id))?;
+            entry.bandwidth_limit_kbps = kbps;
+            if let Some(control) = queue.controls.get(model_id) {
+                control.limiter.set_limit(kbps * 1024);
+            }
+        }
+
+        self.persist();
+        Ok(())
+    }
+
+    /// Re-reads the concurrency and global bandwidth settings.
+    pub fn apply_settings(self: &Arc<Self>) {
+        let settings = get_settings(&self.app_handle);
+        self.global_limiter
+            .set_limit(settings.download_bandwidth_limit_kbps * 1024);
+        self.schedule();
+    }
+
+    pub fn get_queue(&self) -> DownloadQueueProgress {
+        self.snapshot(&HashMap::new())
+    }
+
+    /// Starts queued downloads until the concurrency limit is reached.
+    fn schedule(self: &Arc<Self>) {
+        let max_concurrent = get_settings(&self.app_handle)
+            .max_concurrent_downloads
+            .max(1);
+
+        let to_start: Vec<(String, Arc<DownloadControl>)> = {
+            let mut queue = self.queue.lock().unwrap();
+            let mut active = queue
+                .entries
+                .iter()
+                .filter(|entry| entry.state == DownloadState::Active)
+                .count();
+
+            let mut started = Vec::new();
+            let global_limiter = self.global_limiter.clone();
+            let DownloadQueue {
+                entries, controls, ..
+            } = &mut *queue;
+            for entry in entries.iter_mut() {
+                if active >= max_concurrent {
+                    break;
+                }
+                // A paused download may still be winding down
+                if entry.state != DownloadState::Queued || controls.contains_key(&entry.model_id) {
+                    continue;
+                }
+                entry.state = DownloadState::Active;
+                let control = Arc::new(DownloadControl::new(
+                    global_limiter.clone(),
+                    entry.bandwidth_limit_kbps * 1024,
+                ));
+                controls.insert(entry.model_id.clone(), control.clone());
+                started.push((entry.model_id.clone(), control));
+                active += 1;
+            }
+            queue.sort();
+            started
+        };
+
+        for (model_id, control) in to_start {
+            debug!("Starting queued download of {}", model_id);
+            let manager = self.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = manager
+                    .model_manager
+                    .download_model(&model_id, &control)
+                    .await;
+                manager.finish(&model_id, result);
+            });
+        }
+    }
+
+    fn finish(self: &Arc<Self>, model_id: &str, result: Result<()>) {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            queue.controls.remove(model_id);
+            match &result {
+                Err(e) if e.is::<DownloadInterrupted>() => {
+                    // Paused downloads stay queued; cancelled ones are already gone
+                    if let Some(entry) = queue.find_mut(model_id) {
+                        if entry.state == DownloadState::Active {
+                            entry.state = DownloadState::Queued;
+                        }
+                    }
+                    debug!("Download of {} stopped", model_id);
+                }
+                Err(e) => {
+                    warn!("Download of {} failed: {}", model_id, e);
+                    queue.entries.retain(|entry| entry.model_id != model_id);
+                }
+                Ok(()) => {
+                    queue.entries.retain(|entry| entry.model_id != model_id);
+                }
+            }
+            queue.sort();
+        }
+
+        self.persist();
+        self.schedule();
+    }
+
+    fn persist(&self) {
+        let entries = self.queue.lock().unwrap().entries.clone();
+        let result = serde_json::to_string_pretty(&entries)
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| fs::write(&self.queue_path, contents).map_err(Into::into));
+        if let Err(e) = result {
+            warn!("Failed to save download queue: {}", e);
+        }
+    }
+
+    async fn report_progress(self: Arc<Self>) {
+        // model id -> (sampled at, bytes downloaded, smoothed bytes/sec)
+        let mut samples: HashMap<String, (Instant, u64, f64)> = HashMap::new();
+        let mut was_empty = true;
+        let mut interval = tokio::time::interval(PROGRESS_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let now = Instant::now();
+            let mut speeds = HashMap::new();
+            {
+                let queue = self.queue.lock().unwrap();
+                samples.retain(|model_id, _| queue.controls.contains_key(model_id));
+                for (model_id, control) in &queue.controls {
+                    let downloaded = control.downloaded.load(Ordering::Relaxed);
+                    let sample = samples
+                        .entry(model_id.clone())
+                        .or_insert((now, downloaded, 0.0));
+                    let elapsed = now.duration_since(sample.0).as_secs_f64();
+                    if elapsed > 0.0 {
+                        let instant = downloaded.saturating_sub(sample.1) as f64 / elapsed;
+                        sample.2 = if sample.2 == 0.0 {
+                            instant
+                        } else {
+                            sample.2 * 0.7 + instant * 0.3
+                        };
+                        sample.0 = now;
+                        sample.1 = downloaded;
+                    }
+                    speeds.insert(model_id.clone(), sample.2);
+                }
+            }
+
+            let progress = self.snapshot(&speeds);
+            let is_empty = progress.downloads.is_empty();
+            // One last event after the queue drains so listeners can clear their state
+            if !is_empty || !was_empty {
+                let _ = self.app_handle.emit("model-download-progress", &progress);
+            }
+            was_empty = is_empty;
+        }
+    }
+
+    fn snapshot(&self, speeds: &HashMap<String, f64>) -> DownloadQueueProgress {
+        let queue = self.queue.lock().unwrap();
+        let total_speed: f64 = speeds.values().sum();
+
+        let mut downloads = Vec::with_capacity(queue.entries.len());
+        let mut queue_position = 0;
+        // Bytes still to fetch for everything ahead in the queue, for queued ETAs
+        let mut remaining_ahead = 0u64;
+
+        for entry in &queue.entries {
+            let control = queue.controls.get(&entry.model_id);
+            let (mut downloaded, mut total) = control
+                .map(|c| {
+                    (
+                        c.downloaded.load(Ordering::Relaxed),
+                        c.total.load(Ordering::Relaxed),
+                    )
+                })
+                .unwrap_or((0, 0));
+
+            if control.is_none() || total == 0 {
+                if let Some(model) = self.model_manager.get_model_info(&entry.model_id) {
+                    downloaded = downloaded.max(model.partial_size);
+                    total = model
+                        .size_bytes
+                        .unwrap_or(model.size_mb * 1024 * 1024)
+                        .max(downloaded);
+                }
+            }
+
+            let remaining = total.saturating_sub(downloaded);
+            let speed = speeds.get(&entry.model_id).copied().unwrap_or(0.0);
+            let position = match entry.state {
+                DownloadState::Queued => {
+                    queue_position += 1;
+                    Some(queue_position)
+                }
+                _ => None,
+            };
+            let eta_seconds = match entry.state {
+                DownloadState::Active if speed > 0.0 => Some((remaining as f64 / speed) as u64),
+                DownloadState::Queued if total_speed > 0.0 => {
+                    Some(((remaining_ahead + remaining) as f64 / total_speed) as u64)
+                }
+                _ => None,
+            };
+            if entry.state != DownloadState::Paused {
+                remaining_ahead += remaining;
+            }
+
+            downloads.push(DownloadProgress {
+                model_id: entry.model_id.clone(),
+                downloaded,
+                total,
+                percentage: if total > 0 {
+                    (downloaded as f64 / total as f64) * 100.0
+                } else {
+                    0.0
+                },
+                state: entry.state,
+                priority: entry.priority,
+                queue_position: position,
+                bytes_per_sec: speed as u64,
+                eta_seconds,
+            });
+        }
+
+        let downloaded = downloads.iter().map(|d| d.downloaded).sum();
+        let total = downloads.iter().map(|d| d.total).sum();
+        DownloadQueueProgress {
+            downloads,
+            downloaded,
+            total,
+            bytes_per_sec: total_speed as u64,
+            eta_seconds: if total_speed > 0.0 {
+                Some((remaining_ahead as f64 / total_speed) as u64)
+            } else {
+                None
+            },
+        }
+    }
+}
@@ -8,7 +8,9 @@ use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_sql::{Migration, MigrationKind};
 
+use crate::audio_toolkit::diarization::{render_turns, speaker_count, SpeakerSegment};
 use crate::audio_toolkit::save_wav_file;
+use crate::post_process::StepOutput;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HistoryEntry {
@@ -20,6 +22,80 @@ pub struct HistoryEntry {
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
+    /// Backend that produced the transcription, e.g. `deepgram:nova-3`
+    pub backend: Option<String>,
+    /// Speaker turns when the recording was diarized
+    pub speaker_segments: Option<Vec<SpeakerSegment>>,
+    /// The transcription translated into `translation_language`. Empty
+    /// when the engine translated but couldn't also return the spoken text,
+    /// which then stands in as the transcription.
+    pub translation: Option<String>,
+    pub translation_language: Option<String>,
+    /// The text after each post-processing step that ran, in order
+    pub pipeline_steps: Option<Vec<StepOutput>>,
+    /// For edit-selection commands, the selected text before the edit.
+    /// The spoken instruction is the transcription and the result the
+    /// post-processed text.
+    pub edited_selection: Option<String>,
+}
+
+/// What one recording produced, as saved to history.
+#[derive(Debug, Default)]
+pub struct TranscriptionRecord {
+    /// The text in the spoken language
+    pub transcription_text: String,
+    pub post_processed_text: Option<String>,
+    pub post_process_prompt: Option<String>,
+    pub backend: Option<String>,
+    pub speaker_segments: Vec<SpeakerSegment>,
+    pub translation: Option<String>,
+    pub translation_language: Option<String>,
+    pub pipeline_steps: Vec<StepOutput>,
+    pub edited_selection: Option<String>,
+}
+
+impl HistoryEntry {
+    /// The text to export: the post-processed text if there is one, else
+    /// the translation, else the transcription, as speaker turns when
+    /// `speaker_labels` is set and more than one speaker was found.
+    pub fn export_text(&self, speaker_labels: bool) -> String {
+        if let Some(text) = self
+            .post_processed_text
+            .as_ref()
+            .or(self.translation.as_ref())
+        {
+            return text.clone();
+        }
+        match &self.speaker_segments {
+            Some(segments) if speaker_labels && speaker_count(segments) > 1 => {
+                render_turns(segments)
+            }
+            _ => self.transcription_text.clone(),
+        }
+    }
+}
+
+const ENTRY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, backend, speaker_segments, translation, translation_language, pipeline_steps, edited_selection";
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+    let speaker_segments: Option<String> = row.get("speaker_segments")?;
+    let pipeline_steps: Option<String> = row.get("pipeline_steps")?;
+    Ok(HistoryEntry {
+        id: row.get("id")?,
+        file_name: row.get("file_name")?,
+        timestamp: row.get("timestamp")?,
+        saved: row.get("saved")?,
+        title: row.get("title")?,
+        transcription_text: row.get("transcription_text")?,
+        post_processed_text: row.get("post_processed_text")?,
+        post_process_prompt: row.get("post_process_prompt")?,
+        backend: row.get("backend")?,
+        speaker_segments: speaker_segments.and_then(|json| serde_json::from_str(&json).ok()),
+        translation: row.get("translation")?,
+        translation_language: row.get("translation_language")?,
+        pipeline_steps: pipeline_steps.and_then(|json| serde_json::from_str(&json).ok()),
+        edited_selection: row.get("edited_selection")?,
+    })
 }
 
 pub struct HistoryManager {
@@ -80,6 +156,54 @@ impl HistoryManager {
                 sql: "ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;",
                 kind: MigrationKind::Up,
             },
+            Migration {
+                version: 4,
+                description: "add_backend_column",
+                sql: "ALTER TABLE transcription_history ADD COLUMN backend TEXT;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 5,
+                description: "create_usage_events_table",
+                sql: "CREATE TABLE IF NOT EXISTS usage_events (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    timestamp INTEGER NOT NULL,
+                    kind TEXT NOT NULL,
+                    provider TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    audio_seconds REAL NOT NULL DEFAULT 0,
+                    prompt_tokens INTEGER NOT NULL DEFAULT 0,
+                    completion_tokens INTEGER NOT NULL DEFAULT 0,
+                    direct INTEGER NOT NULL DEFAULT 1
+                );
+                CREATE INDEX IF NOT EXISTS idx_usage_events_timestamp ON usage_events (timestamp);",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 6,
+                description: "add_speaker_segments_column",
+                sql: "ALTER TABLE transcription_history ADD COLUMN speaker_segments TEXT;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 7,
+                description: "add_translation_columns",
+                sql: "ALTER TABLE transcription_history ADD COLUMN translation TEXT;
+                ALTER TABLE transcription_history ADD COLUMN translation_language TEXT;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 8,
+                description: "add_pipeline_steps_column",
+                sql: "ALTER TABLE transcription_history ADD COLUMN pipeline_steps TEXT;",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 9,
+                description: "add_edited_selection_column",
+                sql: "ALTER TABLE transcription_history ADD COLUMN edited_selection TEXT;",
+                kind: MigrationKind::Up,
+            },
         ]
     }
 
@@ -98,9 +222,7 @@ impl HistoryManager {
     pub async fn save_transcription(
         &self,
         audio_samples: Vec<f32>,
-        transcription_text: String,
-        post_processed_text: Option<String>,
-        post_process_prompt: Option<String>,
+        record: TranscriptionRecord,
     ) -> Result<()> {
         let timestamp = Utc::now().timestamp();
         let file_name = format!("handy-{}.wav", timestamp);
@@ -111,14 +233,7 @@ impl HistoryManager {
         save_wav_file(file_path, &audio_samples).await?;
 
         // Save to database
-        self.save_to_database(
-            file_name,
-            timestamp,
-            title,
-            transcription_text,
-            post_processed_text,
-            post_process_prompt,
-        )?;
+        self.save_to_database(file_name, timestamp, title, record)?;
 
         // Clean up old entries
         self.cleanup_old_entries()?;
@@ -136,14 +251,36 @@ impl HistoryManager {
         file_name: String,
         timestamp: i64,
         title: String,
-        transcription_text: String,
-        post_processed_text: Option<String>,
-        post_process_prompt: Option<String>,
+        record: TranscriptionRecord,
     ) -> Result<()> {
+        let speaker_segments = if record.speaker_segments.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&record.speaker_segments)?)
+        };
+        let pipeline_steps = if record.pipeline_steps.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&record.pipeline_steps)?)
+        };
         let conn = self.get_connection()?;
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, backend, speaker_segments, translation, translation_language, pipeline_steps, edited_selection) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                file_name,
+                timestamp,
+                false,
+                title,
+                record.transcription_text,
+                record.post_processed_text,
+                record.post_process_prompt,
+                record.backend,
+                speaker_segments,
+                record.translation,
+                record.translation_language,
+                pipeline_steps,
+                record.edited_selection,
+            ],
         )?;
 
         debug!("Saved transcription to database");
@@ -272,22 +409,12 @@ impl HistoryManager {
 
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history ORDER BY timestamp DESC",
+            ENTRY_COLUMNS
+        ))?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-            })
-        })?;
+        let rows = stmt.query_map([], entry_from_row)?;
 
         let mut entries = Vec::new();
         for row in rows {
@@ -330,25 +457,12 @@ impl HistoryManager {
 
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
-             FROM transcription_history WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE id = ?1",
+            ENTRY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([id], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([id], entry_from_row).optional()?;
 
         Ok(entry)
     }
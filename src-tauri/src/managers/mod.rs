@@ -1,4 +1,8 @@
+pub mod account;
 pub mod audio;
+pub mod download;
 pub mod history;
 pub mod model;
 pub mod transcription;
+pub mod transcription_queue;
+pub mod usage;
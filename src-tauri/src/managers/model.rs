@@ -1,19 +1,21 @@
+use crate::managers::download::{DownloadControl, DownloadInterrupted};
 use crate::settings::{get_settings, write_settings};
 use anyhow::Result;
 use flate2::read::GzDecoder;
 use futures_util::StreamExt;
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use tar::Archive;
 use tauri::{AppHandle, Emitter, Manager};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EngineType {
     Whisper,
     Parakeet,
@@ -34,158 +36,230 @@ pub struct ModelInfo {
     pub engine_type: EngineType,
     pub accuracy_score: f32, // 0.0 to 1.0, higher is more accurate
     pub speed_score: f32,    // 0.0 to 1.0, higher is faster
+    pub is_custom: bool,
+    pub local_path: Option<String>,
+    pub sha256: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub mirrors: Vec<String>,
+    pub benchmark: Option<ModelBenchmark>,
 }
 
+/// Measurements from running a model over the reference clips on this machine.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DownloadProgress {
+pub struct ModelBenchmark {
+    pub load_time_ms: u64,
+    /// Processing time divided by audio duration, lower is faster
+    pub real_time_factor: f32,
+    /// Memory added while the model was loaded and running, where measurable
+    pub peak_memory_mb: Option<f32>,
+    pub word_error_rate: f32,
+    pub character_error_rate: f32,
+    pub clip_count: usize,
+    pub audio_seconds: f32,
+    pub benchmarked_at: i64,
+}
+
+impl ModelBenchmark {
+    fn accuracy_score(&self) -> f32 {
+        (1.0 - self.word_error_rate).clamp(0.0, 1.0)
+    }
+
+    /// Maps the real-time factor onto 0..1: 0.1 scores 0.5, 0.02 about 0.83.
+    fn speed_score(&self) -> f32 {
+        (1.0 / (1.0 + 10.0 * self.real_time_factor.max(0.0))).clamp(0.0, 1.0)
+    }
+}
+
+impl ModelInfo {
+    fn apply_benchmark(&mut self, benchmark: ModelBenchmark) {
+        self.accuracy_score = benchmark.accuracy_score();
+        self.speed_score = benchmark.speed_score();
+        self.benchmark = Some(benchmark);
+    }
+
+    /// The primary download URL followed by any mirrors, in failover order.
+    fn download_urls(&self) -> Vec<String> {
+        self.url
+            .iter()
+            .chain(self.mirrors.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+/// One model entry as written in a manifest file.
+/// Entries with a `path` point at a model that already lives on disk
+/// (a GGML file for Whisper, a directory for Parakeet) and are never downloaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelManifestEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub filename: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub size_mb: u64,
+    #[serde(default)]
+    pub is_directory: bool,
+    pub engine_type: EngineType,
+    #[serde(default = "default_manifest_score")]
+    pub accuracy_score: f32,
+    #[serde(default = "default_manifest_score")]
+    pub speed_score: f32,
+    /// Lowercase hex SHA-256 of the downloaded artifact (the archive for directory models)
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Exact size in bytes of the downloaded artifact
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    /// Alternative download URLs tried in order when `url` fails
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    /// Files match the checksum and size recorded for the model
+    Verified,
+    /// The model is present but there is nothing to check it against
+    Unverified,
+    /// The download was interrupted or files are missing
+    Incomplete,
+    /// Files don't match the recorded checksum or size
+    Corrupted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVerification {
     pub model_id: String,
-    pub downloaded: u64,
-    pub total: u64,
-    pub percentage: f64,
+    pub status: IntegrityStatus,
+    pub detail: Option<String>,
+    /// Whether the model can be repaired by downloading it again
+    pub can_redownload: bool,
+}
+
+/// Checksums of the files extracted from a directory model's archive,
+/// written next to the model so it can be verified after the archive is gone.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IntegrityRecord {
+    files: BTreeMap<String, IntegrityRecordEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IntegrityRecordEntry {
+    size: u64,
+    sha256: String,
+}
+
+const INTEGRITY_RECORD_SUFFIX: &str = ".integrity.json";
+
+fn default_manifest_score() -> f32 {
+    0.5
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ModelManifest {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    models: Vec<ModelManifestEntry>,
+}
+
+const BUNDLED_MODEL_MANIFEST: &str = include_str!("../../resources/models.json");
+const USER_MODEL_MANIFEST_FILE: &str = "models.json";
+const BENCHMARK_RESULTS_FILE: &str = "benchmark_results.json";
+
+impl ModelManifestEntry {
+    fn into_model_info(self, is_custom: bool) -> ModelInfo {
+        ModelInfo {
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            filename: self.filename,
+            url: self.url,
+            size_mb: self.size_mb,
+            is_downloaded: false,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: self.is_directory,
+            engine_type: self.engine_type,
+            accuracy_score: self.accuracy_score.clamp(0.0, 1.0),
+            speed_score: self.speed_score.clamp(0.0, 1.0),
+            is_custom,
+            local_path: self.path,
+            sha256: self.sha256.map(|hash| hash.trim().to_lowercase()),
+            size_bytes: self.size_bytes,
+            mirrors: self.mirrors,
+            benchmark: None,
+        }
+    }
 }
 
 pub struct ModelManager {
     app_handle: AppHandle,
     models_dir: PathBuf,
+    user_manifest_path: PathBuf,
+    benchmark_results_path: PathBuf,
     available_models: Mutex<HashMap<String, ModelInfo>>,
+    active_downloads: Mutex<HashSet<String>>,
 }
 
 impl ModelManager {
     pub fn new(app_handle: &AppHandle) -> Result<Self> {
-        // Create models directory in app data
-        let models_dir = app_handle
+        let app_data_dir = app_handle
             .path()
             .app_data_dir()
-            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?
-            .join("models");
+            .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?;
+
+        // Create models directory in app data
+        let models_dir = app_data_dir.join("models");
 
         if !models_dir.exists() {
             fs::create_dir_all(&models_dir)?;
         }
 
-        let mut available_models = HashMap::new();
+        let user_manifest_path = app_data_dir.join(USER_MODEL_MANIFEST_FILE);
 
-        // TODO this should be read from a JSON file or something..
-        available_models.insert(
-            "small".to_string(),
-            ModelInfo {
-                id: "small".to_string(),
-                name: "Whisper Small".to_string(),
-                description: "Fast and fairly accurate.".to_string(),
-                filename: "ggml-small.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-small.bin".to_string()),
-                size_mb: 487,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.60,
-                speed_score: 0.85,
-            },
-        );
-
-        // Add downloadable models
-        available_models.insert(
-            "medium".to_string(),
-            ModelInfo {
-                id: "medium".to_string(),
-                name: "Whisper Medium".to_string(),
-                description: "Good accuracy, medium speed".to_string(),
-                filename: "whisper-medium-q4_1.bin".to_string(),
-                url: Some("https://blob.handy.computer/whisper-medium-q4_1.bin".to_string()),
-                size_mb: 492, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.75,
-                speed_score: 0.60,
-            },
-        );
-
-        available_models.insert(
-            "turbo".to_string(),
-            ModelInfo {
-                id: "turbo".to_string(),
-                name: "Whisper Turbo".to_string(),
-                description: "Balanced accuracy and speed.".to_string(),
-                filename: "ggml-large-v3-turbo.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-turbo.bin".to_string()),
-                size_mb: 1600, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.80,
-                speed_score: 0.40,
-            },
-        );
+        // The bundled manifest provides the stock catalog; the user manifest
+        // can override those entries by id or add entirely new ones.
+        let mut available_models = HashMap::new();
 
-        available_models.insert(
-            "large".to_string(),
-            ModelInfo {
-                id: "large".to_string(),
-                name: "Whisper Large".to_string(),
-                description: "Good accuracy, but slow.".to_string(),
-                filename: "ggml-large-v3-q5_0.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-q5_0.bin".to_string()),
-                size_mb: 1100, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.85,
-                speed_score: 0.30,
-            },
-        );
+        let bundled: ModelManifest = serde_json::from_str(BUNDLED_MODEL_MANIFEST)
+            .map_err(|e| anyhow::anyhow!("Failed to parse bundled model manifest: {}", e))?;
+        for entry in bundled.models {
+            available_models.insert(entry.id.clone(), entry.into_model_info(false));
+        }
 
-        // Add NVIDIA Parakeet models (directory-based)
-        available_models.insert(
-            "parakeet-tdt-0.6b-v2".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v2".to_string(),
-                name: "Parakeet V2".to_string(),
-                description: "English only. The best model for English speakers.".to_string(),
-                filename: "parakeet-tdt-0.6b-v2-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v2-int8.tar.gz".to_string()),
-                size_mb: 473, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.85,
-                speed_score: 0.85,
-            },
-        );
+        for entry in Self::read_user_manifest(&user_manifest_path).models {
+            let is_custom = entry.path.is_some() || !available_models.contains_key(&entry.id);
+            debug!(
+                "Loaded model '{}' from user manifest (custom: {})",
+                entry.id, is_custom
+            );
+            available_models.insert(entry.id.clone(), entry.into_model_info(is_custom));
+        }
 
-        available_models.insert(
-            "parakeet-tdt-0.6b-v3".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v3".to_string(),
-                name: "Parakeet V3".to_string(),
-                description: "Fast and accurate".to_string(),
-                filename: "parakeet-tdt-0.6b-v3-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v3-int8.tar.gz".to_string()),
-                size_mb: 478, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.80,
-                speed_score: 0.85,
-            },
-        );
+        // Scores measured on this machine replace the catalog's estimates
+        let benchmark_results_path = app_data_dir.join(BENCHMARK_RESULTS_FILE);
+        for (model_id, benchmark) in Self::read_benchmark_results(&benchmark_results_path) {
+            if let Some(model) = available_models.get_mut(&model_id) {
+                model.apply_benchmark(benchmark);
+            }
+        }
 
         let manager = Self {
             app_handle: app_handle.clone(),
             models_dir,
+            user_manifest_path,
+            benchmark_results_path,
             available_models: Mutex::new(available_models),
+            active_downloads: Mutex::new(HashSet::new()),
         };
 
         // Migrate any bundled models to user directory
@@ -200,6 +274,214 @@ impl ModelManager {
         Ok(manager)
     }
 
+    fn read_user_manifest(path: &Path) -> ModelManifest {
+        if !path.exists() {
+            return ModelManifest::default();
+        }
+
+        match fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| serde_json::from_str(&contents).map_err(anyhow::Error::from))
+        {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                warn!("Ignoring unreadable user model manifest {:?}: {}", path, e);
+                ModelManifest::default()
+            }
+        }
+    }
+
+    fn write_user_manifest(&self, manifest: &ModelManifest) -> Result<()> {
+        let contents = serde_json::to_string_pretty(manifest)?;
+        fs::write(&self.user_manifest_path, contents)?;
+        Ok(())
+    }
+
+    fn read_benchmark_results(path: &Path) -> HashMap<String, ModelBenchmark> {
+        if !path.exists() {
+            return HashMap::new();
+        }
+
+        match fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| serde_json::from_str(&contents).map_err(anyhow::Error::from))
+        {
+            Ok(results) => results,
+            Err(e) => {
+                warn!("Ignoring unreadable benchmark results {:?}: {}", path, e);
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Stores a benchmark result and updates the model's displayed scores from it.
+    pub fn record_benchmark(&self, model_id: &str, benchmark: ModelBenchmark) -> Result<()> {
+        let mut results = Self::read_benchmark_results(&self.benchmark_results_path);
+        results.insert(model_id.to_string(), benchmark.clone());
+        fs::write(
+            &self.benchmark_results_path,
+            serde_json::to_string_pretty(&results)?,
+        )?;
+
+        let mut models = self.available_models.lock().unwrap();
+        if let Some(model) = models.get_mut(model_id) {
+            model.apply_benchmark(benchmark);
+        }
+        Ok(())
+    }
+
+    /// The benchmarked model with the best balance of accuracy and speed,
+    /// ignoring any that can't keep up with real-time dictation.
+    pub fn recommended_model(&self) -> Option<String> {
+        let models = self.available_models.lock().unwrap();
+        models
+            .values()
+            .filter_map(|model| Some((model, model.benchmark.as_ref()?)))
+            .filter(|(_, benchmark)| benchmark.real_time_factor < 0.5)
+            .max_by(|(a, _), (b, _)| {
+                let score = |m: &ModelInfo| m.accuracy_score * 0.6 + m.speed_score * 0.4;
+                score(a).total_cmp(&score(b))
+            })
+            .map(|(model, _)| model.id.clone())
+    }
+
+    /// Where a model lives on disk: its registered path for custom local
+    /// models, or its filename inside the models directory otherwise.
+    fn model_location(&self, model: &ModelInfo) -> PathBuf {
+        match &model.local_path {
+            Some(path) => PathBuf::from(path),
+            None => self.models_dir.join(&model.filename),
+        }
+    }
+
+    /// Registers a model that already exists on disk so it can be selected
+    /// and loaded like any downloaded model. The entry is persisted to the
+    /// user manifest in the app data directory.
+    pub fn register_custom_model(
+        &self,
+        name: &str,
+        path: &Path,
+        engine_type: EngineType,
+        description: Option<String>,
+        accuracy_score: Option<f32>,
+        speed_score: Option<f32>,
+    ) -> Result<ModelInfo> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Model name cannot be empty"));
+        }
+
+        let is_directory = match engine_type {
+            EngineType::Whisper => {
+                if !path.is_file() {
+                    return Err(anyhow::anyhow!(
+                        "Whisper models must be a single GGML file: {:?}",
+                        path
+                    ));
+                }
+                false
+            }
+            EngineType::Parakeet => {
+                if !path.is_dir() {
+                    return Err(anyhow::anyhow!(
+                        "Parakeet models must be a directory: {:?}",
+                        path
+                    ));
+                }
+                true
+            }
+        };
+
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Model path is not valid UTF-8: {:?}", path))?
+            .to_string();
+
+        // Derive a stable id from the name, making sure it doesn't collide
+        let slug: String = name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>()
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        let base_id = format!("custom-{}", slug);
+        let id = {
+            let models = self.available_models.lock().unwrap();
+            let mut candidate = base_id.clone();
+            let mut suffix = 2;
+            while models.contains_key(&candidate) {
+                candidate = format!("{}-{}", base_id, suffix);
+                suffix += 1;
+            }
+            candidate
+        };
+
+        let entry = ModelManifestEntry {
+            id: id.clone(),
+            name: name.to_string(),
+            description: description.unwrap_or_else(|| "Custom local model".to_string()),
+            filename: path
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            url: None,
+            path: Some(path_str),
+            size_mb: path_size_bytes(path) / (1024 * 1024),
+            is_directory,
+            engine_type,
+            accuracy_score: accuracy_score.unwrap_or_else(default_manifest_score),
+            speed_score: speed_score.unwrap_or_else(default_manifest_score),
+            sha256: None,
+            size_bytes: None,
+            mirrors: Vec::new(),
+        };
+
+        let mut manifest = Self::read_user_manifest(&self.user_manifest_path);
+        manifest.version = manifest.version.max(1);
+        manifest.models.push(entry.clone());
+        self.write_user_manifest(&manifest)?;
+
+        {
+            let mut models = self.available_models.lock().unwrap();
+            models.insert(id.clone(), entry.into_model_info(true));
+        }
+        self.update_download_status()?;
+
+        info!("Registered custom model {} at {:?}", id, path);
+        self.get_model_info(&id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found after registration: {}", id))
+    }
+
+    /// Removes a custom model from the catalog. Files registered from disk
+    /// are left untouched since they belong to the user.
+    pub fn remove_custom_model(&self, model_id: &str) -> Result<()> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+
+        if !model_info.is_custom {
+            return Err(anyhow::anyhow!(
+                "Model {} is part of the built-in catalog",
+                model_id
+            ));
+        }
+
+        let mut manifest = Self::read_user_manifest(&self.user_manifest_path);
+        manifest.models.retain(|entry| entry.id != model_id);
+        self.write_user_manifest(&manifest)?;
+
+        {
+            let mut models = self.available_models.lock().unwrap();
+            models.remove(model_id);
+        }
+
+        info!("Removed custom model {}", model_id);
+        Ok(())
+    }
+
     pub fn get_available_models(&self) -> Vec<ModelInfo> {
         let models = self.available_models.lock().unwrap();
         models.values().cloned().collect()
@@ -241,6 +523,19 @@ impl ModelManager {
         let mut models = self.available_models.lock().unwrap();
 
         for model in models.values_mut() {
+            if let Some(local_path) = &model.local_path {
+                // Custom local models are "downloaded" as long as they are still on disk
+                let path = Path::new(local_path);
+                model.is_downloaded = if model.is_directory {
+                    path.is_dir()
+                } else {
+                    path.is_file()
+                };
+                model.is_downloading = false;
+                model.partial_size = 0;
+                continue;
+            }
+
             if model.is_directory {
                 // For directory-based models, check if the directory exists
                 let model_path = self.models_dir.join(&model.filename);
@@ -310,7 +605,9 @@ impl ModelManager {
         Ok(())
     }
 
-    pub async fn download_model(&self, model_id: &str) -> Result<()> {
+    /// Downloads a model, checking `control` between chunks for pause,
+    /// cancellation and bandwidth limits. Queueing is up to the caller.
+    pub async fn download_model(&self, model_id: &str, control: &DownloadControl) -> Result<()> {
         let model_info = {
             let models = self.available_models.lock().unwrap();
             models.get(model_id).cloned()
@@ -319,9 +616,10 @@ impl ModelManager {
         let model_info =
             model_info.ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
 
-        let url = model_info
-            .url
-            .ok_or_else(|| anyhow::anyhow!("No download URL for model"))?;
+        let urls = model_info.download_urls();
+        if urls.is_empty() {
+            return Err(anyhow::anyhow!("No download URL for model"));
+        }
         let model_path = self.models_dir.join(&model_info.filename);
         let partial_path = self
             .models_dir
@@ -337,15 +635,17 @@ impl ModelManager {
             return Ok(());
         }
 
-        // Check if we have a partial download to resume
-        let resume_from = if partial_path.exists() {
-            let size = partial_path.metadata()?.len();
-            info!("Resuming download of model {} from byte {}", model_id, size);
-            size
-        } else {
-            info!("Starting fresh download of model {} from {}", model_id, url);
-            0
-        };
+        if !self
+            .active_downloads
+            .lock()
+            .unwrap()
+            .insert(model_id.to_string())
+        {
+            return Err(anyhow::anyhow!(
+                "Model is already downloading: {}",
+                model_id
+            ));
+        }
 
         // Mark as downloading
         {
@@ -355,20 +655,12 @@ impl ModelManager {
             }
         }
 
-        // Create HTTP client with range request for resuming
-        let client = reqwest::Client::new();
-        let mut request = client.get(&url);
+        let result = self
+            .download_and_install(&model_info, &urls, &partial_path, control)
+            .await;
+        self.active_downloads.lock().unwrap().remove(model_id);
 
-        if resume_from > 0 {
-            request = request.header("Range", format!("bytes={}-", resume_from));
-        }
-
-        let response = request.send().await?;
-
-        // Check for success or partial content status
-        if !response.status().is_success()
-            && response.status() != reqwest::StatusCode::PARTIAL_CONTENT
-        {
+        if let Err(e) = &result {
             // Mark as not downloading on error
             {
                 let mut models = self.available_models.lock().unwrap();
@@ -376,82 +668,111 @@ impl ModelManager {
                     model.is_downloading = false;
                 }
             }
-            return Err(anyhow::anyhow!(
-                "Failed to download model: HTTP {}",
-                response.status()
-            ));
+            if e.is::<DownloadInterrupted>() {
+                let _ = self.update_download_status();
+                return result;
+            }
+            let _ = self.app_handle.emit(
+                "model-download-failed",
+                &serde_json::json!({
+                    "model_id": model_id,
+                    "error": e.to_string()
+                }),
+            );
+            return result;
         }
 
-        let total_size = if resume_from > 0 {
-            // For resumed downloads, add the resume point to content length
-            resume_from + response.content_length().unwrap_or(0)
-        } else {
-            response.content_length().unwrap_or(0)
-        };
+        // Update download status
+        {
+            let mut models = self.available_models.lock().unwrap();
+            if let Some(model) = models.get_mut(model_id) {
+                model.is_downloading = false;
+                model.is_downloaded = true;
+                model.partial_size = 0;
+            }
+        }
 
-        let mut downloaded = resume_from;
-        let mut stream = response.bytes_stream();
+        // Emit completion event
+        let _ = self.app_handle.emit("model-download-complete", model_id);
 
-        // Open file for appending if resuming, or create new if starting fresh
-        let mut file = if resume_from > 0 {
-            std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(&partial_path)?
-        } else {
-            std::fs::File::create(&partial_path)?
-        };
+        info!(
+            "Successfully downloaded model {} to {:?}",
+            model_id, model_path
+        );
 
-        // Emit initial progress
-        let initial_progress = DownloadProgress {
-            model_id: model_id.to_string(),
-            downloaded,
-            total: total_size,
-            percentage: if total_size > 0 {
-                (downloaded as f64 / total_size as f64) * 100.0
-            } else {
-                0.0
-            },
-        };
-        let _ = self
-            .app_handle
-            .emit("model-download-progress", &initial_progress);
-
-        // Download with progress
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| {
-                // Mark as not downloading on error
-                {
-                    let mut models = self.available_models.lock().unwrap();
-                    if let Some(model) = models.get_mut(model_id) {
-                        model.is_downloading = false;
-                    }
-                }
-                e
-            })?;
+        Ok(())
+    }
 
-            file.write_all(&chunk)?;
-            downloaded += chunk.len() as u64;
+    /// Downloads the model artifact, trying each URL in turn until one
+    /// produces bytes that match the recorded size and checksum, then moves
+    /// or extracts it into place.
+    async fn download_and_install(
+        &self,
+        model_info: &ModelInfo,
+        urls: &[String],
+        partial_path: &Path,
+        control: &DownloadControl,
+    ) -> Result<()> {
+        let model_id = model_info.id.as_str();
+        let mut last_error = None;
+        let mut verified = false;
+
+        for url in urls {
+            // A partial file left over from an earlier attempt may itself be
+            // the reason verification fails, so each URL gets one retry from scratch.
+            let mut fresh_retry_left = true;
+
+            loop {
+                let resumed = partial_path.exists();
+                let attempt = match fetch_to_partial(model_info, url, partial_path, control).await {
+                    Ok(()) => verify_artifact(
+                        partial_path,
+                        model_info.size_bytes,
+                        model_info.sha256.as_deref(),
+                    ),
+                    // Pausing or cancelling isn't a reason to try another mirror
+                    Err(e) if e.is::<DownloadInterrupted>() => return Err(e),
+                    Err(e) => {
+                        warn!("Download of model {} from {} failed: {}", model_id, url, e);
+                        last_error = Some(e);
+                        break;
+                    }
+                };
 
-            let percentage = if total_size > 0 {
-                (downloaded as f64 / total_size as f64) * 100.0
-            } else {
-                0.0
-            };
+                match attempt {
+                    Ok(()) => {
+                        verified = true;
+                        break;
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Downloaded model {} from {} failed verification: {}",
+                            model_id, url, e
+                        );
+                        let _ = fs::remove_file(partial_path);
+                        last_error = Some(e);
+                        if resumed && fresh_retry_left {
+                            fresh_retry_left = false;
+                            info!("Retrying download of model {} from scratch", model_id);
+                            continue;
+                        }
+                        break;
+                    }
+                }
+            }
 
-            // Emit progress event
-            let progress = DownloadProgress {
-                model_id: model_id.to_string(),
-                downloaded,
-                total: total_size,
-                percentage,
-            };
+            if verified {
+                break;
+            }
+        }
 
-            let _ = self.app_handle.emit("model-download-progress", &progress);
+        if !verified {
+            return Err(last_error
+                .unwrap_or_else(|| anyhow::anyhow!("No download URL for model"))
+                .context(format!("Failed to download model {}", model_id)));
         }
 
-        file.flush()?;
-        drop(file); // Ensure file is closed before moving
+        let model_path = self.models_dir.join(&model_info.filename);
 
         // Handle directory-based models (extract tar.gz) vs file-based models
         if model_info.is_directory {
@@ -463,7 +784,7 @@ impl ModelManager {
             let temp_extract_dir = self
                 .models_dir
                 .join(format!("{}.extracting", &model_info.filename));
-            let final_model_dir = self.models_dir.join(&model_info.filename);
+            let final_model_dir = model_path;
 
             // Clean up any previous incomplete extraction
             if temp_extract_dir.exists() {
@@ -474,7 +795,7 @@ impl ModelManager {
             fs::create_dir_all(&temp_extract_dir)?;
 
             // Open the downloaded tar.gz file
-            let tar_gz = File::open(&partial_path)?;
+            let tar_gz = File::open(partial_path)?;
             let tar = GzDecoder::new(tar_gz);
             let mut archive = Archive::new(tar);
 
@@ -516,36 +837,177 @@ impl ModelManager {
                 fs::rename(&temp_extract_dir, &final_model_dir)?;
             }
 
+            // Record what was extracted so the directory can be verified later
+            if let Err(e) = self.write_integrity_record(model_info, &final_model_dir) {
+                warn!("Failed to write integrity record for {}: {}", model_id, e);
+            }
+
             info!("Successfully extracted archive for model: {}", model_id);
             // Emit extraction completed event
             let _ = self.app_handle.emit("model-extraction-completed", model_id);
 
             // Remove the downloaded tar.gz file
-            let _ = fs::remove_file(&partial_path);
+            let _ = fs::remove_file(partial_path);
         } else {
             // Move partial file to final location for file-based models
-            fs::rename(&partial_path, &model_path)?;
+            fs::rename(partial_path, &model_path)?;
         }
 
-        // Update download status
-        {
-            let mut models = self.available_models.lock().unwrap();
-            if let Some(model) = models.get_mut(model_id) {
-                model.is_downloading = false;
-                model.is_downloaded = true;
-                model.partial_size = 0;
+        Ok(())
+    }
+
+    fn integrity_record_path(&self, model_info: &ModelInfo) -> PathBuf {
+        self.models_dir.join(format!(
+            "{}{}",
+            &model_info.filename, INTEGRITY_RECORD_SUFFIX
+        ))
+    }
+
+    fn write_integrity_record(&self, model_info: &ModelInfo, model_dir: &Path) -> Result<()> {
+        let mut record = IntegrityRecord::default();
+        for file in list_files(model_dir) {
+            let relative = file
+                .strip_prefix(model_dir)?
+                .to_string_lossy()
+                .replace('\\', "/");
+            record.files.insert(
+                relative,
+                IntegrityRecordEntry {
+                    size: file.metadata()?.len(),
+                    sha256: sha256_file(&file)?,
+                },
+            );
+        }
+
+        fs::write(
+            self.integrity_record_path(model_info),
+            serde_json::to_string_pretty(&record)?,
+        )?;
+        Ok(())
+    }
+
+    /// Checks a model on disk against its recorded checksum and size.
+    /// Hashing reads every byte of the model, so call this off the main thread.
+    pub fn verify_model(&self, model_id: &str) -> Result<ModelVerification> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+
+        let can_redownload =
+            model_info.local_path.is_none() && !model_info.download_urls().is_empty();
+        let report = |status: IntegrityStatus, detail: Option<String>| ModelVerification {
+            model_id: model_id.to_string(),
+            status,
+            detail,
+            can_redownload,
+        };
+
+        let model_path = self.model_location(&model_info);
+        let partial_path = self
+            .models_dir
+            .join(format!("{}.partial", &model_info.filename));
+
+        if !model_path.exists() {
+            let detail = if partial_path.exists() {
+                "Download was interrupted before it finished"
+            } else {
+                "Model files are missing"
+            };
+            return Ok(report(
+                IntegrityStatus::Incomplete,
+                Some(detail.to_string()),
+            ));
+        }
+
+        if !model_info.is_directory {
+            if model_info.sha256.is_none() && model_info.size_bytes.is_none() {
+                return Ok(report(IntegrityStatus::Unverified, None));
             }
+            return Ok(
+                match verify_artifact(
+                    &model_path,
+                    model_info.size_bytes,
+                    model_info.sha256.as_deref(),
+                ) {
+                    Ok(()) => report(IntegrityStatus::Verified, None),
+                    Err(e) => report(IntegrityStatus::Corrupted, Some(e.to_string())),
+                },
+            );
         }
 
-        // Emit completion event
-        let _ = self.app_handle.emit("model-download-complete", model_id);
+        if list_files(&model_path).is_empty() {
+            return Ok(report(
+                IntegrityStatus::Incomplete,
+                Some("Model directory is empty".to_string()),
+            ));
+        }
 
-        info!(
-            "Successfully downloaded model {} to {:?}",
-            model_id, model_path
-        );
+        // Registered directories and models downloaded before integrity records
+        // existed have nothing to compare against
+        let record_path = self.integrity_record_path(&model_info);
+        if model_info.local_path.is_some() || !record_path.exists() {
+            return Ok(report(IntegrityStatus::Unverified, None));
+        }
 
-        Ok(())
+        let record: IntegrityRecord = serde_json::from_str(&fs::read_to_string(&record_path)?)?;
+        for (relative, expected) in &record.files {
+            let file = model_path.join(relative);
+            if !file.is_file() {
+                return Ok(report(
+                    IntegrityStatus::Incomplete,
+                    Some(format!("Missing file {}", relative)),
+                ));
+            }
+            if let Err(e) = verify_artifact(&file, Some(expected.size), Some(&expected.sha256)) {
+                return Ok(report(
+                    IntegrityStatus::Corrupted,
+                    Some(format!("{}: {}", relative, e)),
+                ));
+            }
+        }
+
+        Ok(report(IntegrityStatus::Verified, None))
+    }
+
+    /// Verifies every model that is on disk or has a leftover partial download.
+    pub fn verify_models(&self) -> Vec<ModelVerification> {
+        let candidates: Vec<ModelInfo> = self
+            .get_available_models()
+            .into_iter()
+            .filter(|model| !self.active_downloads.lock().unwrap().contains(&model.id))
+            .filter(|model| {
+                model.is_downloaded
+                    || self
+                        .models_dir
+                        .join(format!("{}.partial", &model.filename))
+                        .exists()
+            })
+            .collect();
+
+        candidates
+            .iter()
+            .map(|model| {
+                let result = self
+                    .verify_model(&model.id)
+                    .unwrap_or_else(|e| ModelVerification {
+                        model_id: model.id.clone(),
+                        status: IntegrityStatus::Corrupted,
+                        detail: Some(e.to_string()),
+                        can_redownload: model.local_path.is_none(),
+                    });
+                if result.status != IntegrityStatus::Verified
+                    && result.status != IntegrityStatus::Unverified
+                {
+                    warn!(
+                        "Model {} failed verification ({:?}): {}",
+                        model.id,
+                        result.status,
+                        result.detail.as_deref().unwrap_or("")
+                    );
+                }
+                result
+            })
+            .collect()
     }
 
     pub fn delete_model(&self, model_id: &str) -> Result<()> {
@@ -561,6 +1023,11 @@ impl ModelManager {
 
         debug!("ModelManager: Found model info: {:?}", model_info);
 
+        // Registered local models are only removed from the catalog, never deleted from disk
+        if model_info.local_path.is_some() {
+            return self.remove_custom_model(model_id);
+        }
+
         let model_path = self.models_dir.join(&model_info.filename);
         let partial_path = self
             .models_dir
@@ -600,6 +1067,11 @@ impl ModelManager {
             return Err(anyhow::anyhow!("No model files found to delete"));
         }
 
+        let record_path = self.integrity_record_path(&model_info);
+        if record_path.exists() {
+            let _ = fs::remove_file(&record_path);
+        }
+
         // Update download status
         self.update_download_status()?;
         debug!("ModelManager: download status updated");
@@ -607,6 +1079,37 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Throws away whatever is on disk for a model so it can be downloaded again.
+    pub fn discard_model_files(&self, model_id: &str) -> Result<()> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+
+        if model_info.local_path.is_some() {
+            return Err(anyhow::anyhow!(
+                "Model {} was registered from disk and cannot be downloaded",
+                model_id
+            ));
+        }
+
+        if self.active_downloads.lock().unwrap().contains(model_id) {
+            return Err(anyhow::anyhow!(
+                "Model is already downloading: {}",
+                model_id
+            ));
+        }
+
+        info!(
+            "Discarding files of model {} before re-downloading",
+            model_id
+        );
+        // Nothing to delete is fine here, the model may be missing entirely
+        if let Err(e) = self.delete_model(model_id) {
+            debug!("Nothing removed before re-downloading {}: {}", model_id, e);
+        }
+        self.update_download_status()
+    }
+
     pub fn get_model_path(&self, model_id: &str) -> Result<PathBuf> {
         let model_info = self
             .get_model_info(model_id)
@@ -624,7 +1127,7 @@ impl ModelManager {
             ));
         }
 
-        let model_path = self.models_dir.join(&model_info.filename);
+        let model_path = self.model_location(&model_info);
         let partial_path = self
             .models_dir
             .join(format!("{}.partial", &model_info.filename));
@@ -682,3 +1185,402 @@ impl ModelManager {
         Ok(())
     }
 }
+
+/// Total size of a file, or of every file below a directory.
+fn path_size_bytes(path: &Path) -> u64 {
+    if path.is_file() {
+        return path.metadata().map(|m| m.len()).unwrap_or(0);
+    }
+
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| path_size_bytes(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Every regular file below a directory, recursively.
+fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                files.extend(list_files(&path));
+            } else if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// Lowercase hex SHA-256 of a file's contents.
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Streams `url` into the partial file, resuming from whatever is already there.
+async fn fetch_to_partial(
+    model_info: &ModelInfo,
+    url: &str,
+    partial_path: &Path,
+    control: &DownloadControl,
+) -> Result<()> {
+    let model_id = model_info.id.as_str();
+
+    // Check if we have a partial download to resume
+    let mut resume_from = if partial_path.exists() {
+        let size = partial_path.metadata()?.len();
+        info!(
+            "Resuming download of model {} from byte {} using {}",
+            model_id, size, url
+        );
+        size
+    } else {
+        info!("Starting fresh download of model {} from {}", model_id, url);
+        0
+    };
+
+    // Nothing left to fetch, let verification decide whether the file is good
+    if let Some(expected) = model_info.size_bytes {
+        if resume_from >= expected {
+            return Ok(());
+        }
+    }
+
+    // Create HTTP client with range request for resuming
+    // No overall timeout, downloads take as long as they take
+    let mut request = crate::http::client().get(url);
+
+    if resume_from > 0 {
+        request = request.header("Range", format!("bytes={}-", resume_from));
+    }
+
+    let response = request.send().await?;
+
+    if resume_from > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial file already covers the whole artifact
+        return Ok(());
+    }
+
+    // Check for success or partial content status
+    if !response.status().is_success() && response.status() != reqwest::StatusCode::PARTIAL_CONTENT
+    {
+        return Err(anyhow::anyhow!(
+            "Failed to download model: HTTP {}",
+            response.status()
+        ));
+    }
+
+    // A server that ignores the Range header sends the whole file again
+    if resume_from > 0 && response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        warn!(
+            "{} does not support resuming, restarting download of {}",
+            url, model_id
+        );
+        resume_from = 0;
+    }
+
+    let total_size = model_info.size_bytes.unwrap_or_else(|| {
+        // For resumed downloads, add the resume point to content length
+        resume_from + response.content_length().unwrap_or(0)
+    });
+
+    let mut downloaded = resume_from;
+    let mut stream = response.bytes_stream();
+
+    // Open file for appending if resuming, or create new if starting fresh
+    let mut file = if resume_from > 0 {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(partial_path)?
+    } else {
+        std::fs::File::create(partial_path)?
+    };
+
+    // Progress is reported by whoever owns the control
+    control.set_progress(downloaded, total_size);
+
+    // Waiting on the next chunk also watches for a pause or cancel, so a
+    // stalled connection can't hold the download open
+    loop {
+        let next = match control.interrupt.run_until_cancelled(stream.next()).await {
+            Ok(next) => next,
+            Err(_) => {
+                file.flush()?;
+                info!(
+                    "Download of model {} stopped at byte {}",
+                    model_id, downloaded
+                );
+                return Err(DownloadInterrupted.into());
+            }
+        };
+        let Some(chunk) = next else {
+            break;
+        };
+
+        let chunk = chunk?;
+        control.throttle(chunk.len() as u64).await;
+
+        file.write_all(&chunk)?;
+        downloaded += chunk.len() as u64;
+        control.set_progress(downloaded, total_size);
+    }
+
+    file.flush()?;
+    Ok(())
+}
+
+/// Checks a file against an expected size and SHA-256. Either may be absent,
+/// in which case that check is skipped.
+fn verify_artifact(path: &Path, size_bytes: Option<u64>, sha256: Option<&str>) -> Result<()> {
+    if let Some(expected) = size_bytes {
+        let actual = path.metadata()?.len();
+        if actual != expected {
+            return Err(anyhow::anyhow!(
+                "Size mismatch: expected {} bytes, found {}",
+                expected,
+                actual
+            ));
+        }
+    }
+
+    if let Some(expected) = sha256 {
+        let actual = sha256_file(path)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(anyhow::anyhow!(
+                "Checksum mismatch: expected {}, found {}",
+                expected,
+                actual
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::managers::download::RateLimiter;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const ARTIFACT_SIZE: usize = 10_000;
+
+    fn artifact() -> Vec<u8> {
+        (0..ARTIFACT_SIZE).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn test_model(size_bytes: Option<u64>) -> ModelInfo {
+        ModelInfo {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            description: String::new(),
+            filename: "test.bin".to_string(),
+            url: None,
+            size_mb: 0,
+            is_downloaded: false,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: false,
+            engine_type: EngineType::Whisper,
+            accuracy_score: 0.0,
+            speed_score: 0.0,
+            is_custom: false,
+            local_path: None,
+            sha256: None,
+            size_bytes,
+            mirrors: Vec::new(),
+            benchmark: None,
+        }
+    }
+
+    fn control() -> DownloadControl {
+        DownloadControl::new(Arc::new(RateLimiter::new(0)), 0)
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "handy-download-{}-{}.partial",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    /// Serves `artifact()` once and returns the request's Range header.
+    /// Honours the range only if `ranges` is set, and after `stall_after`
+    /// bytes stops sending without closing the connection.
+    async fn serve_artifact(
+        listener: TcpListener,
+        ranges: bool,
+        stall_after: Option<usize>,
+    ) -> Option<String> {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            let read = socket.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+        }
+        let range = String::from_utf8_lossy(&request).lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("range")
+                .then(|| value.trim().to_string())
+        });
+
+        let body = artifact();
+        let start = range
+            .as_deref()
+            .filter(|_| ranges)
+            .and_then(|range| range.strip_prefix("bytes="))
+            .and_then(|range| range.trim_end_matches('-').parse::<usize>().ok());
+        let head = match start {
+            Some(start) => format!(
+                "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\n\r\n",
+                body.len() - start,
+                start,
+                body.len() - 1,
+                body.len()
+            ),
+            None => format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len()),
+        };
+        let body = &body[start.unwrap_or(0)..];
+
+        socket.write_all(head.as_bytes()).await.unwrap();
+        match stall_after {
+            Some(sent) => {
+                socket.write_all(&body[..sent]).await.unwrap();
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+            None => socket.write_all(body).await.unwrap(),
+        }
+        range
+    }
+
+    async fn listen() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/model.bin", listener.local_addr().unwrap());
+        (listener, url)
+    }
+
+    #[tokio::test]
+    async fn fetch_resumes_from_the_partial_file() {
+        let (listener, url) = listen().await;
+        let server = tokio::spawn(serve_artifact(listener, true, None));
+        let path = temp_path("resume");
+        fs::write(&path, &artifact()[..4_000]).unwrap();
+
+        let model = test_model(Some(ARTIFACT_SIZE as u64));
+        fetch_to_partial(&model, &url, &path, &control())
+            .await
+            .unwrap();
+
+        assert_eq!(server.await.unwrap().as_deref(), Some("bytes=4000-"));
+        assert_eq!(fs::read(&path).unwrap(), artifact());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn fetch_restarts_when_the_server_ignores_the_range() {
+        let (listener, url) = listen().await;
+        let server = tokio::spawn(serve_artifact(listener, false, None));
+        let path = temp_path("restart");
+        fs::write(&path, &artifact()[..4_000]).unwrap();
+
+        let model = test_model(Some(ARTIFACT_SIZE as u64));
+        fetch_to_partial(&model, &url, &path, &control())
+            .await
+            .unwrap();
+
+        assert!(server.await.unwrap().is_some());
+        assert_eq!(fs::read(&path).unwrap(), artifact());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn fetch_stops_on_cancel_while_the_server_stalls() {
+        let (listener, url) = listen().await;
+        let server = tokio::spawn(serve_artifact(listener, true, Some(1_000)));
+        let path = temp_path("cancel");
+
+        let control = Arc::new(control());
+        let canceller = {
+            let control = Arc::clone(&control);
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                control.interrupt.cancel();
+            })
+        };
+
+        let model = test_model(Some(ARTIFACT_SIZE as u64));
+        let result = tokio::time::timeout(
+            Duration::from_secs(10),
+            fetch_to_partial(&model, &url, &path, &control),
+        )
+        .await
+        .expect("a stalled download should stop when cancelled");
+
+        assert!(result.unwrap_err().is::<DownloadInterrupted>());
+        // What arrived before the cancel is kept for resuming
+        assert_eq!(fs::read(&path).unwrap(), &artifact()[..1_000]);
+        canceller.await.unwrap();
+        server.abort();
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn verify_rejects_a_checksum_mismatch() {
+        let path = temp_path("verify");
+        fs::write(&path, artifact()).unwrap();
+        let sha256 = format!("{:x}", Sha256::digest(artifact()));
+        let size = Some(ARTIFACT_SIZE as u64);
+
+        assert!(verify_artifact(&path, size, Some(&sha256)).is_ok());
+        assert!(verify_artifact(&path, size, Some(&sha256.to_uppercase())).is_ok());
+        let wrong = "0".repeat(64);
+        let error = verify_artifact(&path, size, Some(&wrong)).unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"), "{}", error);
+        let error = verify_artifact(&path, Some(1), Some(&sha256)).unwrap_err();
+        assert!(error.to_string().contains("Size mismatch"), "{}", error);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_bundled_models_have_checksums() {
+        let manifest: ModelManifest = serde_json::from_str(BUNDLED_MODEL_MANIFEST).unwrap();
+        assert!(!manifest.models.is_empty());
+        for model in manifest.models {
+            let sha256 = model.sha256.as_deref().unwrap_or_default();
+            assert!(
+                sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit()),
+                "{} has no SHA-256; run scripts/update_model_checksums.py",
+                model.id
+            );
+            assert!(
+                model.size_bytes.is_some_and(|size| size > 0),
+                "{} has no size_bytes; run scripts/update_model_checksums.py",
+                model.id
+            );
+        }
+    }
+}
@@ -1,9 +1,11 @@
-use crate::audio_toolkit::apply_custom_words;
+use crate::cancellation::CancellationToken;
+use crate::helpers::memory::{available_memory_bytes, resident_memory_bytes};
 use crate::managers::model::{EngineType, ModelManager};
-use crate::settings::{get_settings, ModelUnloadTimeout};
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout, TranscriptionProvider};
 use anyhow::Result;
 use log::{debug, error, info, warn};
 use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
@@ -18,6 +20,10 @@ use transcribe_rs::{
     },
     TranscriptionEngine,
 };
+use whisper_rs::{WhisperContext, WhisperContextParameters};
+
+/// One second of silence at 16 kHz, enough to trigger the first-inference setup
+const WARM_UP_SAMPLES: usize = 16000;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
@@ -25,13 +31,142 @@ pub struct ModelStateEvent {
     pub model_id: Option<String>,
     pub model_name: Option<String>,
     pub error: Option<String>,
+    /// Resident memory the model added when it loaded, reported with
+    /// `loading_completed` where the platform can measure it
+    pub model_memory_bytes: Option<u64>,
 }
 
-enum LoadedEngine {
+pub(crate) enum LoadedEngine {
     Whisper(WhisperEngine),
     Parakeet(ParakeetEngine),
 }
 
+impl LoadedEngine {
+    /// Loads the engine for `engine_type` from a model file or directory.
+    pub(crate) fn load(engine_type: EngineType, model_path: &Path) -> Result<Self> {
+        match engine_type {
+            EngineType::Whisper => {
+                let mut engine = WhisperEngine::new();
+                engine
+                    .load_model(model_path)
+                    .map_err(|e| anyhow::anyhow!("{}", e))?;
+                Ok(LoadedEngine::Whisper(engine))
+            }
+            EngineType::Parakeet => {
+                let mut engine = ParakeetEngine::new();
+                engine
+                    .load_model_with_params(model_path, ParakeetModelParams::int8())
+                    .map_err(|e| anyhow::anyhow!("{}", e))?;
+                Ok(LoadedEngine::Parakeet(engine))
+            }
+        }
+    }
+
+    /// Transcribes 16kHz mono audio. `language` only applies to Whisper.
+    pub(crate) fn transcribe(
+        &mut self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        translate: bool,
+    ) -> Result<String> {
+        let result = match self {
+            LoadedEngine::Whisper(whisper_engine) => {
+                let params = WhisperInferenceParams {
+                    language,
+                    translate,
+                    ..Default::default()
+                };
+
+                whisper_engine
+                    .transcribe_samples(audio, Some(params))
+                    .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?
+            }
+            LoadedEngine::Parakeet(parakeet_engine) => {
+                let params = ParakeetInferenceParams {
+                    timestamp_granularity: TimestampGranularity::Segment,
+                    ..Default::default()
+                };
+
+                parakeet_engine
+                    .transcribe_samples(audio, Some(params))
+                    .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?
+            }
+        };
+        Ok(result.text)
+    }
+}
+
+/// Whisper context used only for language identification.
+/// transcribe-rs does not expose detection, so we keep a second context
+/// for the same model file and create it lazily the first time the user
+/// restricts auto-detection to more than one language. Its memory counts
+/// towards the model's, and it is dropped whenever the model is.
+struct LanguageDetector {
+    model_path: PathBuf,
+    context: WhisperContext,
+}
+
+impl LanguageDetector {
+    fn new(model_path: &Path) -> Result<Self> {
+        let path = model_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid model path: {:?}", model_path))?;
+        let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+            .map_err(|e| anyhow::anyhow!("Failed to load language detection context: {}", e))?;
+        Ok(Self {
+            model_path: model_path.to_path_buf(),
+            context,
+        })
+    }
+
+    /// Runs Whisper language detection and returns the most likely language
+    /// among `allowed`, together with its probability.
+    fn detect(&self, audio: &[f32], allowed: &[String]) -> Result<(String, f32)> {
+        let threads = thread::available_parallelism()
+            .map(|n| n.get().min(8))
+            .unwrap_or(4);
+
+        let mut state = self
+            .context
+            .create_state()
+            .map_err(|e| anyhow::anyhow!("Failed to create whisper state: {}", e))?;
+        state
+            .pcm_to_mel(audio, threads)
+            .map_err(|e| anyhow::anyhow!("Failed to compute mel spectrogram: {}", e))?;
+        let (_, probabilities) = state
+            .lang_detect(0, threads)
+            .map_err(|e| anyhow::anyhow!("Whisper language detection failed: {}", e))?;
+
+        allowed
+            .iter()
+            .filter_map(|language| {
+                let id = whisper_rs::get_lang_id(language)?;
+                probabilities
+                    .get(id as usize)
+                    .map(|probability| (language.clone(), *probability))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .ok_or_else(|| anyhow::anyhow!("None of the allowed languages are known to Whisper"))
+    }
+}
+
+/// Maps the user's allowed languages to the ISO 639-1 codes Whisper understands.
+/// zh-Hans and zh-Hant both collapse to zh; the script is handled by OpenCC afterwards.
+fn whisper_allowed_languages(allowed: &[String]) -> Vec<String> {
+    let mut languages: Vec<String> = Vec::new();
+    for language in allowed {
+        let normalized = if language == "zh-Hans" || language == "zh-Hant" {
+            "zh".to_string()
+        } else {
+            language.clone()
+        };
+        if !languages.contains(&normalized) {
+            languages.push(normalized);
+        }
+    }
+    languages
+}
+
 #[derive(Clone)]
 pub struct TranscriptionManager {
     engine: Arc<Mutex<Option<LoadedEngine>>>,
@@ -43,6 +178,8 @@ pub struct TranscriptionManager {
     watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
     is_loading: Arc<Mutex<bool>>,
     loading_condvar: Arc<Condvar>,
+    language_detector: Arc<Mutex<Option<LanguageDetector>>>,
+    model_memory_bytes: Arc<Mutex<Option<u64>>>,
 }
 
 impl TranscriptionManager {
@@ -62,9 +199,11 @@ impl TranscriptionManager {
             watcher_handle: Arc::new(Mutex::new(None)),
             is_loading: Arc::new(Mutex::new(false)),
             loading_condvar: Arc::new(Condvar::new()),
+            language_detector: Arc::new(Mutex::new(None)),
+            model_memory_bytes: Arc::new(Mutex::new(None)),
         };
 
-        // Start the idle watcher
+        // Start the idle and memory-pressure watcher
         {
             let app_handle_cloned = app_handle.clone();
             let manager_cloned = manager.clone();
@@ -79,6 +218,18 @@ impl TranscriptionManager {
                     }
 
                     let settings = get_settings(&app_handle_cloned);
+
+                    if manager_cloned.is_model_loaded() && manager_cloned.memory_is_low(&settings) {
+                        info!(
+                            "Available memory below {} MB, unloading model",
+                            settings.low_memory_unload_threshold_mb
+                        );
+                        if let Err(e) = manager_cloned.unload_model() {
+                            error!("Failed to unload model under memory pressure: {}", e);
+                        }
+                        continue;
+                    }
+
                     let timeout_seconds = settings.model_unload_timeout.to_seconds();
 
                     if let Some(limit_seconds) = timeout_seconds {
@@ -107,6 +258,7 @@ impl TranscriptionManager {
                                             model_id: None,
                                             model_name: None,
                                             error: None,
+                                            model_memory_bytes: None,
                                         },
                                     );
                                     let unload_duration = unload_start.elapsed();
@@ -132,6 +284,51 @@ impl TranscriptionManager {
         engine.is_some()
     }
 
+    /// Resident memory the current model added when it was loaded, if known.
+    pub fn model_memory_bytes(&self) -> Option<u64> {
+        *self.model_memory_bytes.lock().unwrap()
+    }
+
+    /// Whether available system memory is under the configured unload
+    /// threshold. Always false where available memory can't be read.
+    fn memory_is_low(&self, settings: &AppSettings) -> bool {
+        let threshold_mb = settings.low_memory_unload_threshold_mb;
+        threshold_mb > 0
+            && available_memory_bytes()
+                .map_or(false, |available| available < threshold_mb * 1024 * 1024)
+    }
+
+    /// Starts loading the selected local model ahead of the first dictation.
+    /// Does nothing if it is already loaded or loading, not downloaded, or
+    /// memory is short enough that it would be unloaded again right away.
+    pub fn preload_model(&self, reason: &str) {
+        if self.is_model_loaded() || *self.is_loading.lock().unwrap() {
+            return;
+        }
+
+        let settings = get_settings(&self.app_handle);
+        if settings.provider != TranscriptionProvider::Local {
+            return;
+        }
+        let is_downloaded = self
+            .model_manager
+            .get_model_info(&settings.selected_model)
+            .map_or(false, |model| model.is_downloaded);
+        if !is_downloaded {
+            return;
+        }
+        if self.memory_is_low(&settings) {
+            debug!(
+                "Skipping model preload ({}): available memory is low",
+                reason
+            );
+            return;
+        }
+
+        debug!("Preloading model {} ({})", settings.selected_model, reason);
+        self.initiate_model_load(&settings.selected_model);
+    }
+
     pub fn unload_model(&self) -> Result<()> {
         let unload_start = std::time::Instant::now();
         debug!("Starting to unload model");
@@ -146,10 +343,15 @@ impl TranscriptionManager {
             }
             *engine = None; // Drop the engine to free memory
         }
+        {
+            let mut detector = self.language_detector.lock().unwrap();
+            *detector = None;
+        }
         {
             let mut current_model = self.current_model_id.lock().unwrap();
             *current_model = None;
         }
+        *self.model_memory_bytes.lock().unwrap() = None;
 
         // Emit unloaded event
         let _ = self.app_handle.emit(
@@ -159,6 +361,7 @@ impl TranscriptionManager {
                 model_id: None,
                 model_name: None,
                 error: None,
+                model_memory_bytes: None,
             },
         );
 
@@ -182,6 +385,7 @@ impl TranscriptionManager {
                 model_id: Some(model_id.to_string()),
                 model_name: None,
                 error: None,
+                model_memory_bytes: None,
             },
         );
 
@@ -199,63 +403,69 @@ impl TranscriptionManager {
                     model_id: Some(model_id.to_string()),
                     model_name: Some(model_info.name.clone()),
                     error: Some(error_msg.to_string()),
+                    model_memory_bytes: None,
                 },
             );
             return Err(anyhow::anyhow!(error_msg));
         }
 
         let model_path = self.model_manager.get_model_path(model_id)?;
+        let memory_before = resident_memory_bytes();
 
         // Create appropriate engine based on model type
-        let loaded_engine = match model_info.engine_type {
-            EngineType::Whisper => {
-                let mut engine = WhisperEngine::new();
-                engine.load_model(&model_path).map_err(|e| {
-                    let error_msg = format!("Failed to load whisper model {}: {}", model_id, e);
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
-                    anyhow::anyhow!(error_msg)
-                })?;
-                LoadedEngine::Whisper(engine)
-            }
-            EngineType::Parakeet => {
-                let mut engine = ParakeetEngine::new();
-                engine
-                    .load_model_with_params(&model_path, ParakeetModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load parakeet model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Parakeet(engine)
+        let mut loaded_engine =
+            LoadedEngine::load(model_info.engine_type, &model_path).map_err(|e| {
+                let engine_name = match model_info.engine_type {
+                    EngineType::Whisper => "whisper",
+                    EngineType::Parakeet => "parakeet",
+                };
+                let error_msg = format!("Failed to load {} model {}: {}", engine_name, model_id, e);
+                let _ = self.app_handle.emit(
+                    "model-state-changed",
+                    ModelStateEvent {
+                        event_type: "loading_failed".to_string(),
+                        model_id: Some(model_id.to_string()),
+                        model_name: Some(model_info.name.clone()),
+                        error: Some(error_msg.clone()),
+                        model_memory_bytes: None,
+                    },
+                );
+                anyhow::anyhow!(error_msg)
+            })?;
+
+        // The first inference allocates compute buffers and, on GPU backends,
+        // compiles kernels. Pay that here rather than on the first dictation.
+        if get_settings(&self.app_handle).warm_up_model {
+            let warm_up_start = std::time::Instant::now();
+            match loaded_engine.transcribe(vec![0.0; WARM_UP_SAMPLES], None, false) {
+                Ok(_) => debug!(
+                    "Warmed up model {} in {}ms",
+                    model_id,
+                    warm_up_start.elapsed().as_millis()
+                ),
+                Err(e) => warn!("Warm-up inference for model {} failed: {}", model_id, e),
             }
-        };
+        }
+
+        let model_memory_bytes = memory_before
+            .zip(resident_memory_bytes())
+            .map(|(before, after)| after.saturating_sub(before));
 
         // Update the current engine and model ID
         {
             let mut engine = self.engine.lock().unwrap();
             *engine = Some(loaded_engine);
         }
+        {
+            // The detector belongs to the previous model
+            let mut detector = self.language_detector.lock().unwrap();
+            *detector = None;
+        }
         {
             let mut current_model = self.current_model_id.lock().unwrap();
             *current_model = Some(model_id.to_string());
         }
+        *self.model_memory_bytes.lock().unwrap() = model_memory_bytes;
 
         // Emit loading completed event
         let _ = self.app_handle.emit(
@@ -265,30 +475,56 @@ impl TranscriptionManager {
                 model_id: Some(model_id.to_string()),
                 model_name: Some(model_info.name.clone()),
                 error: None,
+                model_memory_bytes,
             },
         );
 
         let load_duration = load_start.elapsed();
         debug!(
-            "Successfully loaded transcription model: {} (took {}ms)",
+            "Successfully loaded transcription model: {} (took {}ms, {} MB resident)",
             model_id,
-            load_duration.as_millis()
+            load_duration.as_millis(),
+            model_memory_bytes.map_or(0, |bytes| bytes / (1024 * 1024))
         );
         Ok(())
     }
 
+    /// Blocks until `model_id` is the loaded model, loading it if another
+    /// model (or none) is loaded. Waits out any load already in progress.
+    pub fn ensure_model(&self, model_id: &str, cancel: &CancellationToken) -> Result<()> {
+        {
+            let mut is_loading = self.is_loading.lock().unwrap();
+            while *is_loading {
+                cancel.check()?;
+                is_loading = self
+                    .loading_condvar
+                    .wait_timeout(is_loading, Duration::from_millis(100))
+                    .unwrap()
+                    .0;
+            }
+        }
+
+        if self.get_current_model().as_deref() == Some(model_id) && self.is_model_loaded() {
+            return Ok(());
+        }
+        cancel.check()?;
+        self.load_model(model_id)
+    }
+
     /// Kicks off the model loading in a background thread if it's not already loaded
-    pub fn initiate_model_load(&self) {
+    pub fn initiate_model_load(&self, model_id: &str) {
         let mut is_loading = self.is_loading.lock().unwrap();
-        if *is_loading || self.is_model_loaded() {
+        if *is_loading
+            || (self.is_model_loaded() && self.get_current_model().as_deref() == Some(model_id))
+        {
             return;
         }
 
         *is_loading = true;
         let self_clone = self.clone();
+        let model_id = model_id.to_string();
         thread::spawn(move || {
-            let settings = get_settings(&self_clone.app_handle);
-            if let Err(e) = self_clone.load_model(&settings.selected_model) {
+            if let Err(e) = self_clone.load_model(&model_id) {
                 error!("Failed to load model: {}", e);
             }
             let mut is_loading = self_clone.is_loading.lock().unwrap();
@@ -297,12 +533,91 @@ impl TranscriptionManager {
         });
     }
 
+    /// Picks the most likely language within `allowed` for the given audio,
+    /// loading the detection context for the current model if needed.
+    fn detect_allowed_language(&self, audio: &[f32], allowed: &[String]) -> Result<(String, f32)> {
+        let model_id = self
+            .get_current_model()
+            .ok_or_else(|| anyhow::anyhow!("No model loaded for language detection"))?;
+        let model_path = self.model_manager.get_model_path(&model_id)?;
+
+        let mut detector = self.language_detector.lock().unwrap();
+        let needs_load = match detector.as_ref() {
+            Some(existing) => existing.model_path != model_path,
+            None => true,
+        };
+        if needs_load {
+            *detector = None;
+            let load_start = std::time::Instant::now();
+            let memory_before = resident_memory_bytes();
+            *detector = Some(LanguageDetector::new(&model_path)?);
+            let detector_bytes = memory_before
+                .zip(resident_memory_bytes())
+                .map(|(before, after)| after.saturating_sub(before));
+
+            // The second context roughly doubles what the model holds
+            let mut model_memory_bytes = self.model_memory_bytes.lock().unwrap();
+            *model_memory_bytes = model_memory_bytes
+                .zip(detector_bytes)
+                .map(|(model, detector)| model + detector);
+            debug!(
+                "Loaded language detection context for {} in {}ms ({} MB resident)",
+                model_id,
+                load_start.elapsed().as_millis(),
+                detector_bytes.map_or(0, |bytes| bytes / (1024 * 1024))
+            );
+        }
+
+        detector
+            .as_ref()
+            .expect("Language detector initialised above")
+            .detect(audio, allowed)
+    }
+
+    /// Whether the model can translate speech into English itself. Whisper
+    /// can, except the turbo variants, which were not trained to translate.
+    pub fn supports_translation(&self, model_id: &str) -> bool {
+        self.model_manager
+            .get_model_info(model_id)
+            .is_some_and(|info| info.engine_type == EngineType::Whisper)
+            && !model_id.contains("turbo")
+    }
+
     pub fn get_current_model(&self) -> Option<String> {
         let current_model = self.current_model_id.lock().unwrap();
         current_model.clone()
     }
 
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
+    /// Returns `Err(Cancelled)` if `cancel` fires before the result is ready; an
+    /// inference that has already started runs to completion but its output
+    /// is discarded.
+    pub fn transcribe(&self, audio: Vec<f32>, cancel: &CancellationToken) -> Result<String> {
+        let settings = get_settings(&self.app_handle);
+        let result = self.transcribe_with_settings(audio, &settings, cancel);
+        self.release_after_transcription(&settings);
+        result
+    }
+
+    /// Unloads the model when it is set to unload immediately. Call once
+    /// every pass over a recording has run, since each pass needs the model.
+    pub fn release_after_transcription(&self, settings: &AppSettings) {
+        if settings.model_unload_timeout == ModelUnloadTimeout::Immediately {
+            info!("Immediately unloading model after transcription");
+            if let Err(e) = self.unload_model() {
+                error!("Failed to immediately unload model: {}", e);
+            }
+        }
+    }
+
+    /// Transcribes with an explicit configuration instead of the stored
+    /// settings, e.g. when evaluating an alternative setup. Leaves the model
+    /// loaded; see `release_after_transcription`.
+    pub fn transcribe_with_settings(
+        &self,
+        audio: Vec<f32>,
+        settings: &AppSettings,
+        cancel: &CancellationToken,
+    ) -> Result<String> {
         // Update last activity timestamp
         self.last_activity.store(
             SystemTime::now()
@@ -326,7 +641,12 @@ impl TranscriptionManager {
             // If the model is loading, wait for it to complete.
             let mut is_loading = self.is_loading.lock().unwrap();
             while *is_loading {
-                is_loading = self.loading_condvar.wait(is_loading).unwrap();
+                cancel.check()?;
+                is_loading = self
+                    .loading_condvar
+                    .wait_timeout(is_loading, Duration::from_millis(100))
+                    .unwrap()
+                    .0;
             }
 
             let engine_guard = self.engine.lock().unwrap();
@@ -335,8 +655,10 @@ impl TranscriptionManager {
             }
         }
 
-        // Get current settings for configuration
-        let settings = get_settings(&self.app_handle);
+        let translate = settings.whisper_translate()
+            && self
+                .get_current_model()
+                .is_some_and(|model_id| self.supports_translation(&model_id));
 
         // Perform transcription with the appropriate engine
         let result = {
@@ -347,70 +669,50 @@ impl TranscriptionManager {
                 )
             })?;
 
-            match engine {
-                LoadedEngine::Whisper(whisper_engine) => {
-                    // Normalize language code for Whisper
-                    // Convert zh-Hans and zh-Hant to zh since Whisper uses ISO 639-1 codes
-                    let whisper_language = if settings.selected_language == "auto" {
-                        None
-                    } else {
-                        let normalized = if settings.selected_language == "zh-Hans"
-                            || settings.selected_language == "zh-Hant"
-                        {
-                            "zh".to_string()
-                        } else {
-                            settings.selected_language.clone()
-                        };
-                        Some(normalized)
-                    };
-
-                    let params = WhisperInferenceParams {
-                        language: whisper_language,
-                        translate: settings.translate_to_english,
-                        ..Default::default()
-                    };
-
-                    whisper_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?
-                }
-                LoadedEngine::Parakeet(parakeet_engine) => {
-                    let params = ParakeetInferenceParams {
-                        timestamp_granularity: TimestampGranularity::Segment,
-                        ..Default::default()
+            let language = match engine {
+                LoadedEngine::Whisper(_) => {
+                    // Empty set: let Whisper auto-detect freely. One language: force it.
+                    // Several: detect, but only pick from the allowed set, then force that.
+                    let allowed = whisper_allowed_languages(&settings.allowed_languages);
+                    let whisper_language = match allowed.len() {
+                        0 => None,
+                        1 => allowed.first().cloned(),
+                        _ => match self.detect_allowed_language(&audio, &allowed) {
+                            Ok((language, probability)) => {
+                                debug!(
+                                    "Detected language '{}' (p={:.3}) within allowed set {:?}",
+                                    language, probability, allowed
+                                );
+                                Some(language)
+                            }
+                            Err(e) => {
+                                warn!(
+                                    "Restricted language detection failed, falling back to '{}': {}",
+                                    allowed[0], e
+                                );
+                                allowed.first().cloned()
+                            }
+                        },
                     };
-
-                    parakeet_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?
+                    whisper_language
                 }
-            }
-        };
+                LoadedEngine::Parakeet(_) => None,
+            };
 
-        // Apply word correction if custom words are configured
-        let corrected_result = if !settings.custom_words.is_empty() {
-            apply_custom_words(
-                &result.text,
-                &settings.custom_words,
-                settings.word_correction_threshold,
-            )
-        } else {
-            result.text
+            cancel.check()?;
+            engine.transcribe(audio, language, translate)?
         };
+        cancel.check()?;
 
         let et = std::time::Instant::now();
-        let translation_note = if settings.translate_to_english {
-            " (translated)"
-        } else {
-            ""
-        };
+        let translation_note = if translate { " (translated)" } else { "" };
         info!(
             "Transcription completed in {}ms{}",
             (et - st).as_millis(),
             translation_note
         );
 
-        let final_result = corrected_result.trim().to_string();
+        let final_result = result.trim().to_string();
 
         if final_result.is_empty() {
             info!("Transcription result is empty");
@@ -418,14 +720,6 @@ impl TranscriptionManager {
             info!("Transcription result: {}", final_result);
         }
 
-        // Check if we should immediately unload the model after transcription
-        if settings.model_unload_timeout == ModelUnloadTimeout::Immediately {
-            info!("Immediately unloading model after transcription");
-            if let Err(e) = self.unload_model() {
-                error!("Failed to immediately unload model: {}", e);
-            }
-        }
-
         Ok(final_result)
     }
 }
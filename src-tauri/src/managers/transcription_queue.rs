@@ -0,0 +1,393 @@
+use crate::cancellation::{CancellationToken, Cancelled, InFlightOperations};
+use crate::http::RequestError;
+use crate::managers::audio::AudioRecordingManager;
+use crate::overlay::{emit_overlay_error, hide_recording_overlay, show_transcribing_overlay};
+use crate::settings::{get_settings, PasteMethod};
+use crate::tray::{change_tray_icon, TrayIconState};
+use crate::utils;
+use log::{debug, error, info};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for a transcription slot
+    Queued,
+    Transcribing,
+    PostProcessing,
+    /// Text was pasted
+    Delivered,
+    /// Nothing to paste
+    Empty,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct JobStatusEvent {
+    pub job_id: u64,
+    pub status: JobStatus,
+    /// Jobs started but not yet delivered, including this one until it is
+    pub pending: usize,
+    pub error: Option<String>,
+}
+
+/// How a job ended. Held back until every earlier job has been delivered.
+pub enum JobOutcome {
+    Text {
+        text: String,
+        /// From the action profile of the shortcut that recorded it
+        paste_method: PasteMethod,
+    },
+    Empty,
+    Failed(RequestError),
+    Cancelled,
+}
+
+type AbandonHook = Box<dyn FnOnce(u64, JobOutcome, CancellationToken) + Send>;
+
+/// One recording's place in the queue. Dropped without `complete`, e.g.
+/// when its task panics, it completes as failed so later jobs still paste.
+pub struct TranscriptionJob {
+    pub id: u64,
+    pub cancel: CancellationToken,
+    on_abandon: Option<AbandonHook>,
+}
+
+impl Drop for TranscriptionJob {
+    fn drop(&mut self) {
+        if let Some(on_abandon) = self.on_abandon.take() {
+            error!("Transcription job {} ended without a result", self.id);
+            on_abandon(
+                self.id,
+                JobOutcome::Failed("Transcription stopped unexpectedly".into()),
+                self.cancel.clone(),
+            );
+        }
+    }
+}
+
+/// A finished job that is next in recording order.
+struct Deliverable {
+    id: u64,
+    outcome: JobOutcome,
+    cancel: CancellationToken,
+    /// Jobs not yet delivered once this one is
+    pending: usize,
+}
+
+struct QueueState {
+    next_job_id: u64,
+    next_delivery: u64,
+    running: usize,
+    finished: BTreeMap<u64, (JobOutcome, CancellationToken)>,
+}
+
+impl QueueState {
+    /// Records a job's outcome and takes every finished job that is now
+    /// next in recording order.
+    fn finish(
+        &mut self,
+        id: u64,
+        outcome: JobOutcome,
+        cancel: CancellationToken,
+    ) -> Vec<Deliverable> {
+        self.finished.insert(id, (outcome, cancel));
+
+        let mut deliverable = Vec::new();
+        while let Some((outcome, cancel)) = self.finished.remove(&self.next_delivery) {
+            let id = self.next_delivery;
+            self.next_delivery += 1;
+            deliverable.push(Deliverable {
+                id,
+                outcome,
+                cancel,
+                pending: TranscriptionQueue::pending_in(self),
+            });
+        }
+        deliverable
+    }
+}
+
+/// Orders transcriptions of back-to-back recordings. Jobs run with bounded
+/// parallelism but their results are always pasted in recording order.
+pub struct TranscriptionQueue {
+    app_handle: AppHandle,
+    state: Mutex<QueueState>,
+    slot_freed: Notify,
+}
+
+/// Holds one of the parallel transcription slots until dropped.
+pub struct TranscriptionSlot {
+    queue: Arc<TranscriptionQueue>,
+}
+
+impl Drop for TranscriptionSlot {
+    fn drop(&mut self) {
+        self.queue.state.lock().unwrap().running -= 1;
+        self.queue.slot_freed.notify_waiters();
+    }
+}
+
+impl TranscriptionQueue {
+    pub fn new(app_handle: &AppHandle) -> Self {
+        Self {
+            app_handle: app_handle.clone(),
+            state: Mutex::new(QueueState {
+                next_job_id: 0,
+                next_delivery: 0,
+                running: 0,
+                finished: BTreeMap::new(),
+            }),
+            slot_freed: Notify::new(),
+        }
+    }
+
+    /// Reserves the next place in paste order. Call this as soon as a
+    /// recording stops, before any async work, so ids follow recording order.
+    pub fn begin_job(self: &Arc<Self>) -> TranscriptionJob {
+        let cancel = self.app_handle.state::<InFlightOperations>().begin();
+        let (id, pending) = {
+            let mut state = self.state.lock().unwrap();
+            let id = state.next_job_id;
+            state.next_job_id += 1;
+            (id, Self::pending_in(&state))
+        };
+
+        debug!("Queued transcription job {} ({} pending)", id, pending);
+        self.emit_status(id, JobStatus::Queued, pending, None);
+        let queue = Arc::clone(self);
+        TranscriptionJob {
+            id,
+            cancel,
+            on_abandon: Some(Box::new(move |id, outcome, cancel| {
+                queue.finish(id, outcome, cancel)
+            })),
+        }
+    }
+
+    /// Waits until fewer than `max_parallel_transcriptions` jobs are running.
+    pub async fn acquire_slot(
+        self: &Arc<Self>,
+        job: &TranscriptionJob,
+    ) -> Result<TranscriptionSlot, Cancelled> {
+        loop {
+            // Register before checking so a slot freed in between is not missed
+            let notified = self.slot_freed.notified();
+            let limit = get_settings(&self.app_handle)
+                .max_parallel_transcriptions
+                .max(1);
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.running < limit {
+                    state.running += 1;
+                    let pending = Self::pending_in(&state);
+                    drop(state);
+
+                    self.emit_status(job.id, JobStatus::Transcribing, pending, None);
+                    return Ok(TranscriptionSlot {
+                        queue: Arc::clone(self),
+                    });
+                }
+            }
+            job.cancel.run_until_cancelled(notified).await?;
+        }
+    }
+
+    pub fn set_status(&self, job: &TranscriptionJob, status: JobStatus) {
+        let pending = self.pending();
+        self.emit_status(job.id, status, pending, None);
+    }
+
+    pub fn pending(&self) -> usize {
+        Self::pending_in(&self.state.lock().unwrap())
+    }
+
+    /// Re-checks the parallelism limit after the setting changes.
+    pub fn apply_settings(&self) {
+        self.slot_freed.notify_waiters();
+    }
+
+    /// Records a job's outcome and delivers every finished job that is next
+    /// in recording order.
+    pub fn complete(&self, mut job: TranscriptionJob, outcome: JobOutcome) {
+        job.on_abandon = None;
+        self.finish(job.id, outcome, job.cancel.clone());
+    }
+
+    fn finish(&self, id: u64, outcome: JobOutcome, cancel: CancellationToken) {
+        // Delivery happens under the lock so pastes reach the main thread in order
+        let mut state = self.state.lock().unwrap();
+        let deliverable = state.finish(id, outcome, cancel);
+        let delivered_any = !deliverable.is_empty();
+        for job in deliverable {
+            self.deliver(job.id, job.outcome, job.cancel, job.pending);
+        }
+
+        if delivered_any {
+            self.refresh_indicators(Self::pending_in(&state));
+        }
+    }
+
+    fn pending_in(state: &QueueState) -> usize {
+        (state.next_job_id - state.next_delivery) as usize
+    }
+
+    fn deliver(&self, id: u64, outcome: JobOutcome, cancel: CancellationToken, pending: usize) {
+        match outcome {
+            JobOutcome::Text { text, paste_method } if !cancel.is_cancelled() => {
+                let app = self.app_handle.clone();
+                let result = self.app_handle.run_on_main_thread(move || {
+                    // A cancel may still land while waiting for the main thread
+                    if cancel.is_cancelled() {
+                        return;
+                    }
+                    if let Err(e) = utils::paste(text, paste_method, app) {
+                        error!("Failed to paste transcription: {}", e);
+                    }
+                });
+                match result {
+                    Ok(()) => self.emit_status(id, JobStatus::Delivered, pending, None),
+                    Err(e) => {
+                        error!("Failed to run paste on main thread: {:?}", e);
+                        self.emit_status(id, JobStatus::Failed, pending, Some(e.to_string()));
+                    }
+                }
+            }
+            JobOutcome::Text { .. } | JobOutcome::Cancelled => {
+                info!("Transcription job {} cancelled", id);
+                self.emit_status(id, JobStatus::Cancelled, pending, None);
+            }
+            JobOutcome::Empty => self.emit_status(id, JobStatus::Empty, pending, None),
+            JobOutcome::Failed(error) => {
+                emit_overlay_error(&self.app_handle, &error);
+                self.emit_status(id, JobStatus::Failed, pending, Some(error.message));
+            }
+        }
+    }
+
+    /// Points the tray and overlay at the remaining work, unless a new
+    /// recording owns them.
+    fn refresh_indicators(&self, pending: usize) {
+        let app = self.app_handle.clone();
+        // Queued behind the pastes so the overlay stays up until they land
+        let _ = self.app_handle.run_on_main_thread(move || {
+            if app.state::<Arc<AudioRecordingManager>>().is_recording() {
+                return;
+            }
+            if pending == 0 {
+                hide_recording_overlay(&app);
+                change_tray_icon(&app, TrayIconState::Idle);
+            } else {
+                show_transcribing_overlay(&app);
+                change_tray_icon(&app, TrayIconState::Transcribing);
+            }
+        });
+    }
+
+    fn emit_status(&self, job_id: u64, status: JobStatus, pending: usize, error: Option<String>) {
+        let _ = self.app_handle.emit(
+            "transcription-job-status",
+            JobStatusEvent {
+                job_id,
+                status,
+                pending,
+                error,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_jobs(count: u64) -> Arc<Mutex<QueueState>> {
+        Arc::new(Mutex::new(QueueState {
+            next_job_id: count,
+            next_delivery: 0,
+            running: 0,
+            finished: BTreeMap::new(),
+        }))
+    }
+
+    /// A job whose outcome, abandoned or not, goes into `state`, recording
+    /// what gets delivered.
+    fn job(
+        id: u64,
+        state: &Arc<Mutex<QueueState>>,
+        delivered: &Arc<Mutex<Vec<(u64, bool)>>>,
+    ) -> TranscriptionJob {
+        let state = Arc::clone(state);
+        let delivered = Arc::clone(delivered);
+        TranscriptionJob {
+            id,
+            cancel: CancellationToken::new(),
+            on_abandon: Some(Box::new(move |id, outcome, cancel| {
+                for job in state.lock().unwrap().finish(id, outcome, cancel) {
+                    let failed = matches!(job.outcome, JobOutcome::Failed(_));
+                    delivered.lock().unwrap().push((job.id, failed));
+                }
+            })),
+        }
+    }
+
+    fn complete(
+        mut job: TranscriptionJob,
+        state: &Arc<Mutex<QueueState>>,
+        delivered: &Arc<Mutex<Vec<(u64, bool)>>>,
+    ) {
+        job.on_abandon = None;
+        for job in state
+            .lock()
+            .unwrap()
+            .finish(job.id, JobOutcome::Empty, job.cancel.clone())
+        {
+            let failed = matches!(job.outcome, JobOutcome::Failed(_));
+            delivered.lock().unwrap().push((job.id, failed));
+        }
+    }
+
+    #[test]
+    fn delivers_in_recording_order() {
+        let state = state_with_jobs(3);
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let first = job(0, &state, &delivered);
+        let second = job(1, &state, &delivered);
+        let third = job(2, &state, &delivered);
+
+        complete(third, &state, &delivered);
+        complete(second, &state, &delivered);
+        assert!(delivered.lock().unwrap().is_empty());
+
+        complete(first, &state, &delivered);
+        assert_eq!(
+            *delivered.lock().unwrap(),
+            vec![(0, false), (1, false), (2, false)]
+        );
+        assert_eq!(TranscriptionQueue::pending_in(&state.lock().unwrap()), 0);
+    }
+
+    #[test]
+    fn an_abandoned_job_fails_instead_of_blocking_later_ones() {
+        let state = state_with_jobs(3);
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let first = job(0, &state, &delivered);
+        let second = job(1, &state, &delivered);
+        let third = job(2, &state, &delivered);
+
+        complete(second, &state, &delivered);
+        complete(third, &state, &delivered);
+        // As when the job's task panics
+        drop(first);
+
+        assert_eq!(
+            *delivered.lock().unwrap(),
+            vec![(0, true), (1, false), (2, false)]
+        );
+    }
+}
@@ -0,0 +1,463 @@
+use anyhow::Result;
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use log::{debug, error, warn};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::settings::{get_settings, UsagePrice};
+
+/// Share of the monthly budget at which the first warning is sent
+const BUDGET_WARNING_RATIO: f64 = 0.8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageKind {
+    Transcription,
+    PostProcess,
+}
+
+impl UsageKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            UsageKind::Transcription => "transcription",
+            UsageKind::PostProcess => "post_process",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "post_process" => UsageKind::PostProcess,
+            _ => UsageKind::Transcription,
+        }
+    }
+}
+
+/// Usage of one provider and model on one day.
+#[derive(Clone, Debug, Serialize)]
+pub struct DailyUsage {
+    /// Local date, `YYYY-MM-DD`
+    pub day: String,
+    pub kind: UsageKind,
+    pub provider: String,
+    pub model: String,
+    /// Whether the requests went straight to the provider. Requests relayed
+    /// through the backend are paid through the account and have no
+    /// estimate.
+    pub direct: bool,
+    pub requests: u64,
+    pub audio_seconds: f64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// In USD, or `None` when the requests were relayed or no price is
+    /// configured for the model
+    pub estimated_cost: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct UsageSummary {
+    /// Estimated spend since the start of the current month, in USD
+    pub month_cost: f64,
+    pub monthly_budget: f64,
+    /// Direct requests this month that no configured price covers
+    pub unpriced_requests: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetLevel {
+    Approaching,
+    Exceeded,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BudgetWarningEvent {
+    pub level: BudgetLevel,
+    pub month_cost: f64,
+    pub monthly_budget: f64,
+}
+
+struct UsageEvent {
+    kind: UsageKind,
+    provider: String,
+    model: String,
+    direct: bool,
+    audio_seconds: f64,
+    prompt_tokens: u32,
+    completion_tokens: u32,
+}
+
+#[derive(Default)]
+struct BudgetState {
+    /// Month and estimated spend so far, kept up to date as usage is
+    /// recorded so the budget check doesn't re-read the whole month
+    month_cost: Option<(i32, u32, f64)>,
+    /// Month and level of the last budget warning, so each is sent once
+    last_warning: Option<(i32, u32, BudgetLevel)>,
+}
+
+impl BudgetState {
+    /// Whether `level` should be announced in `month`, remembering it if so.
+    fn should_warn(&mut self, month: (i32, u32), level: BudgetLevel) -> bool {
+        if let Some((year, last_month, last_level)) = self.last_warning {
+            if (year, last_month) == month
+                && (last_level == level || last_level == BudgetLevel::Exceeded)
+            {
+                return false;
+            }
+        }
+        self.last_warning = Some((month.0, month.1, level));
+        true
+    }
+}
+
+/// Records remote transcriptions and post-processing calls in the history
+/// database so usage and its cost can be reviewed without the provider's
+/// dashboard.
+#[derive(Clone)]
+pub struct UsageManager {
+    app_handle: AppHandle,
+    db_path: PathBuf,
+    budget: Arc<Mutex<BudgetState>>,
+}
+
+impl UsageManager {
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        let db_path = app_handle.path().app_data_dir()?.join("history.db");
+        Ok(Self {
+            app_handle: app_handle.clone(),
+            db_path,
+            budget: Arc::new(Mutex::new(BudgetState::default())),
+        })
+    }
+
+    fn get_connection(&self) -> Result<Connection> {
+        Ok(Connection::open(&self.db_path)?)
+    }
+
+    /// Records a transcription by the backend with history id `backend`,
+    /// e.g. `deepgram:nova-3`. `direct` is false when the request was relayed
+    /// through the backend. Local transcriptions cost nothing and are
+    /// skipped.
+    pub fn record_transcription(&self, backend: &str, audio_seconds: f64, direct: bool) {
+        let (provider, model) = backend.split_once(':').unwrap_or((backend, ""));
+        if provider == "local" {
+            return;
+        }
+        self.record(UsageEvent {
+            kind: UsageKind::Transcription,
+            provider: provider.to_string(),
+            model: model.to_string(),
+            direct,
+            audio_seconds,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+        });
+    }
+
+    /// Records a post-processing call with the token counts its response
+    /// reported.
+    pub fn record_post_process(
+        &self,
+        provider: &str,
+        model: &str,
+        prompt_tokens: u32,
+        completion_tokens: u32,
+    ) {
+        self.record(UsageEvent {
+            kind: UsageKind::PostProcess,
+            provider: provider.to_string(),
+            model: model.to_string(),
+            direct: true,
+            audio_seconds: 0.0,
+            prompt_tokens,
+            completion_tokens,
+        });
+    }
+
+    /// Forgets the running month total so the next record re-prices the
+    /// month. Call after the prices change.
+    pub fn prices_changed(&self) {
+        self.budget.lock().unwrap().month_cost = None;
+    }
+
+    fn record(&self, event: UsageEvent) {
+        let manager = self.clone();
+        tauri::async_runtime::spawn_blocking(move || manager.store(event));
+    }
+
+    fn store(&self, event: UsageEvent) {
+        // Held across the insert so a month total read from the database
+        // can't also count an event another thread is about to add
+        let mut budget = self.budget.lock().unwrap();
+        let result = self.get_connection().and_then(|conn| {
+            conn.execute(
+                "INSERT INTO usage_events (timestamp, kind, provider, model, direct, audio_seconds, prompt_tokens, completion_tokens) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    Utc::now().timestamp(),
+                    event.kind.as_str(),
+                    event.provider,
+                    event.model,
+                    event.direct,
+                    event.audio_seconds,
+                    event.prompt_tokens,
+                    event.completion_tokens
+                ],
+            )?;
+            Ok(())
+        });
+        if let Err(e) = result {
+            error!("Failed to record usage: {}", e);
+            return;
+        }
+        debug!(
+            "Recorded {} usage for {}:{} ({:.1}s audio, {} + {} tokens, direct: {})",
+            event.kind.as_str(),
+            event.provider,
+            event.model,
+            event.audio_seconds,
+            event.prompt_tokens,
+            event.completion_tokens,
+            event.direct
+        );
+        if !event.direct {
+            return;
+        }
+
+        let settings = get_settings(&self.app_handle);
+        let cost = find_price(&settings.usage_prices, &event.provider, &event.model)
+            .map(|price| {
+                estimate_cost(
+                    price,
+                    event.audio_seconds,
+                    event.prompt_tokens as u64,
+                    event.completion_tokens as u64,
+                )
+            })
+            .unwrap_or(0.0);
+        let now = Local::now();
+        let month = (now.year(), now.month());
+        let month_cost = match budget.month_cost {
+            Some((year, month_of_year, total)) if (year, month_of_year) == month => total + cost,
+            // The database already includes the event just recorded
+            _ => match self.summary() {
+                Ok(summary) => summary.month_cost,
+                Err(e) => {
+                    error!("Failed to total usage for the budget check: {}", e);
+                    return;
+                }
+            },
+        };
+        budget.month_cost = Some((month.0, month.1, month_cost));
+
+        let monthly_budget = settings.monthly_budget_usd;
+        let Some(level) = budget_level(month_cost, monthly_budget) else {
+            return;
+        };
+        if !budget.should_warn(month, level) {
+            return;
+        }
+        warn!(
+            "Estimated usage this month is ${:.2} of the ${:.2} budget",
+            month_cost, monthly_budget
+        );
+        if let Err(e) = self.app_handle.emit(
+            "usage-budget-warning",
+            BudgetWarningEvent {
+                level,
+                month_cost,
+                monthly_budget,
+            },
+        ) {
+            error!("Failed to emit usage-budget-warning event: {}", e);
+        }
+    }
+
+    /// Usage per day, provider and model over the last `days` days,
+    /// newest first.
+    pub fn usage_by_day(&self, days: u32) -> Result<Vec<DailyUsage>> {
+        let today = Local::now().date_naive();
+        let first_day = today - chrono::Days::new(days.saturating_sub(1) as u64);
+        self.usage_since(local_midnight(first_day))
+    }
+
+    pub fn summary(&self) -> Result<UsageSummary> {
+        let today = Local::now().date_naive();
+        let month_start = today.with_day(1).unwrap_or(today);
+        let usage = self.usage_since(local_midnight(month_start))?;
+
+        Ok(UsageSummary {
+            month_cost: usage
+                .iter()
+                .filter_map(|row| row.estimated_cost)
+                .fold(0.0, |total, cost| total + cost),
+            monthly_budget: get_settings(&self.app_handle).monthly_budget_usd,
+            unpriced_requests: usage
+                .iter()
+                .filter(|row| row.direct && row.estimated_cost.is_none())
+                .map(|row| row.requests)
+                .sum(),
+        })
+    }
+
+    fn usage_since(&self, since: i64) -> Result<Vec<DailyUsage>> {
+        let prices = get_settings(&self.app_handle).usage_prices;
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT date(timestamp, 'unixepoch', 'localtime') AS day, kind, provider, model, direct, COUNT(*) AS requests, SUM(audio_seconds) AS audio_seconds, SUM(prompt_tokens) AS prompt_tokens, SUM(completion_tokens) AS completion_tokens FROM usage_events WHERE timestamp >= ?1 GROUP BY day, kind, provider, model, direct ORDER BY day DESC, provider, model",
+        )?;
+
+        let rows = stmt.query_map(params![since], |row| {
+            let kind: String = row.get("kind")?;
+            Ok(DailyUsage {
+                day: row.get("day")?,
+                kind: UsageKind::parse(&kind),
+                provider: row.get("provider")?,
+                model: row.get("model")?,
+                direct: row.get("direct")?,
+                requests: row.get::<_, i64>("requests")? as u64,
+                audio_seconds: row.get("audio_seconds")?,
+                prompt_tokens: row.get::<_, i64>("prompt_tokens")? as u64,
+                completion_tokens: row.get::<_, i64>("completion_tokens")? as u64,
+                estimated_cost: None,
+            })
+        })?;
+
+        let mut usage = Vec::new();
+        for row in rows {
+            let mut row = row?;
+            if row.direct {
+                row.estimated_cost = find_price(&prices, &row.provider, &row.model).map(|price| {
+                    estimate_cost(
+                        price,
+                        row.audio_seconds,
+                        row.prompt_tokens,
+                        row.completion_tokens,
+                    )
+                });
+            }
+            usage.push(row);
+        }
+        Ok(usage)
+    }
+}
+
+/// The warning due once `month_cost` is spent of `budget`, if any. A
+/// budget of zero turns warnings off.
+fn budget_level(month_cost: f64, budget: f64) -> Option<BudgetLevel> {
+    if budget <= 0.0 {
+        None
+    } else if month_cost >= budget {
+        Some(BudgetLevel::Exceeded)
+    } else if month_cost >= budget * BUDGET_WARNING_RATIO {
+        Some(BudgetLevel::Approaching)
+    } else {
+        None
+    }
+}
+
+fn local_midnight(date: NaiveDate) -> i64 {
+    Local
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|time| time.timestamp())
+        .unwrap_or(0)
+}
+
+/// The price for `model`, or failing that the provider-wide price.
+pub fn find_price<'a>(
+    prices: &'a [UsagePrice],
+    provider: &str,
+    model: &str,
+) -> Option<&'a UsagePrice> {
+    prices
+        .iter()
+        .find(|price| price.provider == provider && price.model.as_deref() == Some(model))
+        .or_else(|| {
+            prices
+                .iter()
+                .find(|price| price.provider == provider && price.model.is_none())
+        })
+}
+
+pub fn estimate_cost(
+    price: &UsagePrice,
+    audio_seconds: f64,
+    prompt_tokens: u64,
+    completion_tokens: u64,
+) -> f64 {
+    price.per_audio_minute * audio_seconds / 60.0
+        + price.per_million_prompt_tokens * prompt_tokens as f64 / 1_000_000.0
+        + price.per_million_completion_tokens * completion_tokens as f64 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(provider: &str, model: Option<&str>, per_audio_minute: f64) -> UsagePrice {
+        UsagePrice {
+            provider: provider.to_string(),
+            model: model.map(|model| model.to_string()),
+            per_audio_minute,
+            per_million_prompt_tokens: 0.0,
+            per_million_completion_tokens: 0.0,
+        }
+    }
+
+    #[test]
+    fn find_price_prefers_the_model_over_the_provider() {
+        let prices = vec![
+            price("deepgram", None, 0.01),
+            price("deepgram", Some("nova-3"), 0.0043),
+            price("openai", Some("whisper-1"), 0.006),
+        ];
+
+        let nova = find_price(&prices, "deepgram", "nova-3").unwrap();
+        assert_eq!(nova.per_audio_minute, 0.0043);
+        let other = find_price(&prices, "deepgram", "enhanced").unwrap();
+        assert_eq!(other.per_audio_minute, 0.01);
+        assert!(find_price(&prices, "openai", "gpt-4o-transcribe").is_none());
+        assert!(find_price(&prices, "groq", "whisper-1").is_none());
+    }
+
+    #[test]
+    fn estimate_cost_adds_audio_and_tokens() {
+        let price = UsagePrice {
+            provider: "openai".to_string(),
+            model: None,
+            per_audio_minute: 0.006,
+            per_million_prompt_tokens: 0.15,
+            per_million_completion_tokens: 0.6,
+        };
+
+        let cost = estimate_cost(&price, 90.0, 2_000_000, 500_000);
+        assert!((cost - (0.009 + 0.3 + 0.3)).abs() < 1e-9);
+        assert_eq!(estimate_cost(&price, 0.0, 0, 0), 0.0);
+    }
+
+    #[test]
+    fn budget_level_warns_from_the_threshold() {
+        assert_eq!(budget_level(7.99, 10.0), None);
+        assert_eq!(budget_level(8.0, 10.0), Some(BudgetLevel::Approaching));
+        assert_eq!(budget_level(9.99, 10.0), Some(BudgetLevel::Approaching));
+        assert_eq!(budget_level(10.0, 10.0), Some(BudgetLevel::Exceeded));
+        assert_eq!(budget_level(25.0, 10.0), Some(BudgetLevel::Exceeded));
+        assert_eq!(budget_level(25.0, 0.0), None);
+    }
+
+    #[test]
+    fn each_budget_warning_is_sent_once_a_month() {
+        let mut state = BudgetState::default();
+        let october = (2026, 10);
+
+        assert!(state.should_warn(october, BudgetLevel::Approaching));
+        assert!(!state.should_warn(october, BudgetLevel::Approaching));
+        assert!(state.should_warn(october, BudgetLevel::Exceeded));
+        assert!(!state.should_warn(october, BudgetLevel::Approaching));
+        assert!(!state.should_warn(october, BudgetLevel::Exceeded));
+        assert!(state.should_warn((2026, 11), BudgetLevel::Approaching));
+    }
+}
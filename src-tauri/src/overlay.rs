@@ -1,3 +1,4 @@
+use crate::http::RequestError;
 use crate::settings;
 use crate::settings::OverlayPosition;
 use enigo::{Enigo, Mouse};
@@ -283,6 +284,22 @@ pub fn hide_recording_overlay(app_handle: &AppHandle) {
     }
 }
 
+/// Shows an error toast in the overlay, headed by the kind of failure
+pub fn emit_overlay_error(app_handle: &AppHandle, error: &RequestError) {
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit("transcription-error", error);
+    }
+}
+
+/// Sends the live transcript so far to the main window and the overlay
+pub fn emit_interim_transcript(app_handle: &AppHandle, text: &str) {
+    let _ = app_handle.emit("transcription-interim", text);
+
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit("transcription-interim", text);
+    }
+}
+
 pub fn emit_levels(app_handle: &AppHandle, levels: &Vec<f32>) {
     // emit levels to main app
     let _ = app_handle.emit("mic-level", levels);
@@ -0,0 +1,588 @@
+//! The text pipeline every transcription goes through before it is pasted:
+//! an ordered list of steps the user can enable and reorder, each working
+//! on the previous step's output.
+
+use crate::audio_toolkit::{
+    apply_custom_words, filter_hallucinations, fix_case_and_spacing, FilterAction,
+};
+use crate::cancellation::CancellationToken;
+use crate::clipboard::read_selection;
+use crate::helpers::active_window::active_window;
+use crate::managers::usage::UsageManager;
+use crate::settings::{AppSettings, PipelineStepKind, ReplacementRule};
+use async_openai::types::{
+    ChatCompletionRequestMessage, ChatCompletionRequestUserMessageArgs,
+    CreateChatCompletionRequestArgs,
+};
+use ferrous_opencc::{config::BuiltinConfig, OpenCC};
+use log::{debug, error, info, warn};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// One transcription on its way through the pipeline.
+pub struct PipelineInput<'a> {
+    pub settings: &'a AppSettings,
+    pub text: &'a str,
+    /// Share of the recording the VAD kept as speech, if known
+    pub speech_ratio: Option<f32>,
+    /// The engine already translated the speech into English
+    pub translated: bool,
+    pub cancel: &'a CancellationToken,
+    pub usage_manager: &'a UsageManager,
+    /// Where the text is going, for prompt variables
+    pub context: &'a PromptContext,
+}
+
+/// What the user was doing when the recording started, captured only for
+/// the variables the prompt actually uses.
+#[derive(Debug, Default, Clone)]
+pub struct PromptContext {
+    pub selection: Option<String>,
+    pub clipboard: Option<String>,
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+}
+
+impl PromptContext {
+    /// Reads the selection, clipboard and focused window if the prompt the
+    /// LLM step will run refers to them. Call before anything takes focus,
+    /// and off the shortcut thread: reading the selection can block.
+    pub fn capture(app: &AppHandle, settings: &AppSettings) -> Self {
+        if !settings.pipeline_step_enabled(PipelineStepKind::LlmPrompt) {
+            return PromptContext::default();
+        }
+        match selected_prompt(settings) {
+            Some(prompt) => Self::capture_for_prompt(app, prompt, false),
+            None => PromptContext::default(),
+        }
+    }
+
+    /// Reads the selection to edit, plus whatever else the edit-selection
+    /// prompt refers to.
+    pub fn capture_for_edit(app: &AppHandle, settings: &AppSettings) -> Self {
+        Self::capture_for_prompt(app, &settings.edit_selection_prompt, true)
+    }
+
+    fn capture_for_prompt(app: &AppHandle, prompt: &str, always_selection: bool) -> Self {
+        let mut context = PromptContext::default();
+        if prompt.contains("${app_name}") || prompt.contains("${window_title}") {
+            if let Some(window) = active_window() {
+                context.app_name = Some(window.app_name);
+                context.window_title = Some(window.title);
+            }
+        }
+        if prompt.contains("${clipboard}") {
+            context.clipboard = app.clipboard().read_text().ok();
+        }
+        if always_selection || prompt.contains("${selection}") {
+            context.selection = read_selection(app);
+        }
+        debug!(
+            "Captured prompt context (app: {:?}, selection: {} chars, clipboard: {} chars)",
+            context.app_name,
+            context.selection.as_deref().map_or(0, str::len),
+            context.clipboard.as_deref().map_or(0, str::len)
+        );
+        context
+    }
+}
+
+/// The text a step produced.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StepOutput {
+    pub step: PipelineStepKind,
+    pub text: String,
+}
+
+#[derive(Debug, Default)]
+pub struct PipelineOutput {
+    pub text: String,
+    /// Every step that ran, in order
+    pub steps: Vec<StepOutput>,
+    /// Output of the translation step
+    pub translation: Option<String>,
+    /// Prompt used by the LLM step
+    pub post_process_prompt: Option<String>,
+}
+
+/// Runs the enabled steps in order. Steps that have nothing to do, such as
+/// custom words with an empty list, are skipped and not recorded. Stops
+/// early once the text is empty or the job is cancelled.
+pub async fn run_pipeline(input: PipelineInput<'_>) -> PipelineOutput {
+    let settings = input.settings;
+    let mut output = PipelineOutput {
+        text: input.text.to_string(),
+        ..Default::default()
+    };
+
+    for step in settings.pipeline().into_iter().filter(|step| step.enabled) {
+        if output.text.trim().is_empty() || input.cancel.is_cancelled() {
+            break;
+        }
+
+        let text = &output.text;
+        let result = match step.kind {
+            PipelineStepKind::HallucinationFilter => {
+                Some(filter(settings, text, input.speech_ratio))
+            }
+            PipelineStepKind::CustomWords if settings.custom_words.is_empty() => None,
+            PipelineStepKind::CustomWords => Some(apply_custom_words(
+                text,
+                &settings.custom_words,
+                settings.word_correction_threshold,
+            )),
+            PipelineStepKind::Replacements if settings.replacement_rules.is_empty() => None,
+            PipelineStepKind::Replacements => {
+                Some(apply_replacements(text, &settings.replacement_rules))
+            }
+            PipelineStepKind::ChineseConversion => convert_chinese_variant(settings, text),
+            PipelineStepKind::Translation if input.translated => None,
+            PipelineStepKind::Translation => {
+                let translation =
+                    translate(settings, text, input.cancel, input.usage_manager).await;
+                output.translation = translation.clone();
+                translation
+            }
+            PipelineStepKind::LlmPrompt => run_llm_prompt(
+                settings,
+                text,
+                input.context,
+                input.cancel,
+                input.usage_manager,
+            )
+            .await
+            .map(|(text, prompt)| {
+                output.post_process_prompt = Some(prompt);
+                text
+            }),
+            PipelineStepKind::Formatting => Some(fix_case_and_spacing(text)),
+        };
+
+        if let Some(text) = result {
+            debug!(
+                "Pipeline step {:?} produced {} chars",
+                step.kind,
+                text.len()
+            );
+            output.steps.push(StepOutput {
+                step: step.kind,
+                text: text.clone(),
+            });
+            output.text = text;
+        }
+    }
+
+    output
+}
+
+/// Drops or trims output Whisper invented from silence.
+fn filter(settings: &AppSettings, text: &str, speech_ratio: Option<f32>) -> String {
+    let filtered = filter_hallucinations(
+        text,
+        settings.forced_language(),
+        speech_ratio,
+        settings.min_speech_ratio,
+    );
+    match &filtered.action {
+        FilterAction::Kept => {}
+        FilterAction::Trimmed(reason) => {
+            info!("Trimmed transcription ({}): {:?}", reason, text)
+        }
+        FilterAction::Dropped(reason) => {
+            info!("Dropped transcription ({}): {:?}", reason, text)
+        }
+    }
+    filtered.text
+}
+
+/// Builds the regex for a replacement rule.
+pub fn compile_rule(rule: &ReplacementRule) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(&rule.pattern)
+        .case_insensitive(rule.case_insensitive)
+        .build()
+}
+
+/// Applies the rules in order, skipping any whose pattern doesn't compile.
+fn apply_replacements(text: &str, rules: &[ReplacementRule]) -> String {
+    rules
+        .iter()
+        .fold(text.to_string(), |text, rule| match compile_rule(rule) {
+            Ok(regex) => regex
+                .replace_all(&text, rule.replacement.as_str())
+                .into_owned(),
+            Err(e) => {
+                warn!("Skipping replacement rule '{}': {}", rule.pattern, e);
+                text
+            }
+        })
+}
+
+/// Asks the LLM to translate into this language; `${language}` is the tag
+/// and `${output}` the transcription.
+const TRANSLATION_PROMPT: &str = "Translate the following transcript into the language with the BCP 47 tag \"${language}\". Keep the meaning, tone and formatting, including any \"Speaker N:\" labels, and reply with only the translation.\n\n${output}";
+
+/// Sends one prompt to the selected post-processing provider and returns
+/// the reply, or `None` when no provider is set up or the request fails.
+async fn complete_with_llm(
+    settings: &AppSettings,
+    prompt: String,
+    cancel: &CancellationToken,
+    usage_manager: &UsageManager,
+) -> Option<String> {
+    let provider = match settings.active_post_process_provider().cloned() {
+        Some(provider) => provider,
+        None => {
+            debug!("LLM request skipped because no provider is selected");
+            return None;
+        }
+    };
+
+    let model = settings
+        .post_process_models
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+
+    if model.trim().is_empty() {
+        debug!(
+            "LLM request skipped because provider '{}' has no model configured",
+            provider.id
+        );
+        return None;
+    }
+
+    let api_key = settings
+        .post_process_api_keys
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+
+    debug!(
+        "Sending LLM request to provider '{}' (model: {}, prompt length: {} chars)",
+        provider.id,
+        model,
+        prompt.len()
+    );
+
+    // Create OpenAI-compatible client
+    let client = match crate::llm_client::create_client(&provider, api_key) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Failed to create LLM client: {}", e);
+            return None;
+        }
+    };
+
+    // Build the chat completion request
+    let message = match ChatCompletionRequestUserMessageArgs::default()
+        .content(prompt)
+        .build()
+    {
+        Ok(msg) => ChatCompletionRequestMessage::User(msg),
+        Err(e) => {
+            error!("Failed to build chat message: {}", e);
+            return None;
+        }
+    };
+
+    let request = match CreateChatCompletionRequestArgs::default()
+        .model(&model)
+        .messages(vec![message])
+        .build()
+    {
+        Ok(req) => req,
+        Err(e) => {
+            error!("Failed to build chat completion request: {}", e);
+            return None;
+        }
+    };
+
+    // Send the request, abandoning it as soon as the operation is cancelled
+    let response = match cancel
+        .run_until_cancelled(client.chat().create(request))
+        .await
+    {
+        Ok(response) => response,
+        Err(_) => {
+            debug!("LLM request cancelled");
+            return None;
+        }
+    };
+
+    match response {
+        Ok(response) => {
+            if let Some(usage) = &response.usage {
+                usage_manager.record_post_process(
+                    &provider.id,
+                    &model,
+                    usage.prompt_tokens,
+                    usage.completion_tokens,
+                );
+            }
+            if let Some(choice) = response.choices.first() {
+                if let Some(content) = &choice.message.content {
+                    debug!(
+                        "LLM request succeeded for provider '{}'. Output length: {} chars",
+                        provider.id,
+                        content.len()
+                    );
+                    return Some(content.clone());
+                }
+            }
+            error!("LLM API response has no content");
+            None
+        }
+        Err(e) => {
+            error!(
+                "LLM request failed for provider '{}': {}. Falling back to original transcription.",
+                provider.id, e
+            );
+            None
+        }
+    }
+}
+
+/// The text of the selected post-processing prompt, if it is set and
+/// not empty.
+fn selected_prompt(settings: &AppSettings) -> Option<&str> {
+    let Some(selected_prompt_id) = &settings.post_process_selected_prompt_id else {
+        debug!("Post-processing skipped because no prompt is selected");
+        return None;
+    };
+
+    let Some(prompt) = settings
+        .post_process_prompts
+        .iter()
+        .find(|prompt| &prompt.id == selected_prompt_id)
+    else {
+        debug!(
+            "Post-processing skipped because prompt '{}' was not found",
+            selected_prompt_id
+        );
+        return None;
+    };
+
+    if prompt.prompt.trim().is_empty() {
+        debug!("Post-processing skipped because the selected prompt is empty");
+        return None;
+    }
+    Some(&prompt.prompt)
+}
+
+/// The variables every prompt can use besides the text it works on:
+/// - `${selection}`: text selected when the recording started
+/// - `${clipboard}`: clipboard text when the recording started
+/// - `${app_name}` and `${window_title}`: the focused application
+/// - `${language}`: the language the text should end up in, if known
+/// - `${date}`: today's date as YYYY-MM-DD
+///
+/// Variables without a value become empty.
+fn context_variables(
+    settings: &AppSettings,
+    context: &PromptContext,
+) -> Vec<(&'static str, String)> {
+    let language = settings
+        .translation_target()
+        .or_else(|| settings.forced_language())
+        .unwrap_or_default();
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+    vec![
+        ("${selection}", optional(&context.selection)),
+        ("${clipboard}", optional(&context.clipboard)),
+        ("${app_name}", optional(&context.app_name)),
+        ("${window_title}", optional(&context.window_title)),
+        ("${language}", language.to_string()),
+        ("${date}", date),
+    ]
+}
+
+/// Replaces the variables in one pass, so text inside a value that looks
+/// like a variable, such as a transcription containing "${date}", is left
+/// alone. Unknown variables are kept as they are.
+fn render_prompt(prompt: &str, variables: &[(&str, String)]) -> String {
+    let mut rendered = String::with_capacity(prompt.len());
+    let mut rest = prompt;
+    while let Some(start) = rest.find("${") {
+        rendered.push_str(&rest[..start]);
+        let candidate = &rest[start..];
+        match variables
+            .iter()
+            .find(|(name, _)| candidate.starts_with(name))
+        {
+            Some((name, value)) => {
+                rendered.push_str(value);
+                rest = &candidate[name.len()..];
+            }
+            None => {
+                rendered.push_str("${");
+                rest = &candidate[2..];
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Runs the selected post-processing prompt. Returns the reply and the
+/// prompt it was built from.
+async fn run_llm_prompt(
+    settings: &AppSettings,
+    transcription: &str,
+    context: &PromptContext,
+    cancel: &CancellationToken,
+    usage_manager: &UsageManager,
+) -> Option<(String, String)> {
+    let prompt = selected_prompt(settings)?.to_string();
+    let mut variables = context_variables(settings, context);
+    variables.push(("${output}", transcription.to_string()));
+    let processed_prompt = render_prompt(&prompt, &variables);
+    complete_with_llm(settings, processed_prompt, cancel, usage_manager)
+        .await
+        .map(|text| (text, prompt))
+}
+
+/// Applies a spoken instruction to the selected text with the
+/// post-processing LLM and returns the edited text. `None` when nothing
+/// was selected, no LLM is set up or the request fails.
+pub async fn edit_selection(
+    settings: &AppSettings,
+    instruction: &str,
+    context: &PromptContext,
+    cancel: &CancellationToken,
+    usage_manager: &UsageManager,
+) -> Option<String> {
+    if context.selection.as_deref().unwrap_or_default().is_empty() {
+        debug!("Edit skipped because no text is selected");
+        return None;
+    }
+
+    let mut variables = context_variables(settings, context);
+    variables.push(("${instruction}", instruction.to_string()));
+    let prompt = render_prompt(&settings.edit_selection_prompt, &variables);
+    let edited = complete_with_llm(settings, prompt, cancel, usage_manager).await?;
+    let edited = edited.trim();
+    (!edited.is_empty()).then(|| edited.to_string())
+}
+
+/// Translates the transcription into the target language with the
+/// post-processing LLM. Returns `None` when no translation is wanted or
+/// no LLM is set up.
+async fn translate(
+    settings: &AppSettings,
+    transcription: &str,
+    cancel: &CancellationToken,
+    usage_manager: &UsageManager,
+) -> Option<String> {
+    let language = settings.translation_target()?;
+    debug!("Translating transcription into '{}'", language);
+
+    let prompt = TRANSLATION_PROMPT
+        .replace("${language}", language)
+        .replace("${output}", transcription);
+    let translation = complete_with_llm(settings, prompt, cancel, usage_manager).await;
+    if translation.is_none() && !cancel.is_cancelled() {
+        warn!(
+            "Could not translate into '{}'; set up a post-processing provider to translate into languages other than English",
+            language
+        );
+    }
+    translation
+}
+
+fn convert_chinese_variant(settings: &AppSettings, transcription: &str) -> Option<String> {
+    // Check if the allowed languages include Simplified or Traditional Chinese
+    let variant = match settings.chinese_variant() {
+        Some(variant) => variant,
+        None => {
+            debug!(
+                "allowed_languages has no Simplified or Traditional Chinese; skipping conversion"
+            );
+            return None;
+        }
+    };
+    let is_simplified = variant == "zh-Hans";
+
+    debug!(
+        "Starting Chinese conversion using OpenCC for language: {}",
+        variant
+    );
+
+    // Use OpenCC to convert based on selected language
+    let config = if is_simplified {
+        // Convert Traditional Chinese to Simplified Chinese
+        BuiltinConfig::Tw2sp
+    } else {
+        // Convert Simplified Chinese to Traditional Chinese
+        BuiltinConfig::S2twp
+    };
+
+    match OpenCC::from_config(config) {
+        Ok(converter) => {
+            let converted = converter.convert(transcription);
+            debug!(
+                "OpenCC conversion completed. Input length: {}, Output length: {}",
+                transcription.len(),
+                converted.len()
+            );
+            Some(converted)
+        }
+        Err(e) => {
+            error!("Failed to initialize OpenCC converter: {}. Falling back to original transcription.", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, replacement: &str, case_insensitive: bool) -> ReplacementRule {
+        ReplacementRule {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            case_insensitive,
+        }
+    }
+
+    #[test]
+    fn test_replacements_apply_in_order() {
+        let rules = vec![
+            rule(r"\bnew line\b", "\n", true),
+            rule(r"(\d+) percent", "$1%", false),
+            rule(r"%", " per cent", false),
+        ];
+        assert_eq!(
+            apply_replacements("up 5 percent New Line done", &rules),
+            "up 5 per cent \n done"
+        );
+    }
+
+    #[test]
+    fn test_render_prompt() {
+        let variables = [
+            ("${selection}", "Hi ${output}".to_string()),
+            ("${app_name}", "Slack".to_string()),
+            ("${clipboard}", String::new()),
+            ("${language}", "de".to_string()),
+            ("${date}", "2024-05-01".to_string()),
+            ("${output}", "see you ${date}".to_string()),
+        ];
+        assert_eq!(
+            render_prompt(
+                "Reply to \"${selection}\" in ${app_name} (${language}, ${date}, ${clipboard}): ${output} ${unknown}",
+                &variables,
+            ),
+            "Reply to \"Hi ${output}\" in Slack (de, 2024-05-01, ): see you ${date} ${unknown}"
+        );
+    }
+
+    #[test]
+    fn test_invalid_replacement_is_skipped() {
+        let rules = vec![rule("(unclosed", "x", false), rule("a", "b", false)];
+        assert_eq!(apply_replacements("a(unclosed", &rules), "b(unclosed");
+    }
+}
@@ -50,3 +50,18 @@ pub fn fetch_api_key(provider: &str) -> Option<String> {
         }
     }
 }
+
+pub fn delete_api_key(provider: &str) -> Result<(), String> {
+    if let Ok(mut cache) = KEY_CACHE.lock() {
+        cache.remove(provider);
+    }
+    let entry = Entry::new(SERVICE_NAME, provider)
+        .map_err(|e| format!("Failed to create keyring entry: {}", e))?;
+    match entry.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => {
+            debug!("Removed '{}' from keyring", provider);
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to remove API key: {}", e)),
+    }
+}
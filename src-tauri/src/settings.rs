@@ -1,6 +1,7 @@
 use log::{debug, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use tauri::AppHandle;
 use tauri_plugin_log::LogLevel;
 use tauri_plugin_store::StoreExt;
@@ -12,6 +13,65 @@ pub struct ShortcutBinding {
     pub description: String,
     pub default_binding: String,
     pub current_binding: String,
+    #[serde(default)]
+    pub profile: ActionProfile,
+}
+
+/// How recordings made with one shortcut are transcribed and delivered.
+/// Unset fields follow the global settings.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActionProfile {
+    #[serde(default)]
+    pub provider: Option<TranscriptionProvider>,
+    /// Local model id, Deepgram model or OpenAI-compatible model of the
+    /// profile's provider
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Spoken language, or "auto" to detect it
+    #[serde(default)]
+    pub language: Option<String>,
+    /// An empty string turns translation off
+    #[serde(default)]
+    pub translation_target_language: Option<String>,
+    /// Post-processing prompt to run; an empty string skips the LLM step
+    #[serde(default)]
+    pub post_process_prompt_id: Option<String>,
+    #[serde(default)]
+    pub paste_method: Option<PasteMethod>,
+}
+
+/// Overrides used while a given application has focus when recording
+/// starts. Applied on top of the shortcut's own profile.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppProfile {
+    pub name: String,
+    /// Process names or, on Linux, window classes, compared without case
+    #[serde(default)]
+    pub apps: Vec<String>,
+    #[serde(default)]
+    pub profile: ActionProfile,
+    /// Replaces the global custom words
+    #[serde(default)]
+    pub custom_words: Option<Vec<String>>,
+}
+
+impl AppProfile {
+    /// Whether the profile lists any of these names. A trailing ".exe" is
+    /// ignored on both sides.
+    pub fn matches(&self, names: &[&str]) -> bool {
+        fn normalize(name: &str) -> String {
+            let name = name.trim().to_lowercase();
+            match name.strip_suffix(".exe") {
+                Some(stem) => stem.to_string(),
+                None => name,
+            }
+        }
+        let names: Vec<String> = names.iter().map(|name| normalize(name)).collect();
+        self.apps
+            .iter()
+            .map(|app| normalize(app))
+            .any(|app| !app.is_empty() && names.contains(&app))
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -21,6 +81,54 @@ pub struct LLMPrompt {
     pub prompt: String,
 }
 
+/// A step of the text pipeline that runs on every transcription.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStepKind {
+    /// Drops or trims text Whisper invented from silence
+    HallucinationFilter,
+    /// Fuzzy-corrects words towards `custom_words`
+    CustomWords,
+    /// Applies `replacement_rules` in order
+    Replacements,
+    /// Converts between Simplified and Traditional Chinese with OpenCC
+    ChineseConversion,
+    /// Translates into `translation_target_language`
+    Translation,
+    /// Rewrites the text with the selected post-processing prompt
+    LlmPrompt,
+    /// Fixes spacing around punctuation and capitalizes sentences
+    Formatting,
+}
+
+impl PipelineStepKind {
+    pub const ALL: [PipelineStepKind; 7] = [
+        PipelineStepKind::HallucinationFilter,
+        PipelineStepKind::CustomWords,
+        PipelineStepKind::Replacements,
+        PipelineStepKind::ChineseConversion,
+        PipelineStepKind::Translation,
+        PipelineStepKind::LlmPrompt,
+        PipelineStepKind::Formatting,
+    ];
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineStep {
+    pub kind: PipelineStepKind,
+    pub enabled: bool,
+}
+
+/// A regular-expression find/replace rule for the replacements step.
+/// `replacement` may refer to groups as `$1` or `${name}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReplacementRule {
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PostProcessProvider {
     pub id: String,
@@ -69,6 +177,27 @@ pub enum ClipboardHandling {
     CopyToClipboard,
 }
 
+/// Body format requested from an OpenAI-compatible transcription endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenAiResponseFormat {
+    Json,
+    Text,
+    VerboseJson,
+}
+
+/// Encoding used when uploading recordings to a cloud provider.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteAudioFormat {
+    /// The most compact format the provider is known to accept
+    #[default]
+    Auto,
+    Wav,
+    Flac,
+    Opus,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum RecordingRetentionPeriod {
@@ -101,6 +230,22 @@ impl Default for ClipboardHandling {
     }
 }
 
+impl Default for OpenAiResponseFormat {
+    fn default() -> Self {
+        OpenAiResponseFormat::Json
+    }
+}
+
+impl OpenAiResponseFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpenAiResponseFormat::Json => "json",
+            OpenAiResponseFormat::Text => "text",
+            OpenAiResponseFormat::VerboseJson => "verbose_json",
+        }
+    }
+}
+
 impl ModelUnloadTimeout {
     pub fn to_minutes(self) -> Option<u64> {
         match self {
@@ -159,6 +304,32 @@ pub enum TranscriptionProvider {
     Openai,
 }
 
+/// A backend tried, in order, after the selected provider fails.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FallbackBackend {
+    pub provider: TranscriptionProvider,
+    /// Local model id, Deepgram model or OpenAI-compatible model to use
+    /// instead of the one selected for that provider
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// What a provider charges, for estimating the cost of recorded usage.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UsagePrice {
+    /// Provider as recorded in the usage ledger, e.g. `deepgram` or `openai`
+    pub provider: String,
+    /// Applies to every model of the provider when not set
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub per_audio_minute: f64,
+    #[serde(default)]
+    pub per_million_prompt_tokens: f64,
+    #[serde(default)]
+    pub per_million_completion_tokens: f64,
+}
+
 impl Default for TranscriptionProvider {
     fn default() -> Self {
         TranscriptionProvider::Local
@@ -198,6 +369,21 @@ pub struct AppSettings {
     pub provider: TranscriptionProvider,
     #[serde(default = "default_deepgram_model")]
     pub deepgram_model: String,
+    #[serde(default = "default_deepgram_smart_format")]
+    pub deepgram_smart_format: bool,
+    #[serde(default = "default_deepgram_punctuate")]
+    pub deepgram_punctuate: bool,
+    #[serde(default)]
+    pub deepgram_numerals: bool,
+    /// Keep "um", "uh" and similar in the transcript instead of dropping them
+    #[serde(default)]
+    pub deepgram_filler_words: bool,
+    /// Stream audio over a WebSocket while recording instead of uploading
+    /// it afterwards. Needs a directly stored Deepgram key.
+    #[serde(default)]
+    pub deepgram_streaming: bool,
+    #[serde(default)]
+    pub remote_audio_format: RemoteAudioFormat,
     #[serde(default = "default_use_secure_storage")]
     pub use_secure_key_storage: bool,
     #[serde(default)]
@@ -210,6 +396,20 @@ pub struct AppSettings {
     pub deepgram_api_key: Option<String>,
     #[serde(default)]
     pub openai_api_key: Option<String>,
+    /// Server used for direct OpenAI-compatible transcription, up to and
+    /// including the version segment, e.g. https://api.groq.com/openai/v1
+    #[serde(default = "default_openai_transcription_base_url")]
+    pub openai_transcription_base_url: String,
+    #[serde(default = "default_openai_transcription_model")]
+    pub openai_transcription_model: String,
+    /// Overrides the language derived from `allowed_languages`
+    #[serde(default)]
+    pub openai_transcription_language: Option<String>,
+    #[serde(default)]
+    pub openai_transcription_prompt: String,
+    #[serde(default)]
+    pub openai_transcription_response_format: OpenAiResponseFormat,
+    /// Legacy plaintext account token, moved to secure storage on startup
     #[serde(default)]
     pub auth_token: Option<String>,
     #[serde(default)]
@@ -226,10 +426,17 @@ pub struct AppSettings {
     pub clamshell_microphone: Option<String>,
     #[serde(default)]
     pub selected_output_device: Option<String>,
-    #[serde(default = "default_translate_to_english")]
-    pub translate_to_english: bool,
-    #[serde(default = "default_selected_language")]
-    pub selected_language: String,
+    /// Language tag to translate transcripts into, e.g. `de`; empty keeps
+    /// the spoken language. English uses Whisper's own translation when the
+    /// backend supports it, anything else goes through the post-processing LLM.
+    #[serde(default)]
+    pub translation_target_language: String,
+    /// When the speech-to-text engine translates, also transcribe the
+    /// spoken text for history. Takes a second pass, or a second request.
+    #[serde(default)]
+    pub translation_keep_source_text: bool,
+    #[serde(default)]
+    pub allowed_languages: Vec<String>,
     #[serde(default = "default_overlay_position")]
     pub overlay_position: OverlayPosition,
     #[serde(default = "default_debug_mode")]
@@ -264,8 +471,58 @@ pub struct AppSettings {
     pub post_process_prompts: Vec<LLMPrompt>,
     #[serde(default)]
     pub post_process_selected_prompt_id: Option<String>,
+    /// Prompt for edit-selection commands; `${instruction}` is what was
+    /// said and `${selection}` the text being edited
+    #[serde(default = "default_edit_selection_prompt")]
+    pub edit_selection_prompt: String,
     #[serde(default)]
     pub mute_while_recording: bool,
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: usize,
+    #[serde(default)]
+    pub download_bandwidth_limit_kbps: u64,
+    #[serde(default = "default_min_speech_ratio")]
+    pub min_speech_ratio: f32,
+    /// Text steps run on every transcription, in order
+    #[serde(default = "default_post_process_pipeline")]
+    pub post_process_pipeline: Vec<PipelineStep>,
+    #[serde(default)]
+    pub replacement_rules: Vec<ReplacementRule>,
+    /// Checked in order; the first one listing the focused application wins
+    #[serde(default)]
+    pub app_profiles: Vec<AppProfile>,
+    #[serde(default = "default_max_parallel_transcriptions")]
+    pub max_parallel_transcriptions: usize,
+    #[serde(default = "default_preload_model_on_startup")]
+    pub preload_model_on_startup: bool,
+    #[serde(default = "default_preload_model_on_speech")]
+    pub preload_model_on_speech: bool,
+    #[serde(default = "default_warm_up_model")]
+    pub warm_up_model: bool,
+    /// Unload the model when available system memory drops below this many
+    /// megabytes. 0 disables the check.
+    #[serde(default)]
+    pub low_memory_unload_threshold_mb: u64,
+    #[serde(default)]
+    pub transcription_fallbacks: Vec<FallbackBackend>,
+    #[serde(default = "default_remote_transcription_timeout_secs")]
+    pub remote_transcription_timeout_secs: u64,
+    #[serde(default = "default_usage_prices")]
+    pub usage_prices: Vec<UsagePrice>,
+    /// Estimated monthly spend to warn about, in USD. 0 disables the warning.
+    #[serde(default)]
+    pub monthly_budget_usd: f64,
+    /// Split recordings into speaker turns. Deepgram labels the words
+    /// itself, local engines cluster the audio first.
+    #[serde(default)]
+    pub diarization_enabled: bool,
+    /// Most speakers local diarization may find. 0 lets it decide.
+    #[serde(default = "default_diarization_max_speakers")]
+    pub diarization_max_speakers: u32,
+    /// Render diarized transcripts as "Speaker 1: …" turns when pasting
+    /// and exporting
+    #[serde(default = "default_speaker_labels")]
+    pub speaker_labels: bool,
 }
 
 fn default_model() -> String {
@@ -280,15 +537,27 @@ fn default_deepgram_model() -> String {
     "nova-3".to_string()
 }
 
-fn default_use_secure_storage() -> bool {
+fn default_deepgram_smart_format() -> bool {
     true
 }
 
-fn default_always_on_microphone() -> bool {
-    false
+fn default_deepgram_punctuate() -> bool {
+    true
+}
+
+fn default_openai_transcription_base_url() -> String {
+    "https://api.openai.com/v1".to_string()
 }
 
-fn default_translate_to_english() -> bool {
+fn default_openai_transcription_model() -> String {
+    "whisper-1".to_string()
+}
+
+fn default_use_secure_storage() -> bool {
+    true
+}
+
+fn default_always_on_microphone() -> bool {
     false
 }
 
@@ -300,10 +569,6 @@ fn default_autostart_enabled() -> bool {
     false
 }
 
-fn default_selected_language() -> String {
-    "auto".to_string()
-}
-
 fn default_overlay_position() -> OverlayPosition {
     #[cfg(target_os = "linux")]
     return OverlayPosition::None;
@@ -323,6 +588,53 @@ fn default_word_correction_threshold() -> f64 {
     0.18
 }
 
+/// The built-in order. The LLM step follows `post_process_enabled` and
+/// formatting is opt-in since it rewrites the engine's punctuation.
+fn default_post_process_pipeline() -> Vec<PipelineStep> {
+    PipelineStepKind::ALL
+        .iter()
+        .map(|&kind| PipelineStep {
+            kind,
+            enabled: !matches!(
+                kind,
+                PipelineStepKind::LlmPrompt | PipelineStepKind::Formatting
+            ),
+        })
+        .collect()
+}
+
+fn default_min_speech_ratio() -> f32 {
+    0.05
+}
+
+fn default_max_parallel_transcriptions() -> usize {
+    1
+}
+
+fn default_remote_transcription_timeout_secs() -> u64 {
+    30
+}
+
+fn default_diarization_max_speakers() -> u32 {
+    4
+}
+
+fn default_speaker_labels() -> bool {
+    true
+}
+
+fn default_preload_model_on_startup() -> bool {
+    true
+}
+
+fn default_preload_model_on_speech() -> bool {
+    true
+}
+
+fn default_warm_up_model() -> bool {
+    true
+}
+
 fn default_history_limit() -> usize {
     5
 }
@@ -339,6 +651,10 @@ fn default_sound_theme() -> SoundTheme {
     SoundTheme::Marimba
 }
 
+fn default_max_concurrent_downloads() -> usize {
+    2
+}
+
 fn default_post_process_enabled() -> bool {
     false
 }
@@ -384,6 +700,33 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
     ]
 }
 
+/// List prices in USD at the time of writing; users can adjust them.
+fn default_usage_prices() -> Vec<UsagePrice> {
+    let audio = |provider: &str, model: Option<&str>, per_minute: f64| UsagePrice {
+        provider: provider.to_string(),
+        model: model.map(|model| model.to_string()),
+        per_audio_minute: per_minute,
+        per_million_prompt_tokens: 0.0,
+        per_million_completion_tokens: 0.0,
+    };
+    let tokens = |provider: &str, model: &str, prompt: f64, completion: f64| UsagePrice {
+        provider: provider.to_string(),
+        model: Some(model.to_string()),
+        per_audio_minute: 0.0,
+        per_million_prompt_tokens: prompt,
+        per_million_completion_tokens: completion,
+    };
+    vec![
+        audio("deepgram", None, 0.0043),
+        audio("deepgram-live", None, 0.0077),
+        audio("openai", Some("whisper-1"), 0.006),
+        audio("openai", Some("gpt-4o-transcribe"), 0.006),
+        audio("openai", Some("gpt-4o-mini-transcribe"), 0.003),
+        tokens("openai", "gpt-4o-mini", 0.15, 0.6),
+        tokens("openai", "gpt-4o", 2.5, 10.0),
+    ]
+}
+
 fn default_post_process_api_keys() -> HashMap<String, String> {
     let mut map = HashMap::new();
     for provider in default_post_process_providers() {
@@ -408,8 +751,32 @@ fn default_post_process_prompts() -> Vec<LLMPrompt> {
     }]
 }
 
+fn default_edit_selection_prompt() -> String {
+    "Edit the text below as instructed.\n\nInstruction: ${instruction}\n\nKeep the language of the text unless the instruction asks otherwise, and preserve formatting such as line breaks and lists. Reply with only the edited text, without quotes or explanations.\n\nText:\n${selection}".to_string()
+}
+
 pub const SETTINGS_STORE_PATH: &str = "settings_store.json";
 
+/// Id of the built-in shortcut that applies a spoken instruction to the
+/// selected text
+pub const EDIT_SELECTION_BINDING_ID: &str = "edit_selection";
+
+fn default_edit_selection_binding() -> ShortcutBinding {
+    #[cfg(target_os = "macos")]
+    let default_shortcut = "option+shift+space";
+    #[cfg(not(target_os = "macos"))]
+    let default_shortcut = "ctrl+shift+space";
+
+    ShortcutBinding {
+        id: EDIT_SELECTION_BINDING_ID.to_string(),
+        name: "Edit Selection".to_string(),
+        description: "Rewrites the selected text following a spoken instruction.".to_string(),
+        default_binding: default_shortcut.to_string(),
+        current_binding: default_shortcut.to_string(),
+        profile: ActionProfile::default(),
+    }
+}
+
 pub fn get_default_settings() -> AppSettings {
     #[cfg(target_os = "windows")]
     let default_shortcut = "ctrl+space";
@@ -429,8 +796,13 @@ pub fn get_default_settings() -> AppSettings {
             description: "Converts your speech into text.".to_string(),
             default_binding: default_shortcut.to_string(),
             current_binding: default_shortcut.to_string(),
+            profile: ActionProfile::default(),
         },
     );
+    bindings.insert(
+        EDIT_SELECTION_BINDING_ID.to_string(),
+        default_edit_selection_binding(),
+    );
 
     AppSettings {
         bindings,
@@ -443,12 +815,23 @@ pub fn get_default_settings() -> AppSettings {
         selected_model: "".to_string(),
         provider: default_provider(),
         deepgram_model: default_deepgram_model(),
+        deepgram_smart_format: default_deepgram_smart_format(),
+        deepgram_punctuate: default_deepgram_punctuate(),
+        deepgram_numerals: false,
+        deepgram_filler_words: false,
+        deepgram_streaming: false,
+        remote_audio_format: RemoteAudioFormat::default(),
         use_secure_key_storage: default_use_secure_storage(),
         api_base_url: None,
         deepgram_api_key_preview: String::new(),
         openai_api_key_preview: String::new(),
         deepgram_api_key: None,
         openai_api_key: None,
+        openai_transcription_base_url: default_openai_transcription_base_url(),
+        openai_transcription_model: default_openai_transcription_model(),
+        openai_transcription_language: None,
+        openai_transcription_prompt: String::new(),
+        openai_transcription_response_format: OpenAiResponseFormat::default(),
         auth_token: None,
         user_id: None,
         minutes_remaining: None,
@@ -457,8 +840,9 @@ pub fn get_default_settings() -> AppSettings {
         selected_microphone: None,
         clamshell_microphone: None,
         selected_output_device: None,
-        translate_to_english: false,
-        selected_language: "auto".to_string(),
+        translation_target_language: String::new(),
+        translation_keep_source_text: false,
+        allowed_languages: Vec::new(),
         overlay_position: OverlayPosition::Bottom,
         debug_mode: false,
         log_level: default_log_level(),
@@ -476,7 +860,26 @@ pub fn get_default_settings() -> AppSettings {
         post_process_models: default_post_process_models(),
         post_process_prompts: default_post_process_prompts(),
         post_process_selected_prompt_id: None,
+        edit_selection_prompt: default_edit_selection_prompt(),
         mute_while_recording: false,
+        max_concurrent_downloads: default_max_concurrent_downloads(),
+        download_bandwidth_limit_kbps: 0,
+        min_speech_ratio: default_min_speech_ratio(),
+        post_process_pipeline: default_post_process_pipeline(),
+        replacement_rules: Vec::new(),
+        app_profiles: Vec::new(),
+        max_parallel_transcriptions: default_max_parallel_transcriptions(),
+        preload_model_on_startup: default_preload_model_on_startup(),
+        preload_model_on_speech: default_preload_model_on_speech(),
+        warm_up_model: default_warm_up_model(),
+        low_memory_unload_threshold_mb: 0,
+        transcription_fallbacks: Vec::new(),
+        remote_transcription_timeout_secs: default_remote_transcription_timeout_secs(),
+        usage_prices: default_usage_prices(),
+        monthly_budget_usd: 0.0,
+        diarization_enabled: false,
+        diarization_max_speakers: default_diarization_max_speakers(),
+        speaker_labels: default_speaker_labels(),
     }
 }
 
@@ -493,6 +896,161 @@ impl AppSettings {
             .find(|provider| provider.id == provider_id)
     }
 
+    /// Returns the language to force when the allowed set holds exactly one entry.
+    /// An empty set means unrestricted auto-detection.
+    pub fn forced_language(&self) -> Option<&str> {
+        match self.allowed_languages.as_slice() {
+            [language] => Some(language.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Applies the action profile of the shortcut that started a recording.
+    pub fn for_binding(self, binding_id: &str) -> Self {
+        match self
+            .bindings
+            .get(binding_id)
+            .map(|binding| binding.profile.clone())
+        {
+            Some(profile) => self.with_profile(profile),
+            None => self,
+        }
+    }
+
+    /// The first app profile for the application with these names.
+    pub fn app_profile_for(&self, names: &[&str]) -> Option<&AppProfile> {
+        self.app_profiles
+            .iter()
+            .find(|profile| profile.matches(names))
+    }
+
+    /// The settings with an app profile's overrides applied.
+    pub fn with_app_profile(self, app_profile: &AppProfile) -> Self {
+        let mut settings = self.with_profile(app_profile.profile.clone());
+        if let Some(words) = &app_profile.custom_words {
+            settings.custom_words = words.clone();
+        }
+        settings
+    }
+
+    fn with_profile(mut self, profile: ActionProfile) -> Self {
+        if let Some(provider) = profile.provider {
+            self.provider = provider;
+        }
+        if let Some(model) = profile.model.filter(|model| !model.trim().is_empty()) {
+            match self.provider {
+                TranscriptionProvider::Local => self.selected_model = model,
+                TranscriptionProvider::Deepgram => self.deepgram_model = model,
+                TranscriptionProvider::Openai => self.openai_transcription_model = model,
+            }
+        }
+        if let Some(language) = profile.language {
+            self.allowed_languages = match language.trim() {
+                "" | "auto" => Vec::new(),
+                language => vec![language.to_string()],
+            };
+            // The OpenAI override would otherwise win over the profile
+            self.openai_transcription_language = None;
+        }
+        if let Some(language) = profile.translation_target_language {
+            self.translation_target_language = language;
+        }
+        if let Some(prompt_id) = profile.post_process_prompt_id {
+            let enabled = !prompt_id.is_empty();
+            if enabled {
+                self.post_process_selected_prompt_id = Some(prompt_id);
+            }
+            self.set_pipeline_step_enabled(PipelineStepKind::LlmPrompt, enabled);
+        }
+        if let Some(paste_method) = profile.paste_method {
+            self.paste_method = paste_method;
+        }
+        self
+    }
+
+    /// The language to translate into, if any.
+    pub fn translation_target(&self) -> Option<&str> {
+        Some(self.translation_target_language.trim()).filter(|language| !language.is_empty())
+    }
+
+    /// Whether to ask speech-to-text engines that can translate to produce
+    /// English directly.
+    pub fn whisper_translate(&self) -> bool {
+        self.translation_target()
+            .is_some_and(|language| language.eq_ignore_ascii_case("en"))
+    }
+
+    /// How long each remote backend gets before the chain moves on.
+    pub fn remote_transcription_timeout(&self) -> Duration {
+        Duration::from_secs(self.remote_transcription_timeout_secs.max(1))
+    }
+
+    /// Whether remote transcription goes through the configured backend
+    /// rather than straight to the provider.
+    pub fn uses_backend_relay(&self) -> bool {
+        self.api_base_url
+            .as_ref()
+            .is_some_and(|url| !url.trim().is_empty())
+    }
+
+    /// A copy that transcribes in the spoken language, for keeping the
+    /// source text next to a translation the engine made.
+    pub fn without_translation(&self) -> AppSettings {
+        let mut settings = self.clone();
+        settings.translation_target_language.clear();
+        settings
+    }
+
+    /// The pipeline in run order. Steps missing from the stored list, e.g.
+    /// ones added in a newer version, are appended disabled.
+    pub fn pipeline(&self) -> Vec<PipelineStep> {
+        let mut steps: Vec<PipelineStep> = Vec::with_capacity(PipelineStepKind::ALL.len());
+        for step in &self.post_process_pipeline {
+            if !steps.iter().any(|existing| existing.kind == step.kind) {
+                steps.push(*step);
+            }
+        }
+        for kind in PipelineStepKind::ALL {
+            if !steps.iter().any(|step| step.kind == kind) {
+                steps.push(PipelineStep {
+                    kind,
+                    enabled: false,
+                });
+            }
+        }
+        steps
+    }
+
+    pub fn pipeline_step_enabled(&self, kind: PipelineStepKind) -> bool {
+        self.pipeline()
+            .iter()
+            .any(|step| step.kind == kind && step.enabled)
+    }
+
+    /// Replaces the pipeline, keeping `post_process_enabled` in step with
+    /// the LLM step.
+    pub fn set_pipeline(&mut self, steps: Vec<PipelineStep>) {
+        self.post_process_pipeline = steps;
+        self.post_process_pipeline = self.pipeline();
+        self.post_process_enabled = self.pipeline_step_enabled(PipelineStepKind::LlmPrompt);
+    }
+
+    pub fn set_pipeline_step_enabled(&mut self, kind: PipelineStepKind, enabled: bool) {
+        let mut steps = self.pipeline();
+        for step in steps.iter_mut().filter(|step| step.kind == kind) {
+            step.enabled = enabled;
+        }
+        self.set_pipeline(steps);
+    }
+
+    /// Returns the Chinese script variant ("zh-Hans" or "zh-Hant") the user asked for, if any.
+    pub fn chinese_variant(&self) -> Option<&str> {
+        self.allowed_languages
+            .iter()
+            .map(|language| language.as_str())
+            .find(|language| *language == "zh-Hans" || *language == "zh-Hant")
+    }
+
     pub fn post_process_provider_mut(
         &mut self,
         provider_id: &str,
@@ -503,13 +1061,98 @@ impl AppSettings {
     }
 }
 
+/// Upgrades settings written by older versions in place.
+/// Returns true if the stored value was changed.
+fn migrate_settings_value(value: &mut serde_json::Value) -> bool {
+    let Some(object) = value.as_object_mut() else {
+        return false;
+    };
+
+    // `selected_language` (a single code or "auto") became `allowed_languages`
+    let mut migrated = false;
+    if let Some(legacy) = object.remove("selected_language") {
+        migrated = true;
+        if !object.contains_key("allowed_languages") {
+            let languages = match legacy.as_str() {
+                Some(code) if !code.is_empty() && code != "auto" => vec![code.to_string()],
+                _ => Vec::new(),
+            };
+            object.insert(
+                "allowed_languages".to_string(),
+                serde_json::to_value(languages).unwrap(),
+            );
+        }
+    }
+
+    // `translate_to_english` became `translation_target_language`
+    if let Some(legacy) = object.remove("translate_to_english") {
+        migrated = true;
+        if legacy.as_bool() == Some(true) && !object.contains_key("translation_target_language") {
+            object.insert(
+                "translation_target_language".to_string(),
+                serde_json::Value::String("en".to_string()),
+            );
+        }
+    }
+
+    // The edit-selection shortcut arrived after the first release. It is
+    // added without keys so existing users don't lose a combination another
+    // app uses; they can record one in settings.
+    if let Some(bindings) = object
+        .get_mut("bindings")
+        .and_then(|bindings| bindings.as_object_mut())
+    {
+        if !bindings.contains_key(EDIT_SELECTION_BINDING_ID) {
+            migrated = true;
+            let binding = ShortcutBinding {
+                current_binding: String::new(),
+                ..default_edit_selection_binding()
+            };
+            bindings.insert(
+                EDIT_SELECTION_BINDING_ID.to_string(),
+                serde_json::to_value(binding).unwrap(),
+            );
+        }
+    }
+
+    // The OpenCC-or-LLM branch became steps of `post_process_pipeline`
+    if !object.contains_key("post_process_pipeline") {
+        migrated = true;
+        let llm_enabled = object
+            .get("post_process_enabled")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        let steps: Vec<PipelineStep> = default_post_process_pipeline()
+            .into_iter()
+            .map(|step| PipelineStep {
+                enabled: match step.kind {
+                    PipelineStepKind::LlmPrompt => llm_enabled,
+                    _ => step.enabled,
+                },
+                ..step
+            })
+            .collect();
+        object.insert(
+            "post_process_pipeline".to_string(),
+            serde_json::to_value(steps).unwrap(),
+        );
+    }
+
+    migrated
+}
+
 pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
     // Initialize store
     let store = app
         .store(SETTINGS_STORE_PATH)
         .expect("Failed to initialize store");
 
-    let settings = if let Some(settings_value) = store.get("settings") {
+    let settings = if let Some(mut settings_value) = store.get("settings") {
+        if migrate_settings_value(&mut settings_value) {
+            debug!("Migrated legacy settings fields");
+            store.set("settings", settings_value.clone());
+        }
+
         // Parse the entire settings object
         match serde_json::from_value::<AppSettings>(settings_value) {
             Ok(settings) => {
@@ -538,7 +1181,10 @@ pub fn get_settings(app: &AppHandle) -> AppSettings {
         .store(SETTINGS_STORE_PATH)
         .expect("Failed to initialize store");
 
-    if let Some(settings_value) = store.get("settings") {
+    if let Some(mut settings_value) = store.get("settings") {
+        if migrate_settings_value(&mut settings_value) {
+            store.set("settings", settings_value.clone());
+        }
         serde_json::from_value::<AppSettings>(settings_value).unwrap_or_else(|_| {
             let default_settings = get_default_settings();
             store.set("settings", serde_json::to_value(&default_settings).unwrap());
@@ -582,3 +1228,72 @@ pub fn get_recording_retention_period(app: &AppHandle) -> RecordingRetentionPeri
     let settings = get_settings(app);
     settings.recording_retention_period
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_selection_binding_migrates_unbound() {
+        let mut value = serde_json::json!({ "bindings": {} });
+        assert!(migrate_settings_value(&mut value));
+        let binding = &value["bindings"][EDIT_SELECTION_BINDING_ID];
+        assert_eq!(binding["current_binding"], "");
+        assert_ne!(binding["default_binding"], "");
+    }
+
+    fn settings_with_binding_profile() -> AppSettings {
+        let mut settings = get_default_settings();
+        settings.custom_words = vec!["Handy".to_string()];
+        settings.paste_method = PasteMethod::CtrlV;
+        settings.bindings.get_mut("transcribe").unwrap().profile = ActionProfile {
+            language: Some("de".to_string()),
+            paste_method: Some(PasteMethod::Direct),
+            ..Default::default()
+        };
+        settings
+    }
+
+    #[test]
+    fn app_profile_overrides_the_binding_profile_where_set() {
+        let app_profile = AppProfile {
+            name: "Terminal".to_string(),
+            apps: vec!["gnome-terminal".to_string()],
+            profile: ActionProfile {
+                language: Some("fr".to_string()),
+                post_process_prompt_id: Some(String::new()),
+                ..Default::default()
+            },
+            custom_words: Some(vec!["kubectl".to_string()]),
+        };
+
+        let settings = settings_with_binding_profile()
+            .for_binding("transcribe")
+            .with_app_profile(&app_profile);
+
+        assert_eq!(settings.allowed_languages, vec!["fr".to_string()]);
+        assert_eq!(settings.paste_method, PasteMethod::Direct);
+        assert_eq!(settings.custom_words, vec!["kubectl".to_string()]);
+        assert!(!settings
+            .pipeline()
+            .iter()
+            .any(|step| step.kind == PipelineStepKind::LlmPrompt && step.enabled));
+    }
+
+    #[test]
+    fn empty_app_profile_follows_the_binding_profile() {
+        let app_profile = AppProfile {
+            name: "Editor".to_string(),
+            apps: vec!["Code".to_string()],
+            ..Default::default()
+        };
+
+        let settings = settings_with_binding_profile()
+            .for_binding("transcribe")
+            .with_app_profile(&app_profile);
+
+        assert_eq!(settings.allowed_languages, vec!["de".to_string()]);
+        assert_eq!(settings.paste_method, PasteMethod::Direct);
+        assert_eq!(settings.custom_words, vec!["Handy".to_string()]);
+    }
+}
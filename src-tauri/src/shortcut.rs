@@ -4,14 +4,62 @@ use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_autostart::ManagerExt;
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
-use crate::actions::ACTION_MAP;
+use crate::actions::{action_for_binding, CUSTOM_TRANSCRIBE_PREFIX};
+use crate::http::{self, Operation};
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::download::DownloadManager;
+use crate::managers::transcription_queue::TranscriptionQueue;
+use crate::managers::usage::UsageManager;
 use crate::settings::ShortcutBinding;
 use crate::settings::{
-    self, get_settings, ClipboardHandling, LLMPrompt, OverlayPosition, PasteMethod,
-    SoundTheme, TranscriptionProvider, UsageMode,
+    self, get_settings, ActionProfile, AppProfile, ClipboardHandling, FallbackBackend, LLMPrompt,
+    OpenAiResponseFormat, OverlayPosition, PasteMethod, PipelineStep, PipelineStepKind,
+    RemoteAudioFormat, ReplacementRule, SoundTheme, TranscriptionProvider, UsageMode, UsagePrice,
 };
 use crate::secure_store;
 use crate::ManagedToggleState;
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+/// Bindings whose keys are held down right now
+static HELD_SHORTCUTS: Lazy<(Mutex<HashSet<String>>, Condvar)> =
+    Lazy::new(|| (Mutex::new(HashSet::new()), Condvar::new()));
+
+/// Time for modifiers to come up after the shortcut's main key
+const MODIFIER_RELEASE_GRACE: Duration = Duration::from_millis(150);
+
+fn set_shortcut_held(binding_id: &str, held: bool) {
+    let (held_shortcuts, released) = &*HELD_SHORTCUTS;
+    let mut held_shortcuts = held_shortcuts.lock().unwrap();
+    if held {
+        held_shortcuts.insert(binding_id.to_string());
+    } else {
+        held_shortcuts.remove(binding_id);
+        released.notify_all();
+    }
+}
+
+/// Blocks until no shortcut is held, so simulated key presses aren't
+/// combined with the user's modifiers (Ctrl+Shift+C opens devtools in
+/// browsers). Returns false if they were still held after `timeout`.
+pub fn wait_for_shortcuts_released(timeout: Duration) -> bool {
+    let (held_shortcuts, released) = &*HELD_SHORTCUTS;
+    let held_shortcuts = held_shortcuts.lock().unwrap();
+    let was_held = !held_shortcuts.is_empty();
+    let (held_shortcuts, result) = released
+        .wait_timeout_while(held_shortcuts, timeout, |held| !held.is_empty())
+        .unwrap();
+    drop(held_shortcuts);
+    if result.timed_out() {
+        return false;
+    }
+    if was_held {
+        std::thread::sleep(MODIFIER_RELEASE_GRACE);
+    }
+    true
+}
 
 pub fn init_shortcuts(app: &AppHandle) {
     let settings = settings::load_or_create_app_settings(app);
@@ -148,17 +196,139 @@ pub fn change_sound_theme_setting(app: AppHandle, theme: String) -> Result<(), S
 }
 
 #[tauri::command]
-pub fn change_translate_to_english_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+pub fn change_translation_target_language_setting(
+    app: AppHandle,
+    language: String,
+) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    settings.translate_to_english = enabled;
+    settings.translation_target_language = language.trim().to_string();
     settings::write_settings(&app, settings);
     Ok(())
 }
 
 #[tauri::command]
-pub fn change_selected_language_setting(app: AppHandle, language: String) -> Result<(), String> {
+pub fn change_translation_keep_source_text_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.translation_keep_source_text = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Replaces the action profile of one shortcut.
+/// Checks that the prompt a profile runs, if any, exists.
+fn validate_profile_prompt(
+    settings: &settings::AppSettings,
+    profile: &ActionProfile,
+) -> Result<(), String> {
+    if let Some(prompt_id) = profile
+        .post_process_prompt_id
+        .as_deref()
+        .filter(|prompt_id| !prompt_id.is_empty())
+    {
+        if !settings
+            .post_process_prompts
+            .iter()
+            .any(|prompt| prompt.id == prompt_id)
+        {
+            return Err(format!("Prompt with id '{}' not found", prompt_id));
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_binding_profile(
+    app: AppHandle,
+    id: String,
+    profile: ActionProfile,
+) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    settings.selected_language = language;
+    validate_profile_prompt(&settings, &profile)?;
+    let binding = settings
+        .bindings
+        .get_mut(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    binding.profile = ActionProfile {
+        model: profile.model.map(|model| model.trim().to_string()),
+        language: profile.language.map(|language| language.trim().to_string()),
+        translation_target_language: profile
+            .translation_target_language
+            .map(|language| language.trim().to_string()),
+        ..profile
+    };
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Adds a transcription shortcut with no keys assigned yet, for the user to
+/// record one and set up its profile.
+#[tauri::command]
+pub fn add_binding(app: AppHandle, name: String) -> Result<ShortcutBinding, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Shortcut name cannot be empty".to_string());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    let id = (1..)
+        .map(|n| format!("{}{}", CUSTOM_TRANSCRIBE_PREFIX, n))
+        .find(|id| !settings.bindings.contains_key(id))
+        .unwrap();
+    let binding = ShortcutBinding {
+        id: id.clone(),
+        name: name.to_string(),
+        description: "Converts your speech into text with its own profile.".to_string(),
+        default_binding: String::new(),
+        current_binding: String::new(),
+        profile: ActionProfile::default(),
+    };
+    settings.bindings.insert(id, binding.clone());
+    settings::write_settings(&app, settings);
+    Ok(binding)
+}
+
+/// Removes a shortcut added with `add_binding`. Built-in shortcuts stay.
+#[tauri::command]
+pub fn remove_binding(app: AppHandle, id: String) -> Result<(), String> {
+    if !id.starts_with(CUSTOM_TRANSCRIBE_PREFIX) {
+        return Err(format!("Binding '{}' cannot be removed", id));
+    }
+
+    let mut settings = settings::get_settings(&app);
+    let binding = settings
+        .bindings
+        .remove(&id)
+        .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+    if let Err(e) = _unregister_shortcut(&app, binding) {
+        warn!("remove_binding: {}", e);
+    }
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_allowed_languages_setting(
+    app: AppHandle,
+    languages: Vec<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    // "auto" is represented by an empty set; drop it along with blanks and duplicates
+    let mut allowed: Vec<String> = Vec::new();
+    for language in languages {
+        let trimmed = language.trim();
+        if trimmed.is_empty() || trimmed == "auto" {
+            continue;
+        }
+        if !allowed.iter().any(|existing| existing == trimmed) {
+            allowed.push(trimmed.to_string());
+        }
+    }
+
+    settings.allowed_languages = allowed;
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -267,6 +437,111 @@ pub fn change_transcription_provider(app: AppHandle, provider: String) -> Result
     Ok(())
 }
 
+#[tauri::command]
+pub fn change_transcription_fallbacks_setting(
+    app: AppHandle,
+    fallbacks: Vec<FallbackBackend>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.transcription_fallbacks = fallbacks;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_remote_transcription_timeout_setting(
+    app: AppHandle,
+    seconds: u64,
+) -> Result<(), String> {
+    if seconds == 0 {
+        return Err("Timeout must be at least one second".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.remote_transcription_timeout_secs = seconds;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_usage_prices_setting(app: AppHandle, prices: Vec<UsagePrice>) -> Result<(), String> {
+    let invalid = prices.iter().any(|price| {
+        [
+            price.per_audio_minute,
+            price.per_million_prompt_tokens,
+            price.per_million_completion_tokens,
+        ]
+        .iter()
+        .any(|value| !value.is_finite() || *value < 0.0)
+    });
+    if invalid {
+        return Err("Prices must be zero or more".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.usage_prices = prices;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<UsageManager>>().prices_changed();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_monthly_budget_setting(app: AppHandle, budget: f64) -> Result<(), String> {
+    if !budget.is_finite() || budget < 0.0 {
+        return Err("Budget must be zero or more".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.monthly_budget_usd = budget;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_diarization_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.diarization_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_diarization_max_speakers_setting(app: AppHandle, count: u32) -> Result<(), String> {
+    if count > 10 {
+        return Err("At most 10 speakers are supported".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.diarization_max_speakers = count;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_speaker_labels_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.speaker_labels = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_remote_audio_format_setting(app: AppHandle, format: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match format.as_str() {
+        "auto" => RemoteAudioFormat::Auto,
+        "wav" => RemoteAudioFormat::Wav,
+        "flac" => RemoteAudioFormat::Flac,
+        "opus" => RemoteAudioFormat::Opus,
+        other => {
+            warn!(
+                "Invalid remote audio format '{}', defaulting to auto",
+                other
+            );
+            RemoteAudioFormat::Auto
+        }
+    };
+    settings.remote_audio_format = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 pub fn change_deepgram_model(app: AppHandle, model: String) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
@@ -275,16 +550,175 @@ pub fn change_deepgram_model(app: AppHandle, model: String) -> Result<(), String
     Ok(())
 }
 
+#[tauri::command]
+pub fn change_deepgram_smart_format_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.deepgram_smart_format = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_deepgram_punctuate_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.deepgram_punctuate = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_deepgram_numerals_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.deepgram_numerals = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_deepgram_filler_words_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.deepgram_filler_words = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_deepgram_streaming_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.deepgram_streaming = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_openai_transcription_base_url(
+    app: AppHandle,
+    base_url: String,
+) -> Result<(), String> {
+    let trimmed = base_url.trim().trim_end_matches('/');
+    if !trimmed.starts_with("http://") && !trimmed.starts_with("https://") {
+        return Err("Base URL must start with http:// or https://".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.openai_transcription_base_url = trimmed.to_string();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_openai_transcription_model(app: AppHandle, model: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.openai_transcription_model = model.trim().to_string();
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_openai_transcription_language(
+    app: AppHandle,
+    language: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.openai_transcription_language = language
+        .map(|language| language.trim().to_string())
+        .filter(|language| !language.is_empty());
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_openai_transcription_prompt(app: AppHandle, prompt: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.openai_transcription_prompt = prompt;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_openai_transcription_response_format(
+    app: AppHandle,
+    format: String,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match format.as_str() {
+        "json" => OpenAiResponseFormat::Json,
+        "text" => OpenAiResponseFormat::Text,
+        "verbose_json" => OpenAiResponseFormat::VerboseJson,
+        other => {
+            warn!("Invalid response format '{}', defaulting to json", other);
+            OpenAiResponseFormat::Json
+        }
+    };
+    settings.openai_transcription_response_format = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Saves the key sent to OpenAI-compatible transcription servers. There is
+/// no common validation endpoint across servers, so the key is stored as is.
+#[tauri::command]
+pub fn store_openai_api_key(app: AppHandle, api_key: String) -> Result<String, String> {
+    let mut settings = settings::get_settings(&app);
+    let trimmed = api_key.trim();
+    if trimmed.is_empty() {
+        return Err("API key cannot be empty".to_string());
+    }
+
+    let preview: String = trimmed.chars().take(6).collect();
+    settings.openai_api_key_preview = format!("{}***", preview);
+
+    if settings.use_secure_key_storage {
+        secure_store::store_api_key("openai", trimmed)
+            .map_err(|e| format!("Couldn't save the OpenAI key to secure storage: {}", e))?;
+        settings.openai_api_key = None;
+    } else {
+        settings.openai_api_key = Some(trimmed.to_string());
+    }
+    settings::write_settings(&app, settings);
+
+    Ok(preview)
+}
+
 #[tauri::command]
 pub fn set_secure_key_storage(app: AppHandle, enabled: bool) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    settings.use_secure_key_storage = enabled;
+    if settings.use_secure_key_storage == enabled {
+        return Ok(());
+    }
 
-    // If switching to secure storage, clear plaintext copy.
+    let keys = [
+        ("deepgram", &mut settings.deepgram_api_key),
+        ("openai", &mut settings.openai_api_key),
+    ];
     if enabled {
-        settings.deepgram_api_key = None;
+        // Move the plaintext keys into the keyring; nothing changes unless
+        // every key made it
+        for (provider, key) in &keys {
+            if let Some(key) = key.as_deref().filter(|key| !key.trim().is_empty()) {
+                secure_store::store_api_key(provider, key).map_err(|e| {
+                    format!(
+                        "Couldn't move the {} key to secure storage: {}",
+                        provider, e
+                    )
+                })?;
+            }
+        }
+        for (_, key) in keys {
+            *key = None;
+        }
+    } else {
+        // Bring the keys back into the settings file before leaving the keyring
+        for (provider, key) in keys {
+            if let Some(stored) = secure_store::fetch_api_key(provider) {
+                *key = Some(stored);
+                if let Err(e) = secure_store::delete_api_key(provider) {
+                    warn!("Failed to remove {} key from keyring: {}", provider, e);
+                }
+            }
+        }
     }
 
+    settings.use_secure_key_storage = enabled;
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -339,13 +773,13 @@ pub async fn validate_and_store_deepgram_key(
 
     let validation_result = if base_url.is_empty() {
         // Validate directly against Deepgram API
-        let client = reqwest::Client::new();
-        let response = client
-            .get("https://api.deepgram.com/v1/projects")
-            .header("Authorization", format!("Token {}", trimmed))
-            .send()
-            .await
-            .map_err(|e| format!("Validation request failed: {}", e))?;
+        let response = http::send(Operation::VALIDATION, || {
+            http::client()
+                .get("https://api.deepgram.com/v1/projects")
+                .header("Authorization", format!("Token {}", trimmed))
+        })
+        .await
+        .map_err(|e| format!("Validation request failed: {}", e))?;
 
         let status = response.status();
         let body = response
@@ -363,13 +797,13 @@ pub async fn validate_and_store_deepgram_key(
         }
     } else {
         let endpoint = format!("{}/api/validate/deepgram", base_url);
-        let client = reqwest::Client::new();
-        let response = client
-            .post(endpoint)
-            .json(&serde_json::json!({ "apiKey": trimmed }))
-            .send()
-            .await
-            .map_err(|e| format!("Validation request failed: {}", e))?;
+        let response = http::send(Operation::VALIDATION, || {
+            http::client()
+                .post(&endpoint)
+                .json(&serde_json::json!({ "apiKey": trimmed }))
+        })
+        .await
+        .map_err(|e| format!("Validation request failed: {}", e))?;
 
         let status = response.status();
         let parsed: serde_json::Value = response
@@ -401,9 +835,8 @@ pub async fn validate_and_store_deepgram_key(
     settings.deepgram_api_key_preview = format!("{}***", preview);
 
     if settings.use_secure_key_storage {
-        if let Err(e) = secure_store::store_api_key("deepgram", trimmed) {
-            warn!("Failed to store Deepgram key in keyring: {}", e);
-        }
+        secure_store::store_api_key("deepgram", trimmed)
+            .map_err(|e| format!("Couldn't save the Deepgram key to secure storage: {}", e))?;
         settings.deepgram_api_key = None;
     } else {
         settings.deepgram_api_key = Some(trimmed.to_string());
@@ -432,6 +865,157 @@ pub fn change_word_correction_threshold_setting(
     Ok(())
 }
 
+#[tauri::command]
+pub fn change_max_concurrent_downloads_setting(app: AppHandle, count: usize) -> Result<(), String> {
+    if count == 0 {
+        return Err("At least one download must be allowed".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.max_concurrent_downloads = count;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<DownloadManager>>().apply_settings();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_download_bandwidth_limit_setting(app: AppHandle, kbps: u64) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.download_bandwidth_limit_kbps = kbps;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<DownloadManager>>().apply_settings();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_post_process_pipeline_setting(
+    app: AppHandle,
+    steps: Vec<PipelineStep>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.set_pipeline(steps);
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_replacement_rules_setting(
+    app: AppHandle,
+    rules: Vec<ReplacementRule>,
+) -> Result<(), String> {
+    for rule in &rules {
+        if rule.pattern.is_empty() {
+            return Err("Replacement patterns can't be empty".to_string());
+        }
+        crate::post_process::compile_rule(rule)
+            .map_err(|e| format!("Invalid pattern '{}': {}", rule.pattern, e))?;
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.replacement_rules = rules;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_app_profiles_setting(
+    app: AppHandle,
+    profiles: Vec<AppProfile>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let mut cleaned = Vec::with_capacity(profiles.len());
+    for profile in profiles {
+        if profile.name.trim().is_empty() {
+            return Err("App profiles need a name".to_string());
+        }
+        validate_profile_prompt(&settings, &profile.profile)?;
+        cleaned.push(AppProfile {
+            name: profile.name.trim().to_string(),
+            apps: profile
+                .apps
+                .iter()
+                .map(|app| app.trim().to_string())
+                .filter(|app| !app.is_empty())
+                .collect(),
+            custom_words: profile.custom_words.map(|words| {
+                words
+                    .iter()
+                    .map(|word| word.trim().to_string())
+                    .filter(|word| !word.is_empty())
+                    .collect()
+            }),
+            ..profile
+        });
+    }
+    settings.app_profiles = cleaned;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_min_speech_ratio_setting(app: AppHandle, ratio: f32) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&ratio) {
+        return Err("Speech ratio must be between 0 and 1".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.min_speech_ratio = ratio;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_max_parallel_transcriptions_setting(
+    app: AppHandle,
+    count: usize,
+) -> Result<(), String> {
+    if count == 0 {
+        return Err("At least one transcription must be allowed to run".to_string());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.max_parallel_transcriptions = count;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<TranscriptionQueue>>().apply_settings();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_preload_model_on_startup_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.preload_model_on_startup = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_preload_model_on_speech_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.preload_model_on_speech = enabled;
+    settings::write_settings(&app, settings);
+    app.state::<Arc<AudioRecordingManager>>()
+        .apply_speech_preload();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_warm_up_model_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.warm_up_model = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_low_memory_unload_threshold_setting(
+    app: AppHandle,
+    threshold_mb: u64,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.low_memory_unload_threshold_mb = threshold_mb;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
@@ -472,7 +1056,7 @@ pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Re
 #[tauri::command]
 pub fn change_post_process_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
-    settings.post_process_enabled = enabled;
+    settings.set_pipeline_step_enabled(PipelineStepKind::LlmPrompt, enabled);
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -628,6 +1212,21 @@ pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), Stri
     Ok(())
 }
 
+#[tauri::command]
+pub fn change_edit_selection_prompt_setting(app: AppHandle, prompt: String) -> Result<(), String> {
+    if prompt.trim().is_empty() {
+        return Err("The edit prompt cannot be empty".to_string());
+    }
+    if !prompt.contains("${selection}") {
+        return Err("The edit prompt must include ${selection}".to_string());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.edit_selection_prompt = prompt;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn fetch_post_process_models(
     app: AppHandle,
@@ -713,15 +1312,13 @@ async fn fetch_models_manual(
         );
     }
 
-    let http_client = reqwest::Client::builder()
+    let http_client = http::client_builder()
         .default_headers(headers)
         .build()
         .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
 
     // Make the request
-    let response = http_client
-        .get(&endpoint)
-        .send()
+    let response = http::send(Operation::MODEL_LIST, || http_client.get(&endpoint))
         .await
         .map_err(|e| format!("Failed to fetch models: {}", e))?;
 
@@ -834,6 +1431,11 @@ pub fn resume_binding(app: AppHandle, id: String) -> Result<(), String> {
 }
 
 fn _register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
+    // Added shortcuts have no keys until the user records some
+    if binding.current_binding.is_empty() {
+        return Ok(());
+    }
+
     // Validate human-level rules first
     if let Err(e) = validate_shortcut_string(&binding.current_binding) {
         warn!(
@@ -869,10 +1471,11 @@ fn _register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), S
     app.global_shortcut()
         .on_shortcut(shortcut, move |ah, scut, event| {
             if scut == &shortcut {
+                set_shortcut_held(&binding_id_for_closure, event.state == ShortcutState::Pressed);
                 let shortcut_string = scut.into_string();
                 let settings = get_settings(ah);
 
-                if let Some(action) = ACTION_MAP.get(&binding_id_for_closure) {
+                if let Some(action) = action_for_binding(&binding_id_for_closure) {
                     if settings.push_to_talk {
                         if event.state == ShortcutState::Pressed {
                             action.start(ah, &binding_id_for_closure, &shortcut_string);
@@ -920,6 +1523,10 @@ fn _register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), S
 }
 
 fn _unregister_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
+    if binding.current_binding.is_empty() {
+        return Ok(());
+    }
+
     let shortcut = match binding.current_binding.parse::<Shortcut>() {
         Ok(s) => s,
         Err(e) => {
@@ -1,4 +1,5 @@
-use crate::actions::ACTION_MAP;
+use crate::actions::{action_for_binding, cancel_live_transcriptions};
+use crate::cancellation::InFlightOperations;
 use crate::managers::audio::AudioRecordingManager;
 use crate::ManagedToggleState;
 use log::{info, warn};
@@ -32,7 +33,7 @@ pub fn cancel_current_operation(app: &AppHandle) {
             info!("Stopping active action for binding: {}", binding_id);
 
             // Call the action's stop method to ensure proper cleanup
-            if let Some(action) = ACTION_MAP.get(&binding_id) {
+            if let Some(action) = action_for_binding(&binding_id) {
                 action.stop(app, &binding_id, "cancelled");
             }
 
@@ -48,8 +49,16 @@ pub fn cancel_current_operation(app: &AppHandle) {
     // Cancel any ongoing recording
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
+    cancel_live_transcriptions();
 
-    // Update tray icon and menu to idle state
+    // Abort transcriptions and post-processing already in flight so nothing gets pasted
+    let cancelled = app.state::<InFlightOperations>().cancel_all();
+    if cancelled > 0 {
+        info!("Cancelled {} in-flight transcription(s)", cancelled);
+    }
+
+    // Reset the overlay, tray icon and menu to idle state
+    hide_recording_overlay(app);
     change_tray_icon(app, crate::tray::TrayIconState::Idle);
 
     info!("Operation cancellation completed - returned to idle state");
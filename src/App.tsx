@@ -1,11 +1,12 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { useEffect, useState } from "react";
-import { Toaster } from "sonner";
+import { Toaster, toast } from "sonner";
 import "./App.css";
 import AccessibilityPermissions from "./components/AccessibilityPermissions";
 import Onboarding from "./components/onboarding";
 import { Sidebar, SECTIONS_CONFIG } from "./components/Sidebar";
-import { SidebarSection } from "./lib/types";
+import { BudgetWarningEvent, SidebarSection } from "./lib/types";
 import { useSettings } from "./hooks/useSettings";
 
 const renderContent = (
@@ -45,6 +46,25 @@ function App() {
     checkOnboardingStatus();
   }, []);
 
+  useEffect(() => {
+    const unlistenPromise = listen<BudgetWarningEvent>(
+      "usage-budget-warning",
+      ({ payload }) => {
+        const spend = `$${payload.month_cost.toFixed(2)}`;
+        const budget = `$${payload.monthly_budget.toFixed(2)}`;
+        const message = `Estimated cloud usage is ${spend} of your ${budget} monthly budget`;
+        if (payload.level === "exceeded") {
+          toast.error(message);
+        } else {
+          toast.warning(message);
+        }
+      },
+    );
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   // Handle keyboard shortcuts for debug mode toggle
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
@@ -9,6 +9,7 @@ import {
   DebugSettings,
   AboutSettings,
   PostProcessingSettings,
+  UsageSettings,
 } from "./settings";
 
 interface SectionConfig {
@@ -49,6 +50,12 @@ export const SECTIONS_CONFIG: Record<string, SectionConfig> = {
     component: HistorySettings,
     enabled: () => true,
   },
+  usage: {
+    label: "Usage",
+    icon: "payments",
+    component: UsageSettings,
+    enabled: () => true,
+  },
   debug: {
     label: "Debug",
     icon: "bug_report",
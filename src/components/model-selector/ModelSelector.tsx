@@ -1,7 +1,11 @@
 import React, { useState, useRef, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { ModelInfo } from "../../lib/types";
+import {
+  DownloadProgress,
+  DownloadQueueProgress,
+  ModelInfo,
+} from "../../lib/types";
 import ModelStatusButton from "./ModelStatusButton";
 import ModelDropdown from "./ModelDropdown";
 import DownloadProgressDisplay from "./DownloadProgressDisplay";
@@ -11,13 +15,7 @@ interface ModelStateEvent {
   model_id?: string;
   model_name?: string;
   error?: string;
-}
-
-interface DownloadProgress {
-  model_id: string;
-  downloaded: number;
-  total: number;
-  percentage: number;
+  model_memory_bytes?: number | null;
 }
 
 type ModelStatus =
@@ -45,6 +43,9 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
   const [currentModelId, setCurrentModelId] = useState<string>("");
   const [modelStatus, setModelStatus] = useState<ModelStatus>("unloaded");
   const [modelError, setModelError] = useState<string | null>(null);
+  const [modelMemoryBytes, setModelMemoryBytes] = useState<number | null>(
+    null,
+  );
   const [modelDownloadProgress, setModelDownloadProgress] = useState<
     Map<string, DownloadProgress>
   >(new Map());
@@ -66,7 +67,8 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
     const modelStateUnlisten = listen<ModelStateEvent>(
       "model-state-changed",
       (event) => {
-        const { event_type, model_id, model_name, error } = event.payload;
+        const { event_type, model_id, model_name, error, model_memory_bytes } =
+          event.payload;
 
         switch (event_type) {
           case "loading_started":
@@ -76,6 +78,7 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
           case "loading_completed":
             setModelStatus("ready");
             setModelError(null);
+            setModelMemoryBytes(model_memory_bytes ?? null);
             if (model_id) setCurrentModelId(model_id);
             break;
           case "loading_failed":
@@ -85,66 +88,52 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
           case "unloaded":
             setModelStatus("unloaded");
             setModelError(null);
+            setModelMemoryBytes(null);
             break;
         }
       },
     );
 
-    // Listen for model download progress
-    const downloadProgressUnlisten = listen<DownloadProgress>(
+    // Listen for download queue progress; each event covers every queued download
+    const downloadProgressUnlisten = listen<DownloadQueueProgress>(
       "model-download-progress",
       (event) => {
-        const progress = event.payload;
-        setModelDownloadProgress((prev) => {
-          const newMap = new Map(prev);
-          newMap.set(progress.model_id, progress);
-          return newMap;
-        });
-        setModelStatus("downloading");
+        const { downloads } = event.payload;
+        setModelDownloadProgress(
+          new Map(downloads.map((progress) => [progress.model_id, progress])),
+        );
+        if (downloads.length > 0) {
+          setModelStatus("downloading");
+        }
 
-        // Update download stats for speed calculation
+        // Speed is measured by the backend, already smoothed
         const now = Date.now();
         setDownloadStats((prev) => {
-          const current = prev.get(progress.model_id);
-          const newStats = new Map(prev);
-
-          if (!current) {
-            // First progress update - initialize
+          const newStats = new Map<string, DownloadStats>();
+          for (const progress of downloads) {
             newStats.set(progress.model_id, {
-              startTime: now,
+              startTime: prev.get(progress.model_id)?.startTime ?? now,
               lastUpdate: now,
               totalDownloaded: progress.downloaded,
-              speed: 0,
+              speed: progress.bytes_per_sec / (1024 * 1024), // MB/s
             });
-          } else {
-            // Calculate speed over last few seconds
-            const timeDiff = (now - current.lastUpdate) / 1000; // seconds
-            const bytesDiff = progress.downloaded - current.totalDownloaded;
-
-            if (timeDiff > 0.5) {
-              // Update speed every 500ms
-              const currentSpeed = bytesDiff / (1024 * 1024) / timeDiff; // MB/s
-              // Smooth the speed with exponential moving average, but ensure positive values
-              const validCurrentSpeed = Math.max(0, currentSpeed);
-              const smoothedSpeed =
-                current.speed > 0
-                  ? current.speed * 0.8 + validCurrentSpeed * 0.2
-                  : validCurrentSpeed;
-
-              newStats.set(progress.model_id, {
-                startTime: current.startTime,
-                lastUpdate: now,
-                totalDownloaded: progress.downloaded,
-                speed: Math.max(0, smoothedSpeed),
-              });
-            }
           }
-
           return newStats;
         });
       },
     );
 
+    // Listen for downloads that failed on every mirror
+    const downloadFailedUnlisten = listen<{ model_id: string; error: string }>(
+      "model-download-failed",
+      (event) => {
+        const errorMsg = `Failed to download model: ${event.payload.error}`;
+        setModelError(errorMsg);
+        setModelStatus("error");
+        onError?.(errorMsg);
+      },
+    );
+
     // Listen for model download completion
     const downloadCompleteUnlisten = listen<string>(
       "model-download-complete",
@@ -229,6 +218,7 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
       document.removeEventListener("mousedown", handleClickOutside);
       modelStateUnlisten.then((fn) => fn());
       downloadProgressUnlisten.then((fn) => fn());
+      downloadFailedUnlisten.then((fn) => fn());
       downloadCompleteUnlisten.then((fn) => fn());
       extractionStartedUnlisten.then((fn) => fn());
       extractionCompletedUnlisten.then((fn) => fn());
@@ -314,6 +304,12 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
     if (modelDownloadProgress.size > 0) {
       if (modelDownloadProgress.size === 1) {
         const [progress] = Array.from(modelDownloadProgress.values());
+        if (progress.state === "paused") {
+          return "Download paused";
+        }
+        if (progress.state === "queued") {
+          return `Queued (#${progress.queue_position ?? 1})`;
+        }
         const percentage = Math.max(
           0,
           Math.min(100, Math.round(progress.percentage)),
@@ -359,6 +355,7 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
         <ModelStatusButton
           status={modelStatus}
           displayText={getModelDisplayText()}
+          memoryBytes={modelStatus === "ready" ? modelMemoryBytes : null}
           isDropdownOpen={showModelDropdown}
           onClick={() => setShowModelDropdown(!showModelDropdown)}
         />
@@ -12,6 +12,7 @@ type ModelStatus =
 interface ModelStatusButtonProps {
   status: ModelStatus;
   displayText: string;
+  memoryBytes?: number | null;
   isDropdownOpen: boolean;
   onClick: () => void;
   className?: string;
@@ -20,6 +21,7 @@ interface ModelStatusButtonProps {
 const ModelStatusButton: React.FC<ModelStatusButtonProps> = ({
   status,
   displayText,
+  memoryBytes,
   isDropdownOpen,
   onClick,
   className = "",
@@ -49,7 +51,11 @@ const ModelStatusButton: React.FC<ModelStatusButtonProps> = ({
     <button
       onClick={onClick}
       className={`flex items-center gap-2 hover:text-text/80 transition-colors ${className}`}
-      title={`Model status: ${displayText}`}
+      title={`Model status: ${displayText}${
+        memoryBytes
+          ? ` (${Math.round(memoryBytes / (1024 * 1024))} MB in memory)`
+          : ""
+      }`}
     >
       <div className={`w-2 h-2 rounded-full ${getStatusColor(status)}`} />
       <span className="max-w-28 truncate">{displayText}</span>
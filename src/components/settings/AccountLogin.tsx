@@ -0,0 +1,118 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { useSettings } from "../../hooks/useSettings";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Button } from "../ui/Button";
+import type { AccountStatus, DeviceLogin } from "../../lib/types";
+
+interface AccountLoginProps {
+  grouped?: boolean;
+}
+
+export const AccountLogin: React.FC<AccountLoginProps> = ({
+  grouped = false,
+}) => {
+  const { refreshSettings } = useSettings();
+  const [status, setStatus] = useState<AccountStatus | null>(null);
+  const [pendingLogin, setPendingLogin] = useState<DeviceLogin | null>(null);
+  const [starting, setStarting] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<AccountStatus>("refresh_account")
+      .then(setStatus)
+      .catch((e) => console.error("Failed to load account:", e));
+
+    const unlistenUpdated = listen<AccountStatus>(
+      "account-updated",
+      ({ payload }) => {
+        setStatus(payload);
+        setPendingLogin(null);
+        refreshSettings();
+      },
+    );
+    const unlistenFailed = listen<{ kind: string; message: string }>(
+      "account-login-failed",
+      ({ payload }) => {
+        setPendingLogin(null);
+        setError(payload.message);
+      },
+    );
+    return () => {
+      unlistenUpdated.then((unlisten) => unlisten());
+      unlistenFailed.then((unlisten) => unlisten());
+    };
+  }, [refreshSettings]);
+
+  const handleSignIn = async () => {
+    setError(null);
+    setStarting(true);
+    try {
+      setPendingLogin(await invoke<DeviceLogin>("start_account_login"));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setStarting(false);
+    }
+  };
+
+  const handleCancel = async () => {
+    await invoke("cancel_account_login");
+    setPendingLogin(null);
+  };
+
+  const handleSignOut = async () => {
+    await invoke("logout_account");
+  };
+
+  const signedIn = status?.signed_in ?? false;
+
+  return (
+    <SettingContainer
+      title="Account"
+      description="Sign in through your browser to transcribe with account credits. Tokens are kept in the system credential store."
+      descriptionMode="inline"
+      grouped={grouped}
+      layout="stacked"
+    >
+      {pendingLogin ? (
+        <div className="flex flex-col gap-2">
+          <p className="text-sm">
+            Confirm this code in the browser window that just opened:
+          </p>
+          <div className="flex items-center gap-3">
+            <span className="text-2xl font-mono tracking-widest">
+              {pendingLogin.user_code}
+            </span>
+            <Button size="sm" variant="secondary" onClick={handleCancel}>
+              Cancel
+            </Button>
+          </div>
+          <p className="text-xs text-mid-gray">
+            Didn't open? Visit {pendingLogin.verification_uri}
+          </p>
+        </div>
+      ) : signedIn ? (
+        <div className="flex flex-col sm:flex-row gap-3 sm:items-center">
+          <div className="text-sm">
+            <p>Signed in as {status?.email ?? status?.user_id ?? "—"}</p>
+            <p className="text-xs text-mid-gray">
+              {status?.minutes_remaining != null
+                ? `${status.minutes_remaining} minutes remaining`
+                : "Remaining minutes unknown"}
+            </p>
+          </div>
+          <Button size="sm" variant="secondary" onClick={handleSignOut}>
+            Sign out
+          </Button>
+        </div>
+      ) : (
+        <Button size="sm" onClick={handleSignIn} disabled={starting}>
+          {starting ? "Opening browser..." : "Sign in"}
+        </Button>
+      )}
+      {error && <p className="text-xs mt-2 text-red-500">{error}</p>}
+    </SettingContainer>
+  );
+};
@@ -0,0 +1,220 @@
+import React, { useEffect, useState } from "react";
+import { type as getOsType } from "@tauri-apps/plugin-os";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import {
+  FOLLOW_DEFAULT,
+  languageOptions,
+  orDefault,
+  pasteMethodOptions,
+} from "./ShortcutProfiles";
+import type { AppProfile, PasteMethod } from "../../lib/types";
+
+interface AppProfilesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const splitList = (value: string) =>
+  value
+    .split(",")
+    .map((item) => item.trim())
+    .filter((item) => item !== "");
+
+interface AppProfileEditorProps {
+  appProfile: AppProfile;
+  osType: string;
+  busy: boolean;
+  onChange: (appProfile: AppProfile) => void;
+  onRemove: () => void;
+  descriptionMode: "inline" | "tooltip";
+  grouped: boolean;
+}
+
+const AppProfileEditor: React.FC<AppProfileEditorProps> = ({
+  appProfile,
+  osType,
+  busy,
+  onChange,
+  onRemove,
+  descriptionMode,
+  grouped,
+}) => {
+  const { getSetting } = useSettings();
+  const profile = appProfile.profile ?? {};
+  const savedApps = appProfile.apps.join(", ");
+  const savedWords = appProfile.custom_words?.join(", ") ?? "";
+  const [apps, setApps] = useState(savedApps);
+  const [words, setWords] = useState(savedWords);
+
+  useEffect(() => {
+    setApps(savedApps);
+  }, [savedApps]);
+
+  useEffect(() => {
+    setWords(savedWords);
+  }, [savedWords]);
+
+  const prompts = getSetting("post_process_prompts") ?? [];
+  const promptOptions = [
+    { value: FOLLOW_DEFAULT, label: "Default prompt" },
+    { value: "", label: "No prompt" },
+    ...prompts.map((prompt) => ({ value: prompt.id, label: prompt.name })),
+  ];
+
+  const updateProfile = (patch: Partial<AppProfile["profile"]>) =>
+    onChange({ ...appProfile, profile: { ...profile, ...patch } });
+
+  return (
+    <SettingContainer
+      title={appProfile.name}
+      description="Used when recording starts in one of these apps. Anything left on default follows the shortcut."
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      layout="stacked"
+    >
+      <div className="grid grid-cols-2 gap-2">
+        <Input
+          type="text"
+          className="col-span-2"
+          value={apps}
+          onChange={(e) => setApps(e.target.value)}
+          onBlur={() => onChange({ ...appProfile, apps: splitList(apps) })}
+          placeholder="Apps, e.g. gnome-terminal, Code, Outlook"
+          variant="compact"
+          disabled={busy}
+        />
+        <Dropdown
+          options={languageOptions}
+          selectedValue={profile.language ?? FOLLOW_DEFAULT}
+          onSelect={(value) => updateProfile({ language: orDefault(value) })}
+          disabled={busy}
+        />
+        <Dropdown
+          options={promptOptions}
+          selectedValue={profile.post_process_prompt_id ?? FOLLOW_DEFAULT}
+          onSelect={(value) =>
+            updateProfile({ post_process_prompt_id: orDefault(value) })
+          }
+          disabled={busy}
+        />
+        <Dropdown
+          options={pasteMethodOptions(osType)}
+          selectedValue={profile.paste_method ?? FOLLOW_DEFAULT}
+          onSelect={(value) =>
+            updateProfile({
+              paste_method: orDefault(value) as PasteMethod | null,
+            })
+          }
+          disabled={busy}
+        />
+        <Input
+          type="text"
+          value={words}
+          onChange={(e) => setWords(e.target.value)}
+          onBlur={() =>
+            onChange({
+              ...appProfile,
+              custom_words: words.trim() ? splitList(words) : null,
+            })
+          }
+          placeholder="Default custom words"
+          variant="compact"
+          disabled={busy}
+        />
+      </div>
+      <div className="flex justify-end pt-2">
+        <Button onClick={onRemove} disabled={busy} variant="danger" size="sm">
+          Remove profile
+        </Button>
+      </div>
+    </SettingContainer>
+  );
+};
+
+export const AppProfiles: React.FC<AppProfilesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [name, setName] = useState("");
+    const [osType, setOsType] = useState<string>("unknown");
+
+    useEffect(() => {
+      setOsType(getOsType());
+    }, []);
+
+    const appProfiles: AppProfile[] = getSetting("app_profiles") ?? [];
+    const busy = isUpdating("app_profiles");
+
+    const handleAdd = () => {
+      if (!name.trim()) return;
+      updateSetting("app_profiles", [
+        ...appProfiles,
+        { name: name.trim(), apps: [], profile: {}, custom_words: null },
+      ]);
+      setName("");
+    };
+
+    const handleChange = (index: number, appProfile: AppProfile) => {
+      updateSetting(
+        "app_profiles",
+        appProfiles.map((existing, i) => (i === index ? appProfile : existing)),
+      );
+    };
+
+    const handleRemove = (index: number) => {
+      updateSetting("app_profiles", appProfiles.filter((_, i) => i !== index));
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title="Add App Profile"
+          description="Switch settings automatically for the app you are dictating into, e.g. Shift+Insert and no prompt in a terminal. Apps are matched by process name, or by window class on Linux (X11 only)."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <div className="flex items-center gap-2">
+            <Input
+              type="text"
+              className="max-w-40"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+              onKeyDown={(e) => {
+                if (e.key === "Enter") {
+                  e.preventDefault();
+                  handleAdd();
+                }
+              }}
+              placeholder="Profile name"
+              variant="compact"
+              disabled={busy}
+            />
+            <Button
+              onClick={handleAdd}
+              disabled={!name.trim() || busy}
+              variant="primary"
+              size="md"
+            >
+              Add
+            </Button>
+          </div>
+        </SettingContainer>
+        {appProfiles.map((appProfile, index) => (
+          <AppProfileEditor
+            key={`${index}-${appProfile.name}`}
+            appProfile={appProfile}
+            osType={osType}
+            busy={busy}
+            onChange={(updated) => handleChange(index, updated)}
+            onRemove={() => handleRemove(index)}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          />
+        ))}
+      </>
+    );
+  },
+);
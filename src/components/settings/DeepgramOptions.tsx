@@ -0,0 +1,64 @@
+import React from "react";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface DeepgramOptionsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const DeepgramOptions: React.FC<DeepgramOptionsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={getSetting("deepgram_streaming") ?? false}
+          onChange={(value) => updateSetting("deepgram_streaming", value)}
+          isUpdating={isUpdating("deepgram_streaming")}
+          label="Live Streaming"
+          description="Stream audio to Deepgram while you speak so text is ready as soon as you stop. Requires a saved Deepgram API key."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <ToggleSwitch
+          checked={getSetting("deepgram_smart_format") ?? true}
+          onChange={(value) => updateSetting("deepgram_smart_format", value)}
+          isUpdating={isUpdating("deepgram_smart_format")}
+          label="Smart Formatting"
+          description="Format dates, times, currency and similar entities so they read naturally."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <ToggleSwitch
+          checked={getSetting("deepgram_punctuate") ?? true}
+          onChange={(value) => updateSetting("deepgram_punctuate", value)}
+          isUpdating={isUpdating("deepgram_punctuate")}
+          label="Punctuation"
+          description="Add punctuation and capitalization to the transcript."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <ToggleSwitch
+          checked={getSetting("deepgram_numerals") ?? false}
+          onChange={(value) => updateSetting("deepgram_numerals", value)}
+          isUpdating={isUpdating("deepgram_numerals")}
+          label="Numerals"
+          description='Write numbers as digits, e.g. "9" instead of "nine".'
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <ToggleSwitch
+          checked={getSetting("deepgram_filler_words") ?? false}
+          onChange={(value) => updateSetting("deepgram_filler_words", value)}
+          isUpdating={isUpdating("deepgram_filler_words")}
+          label="Keep Filler Words"
+          description='Keep "um", "uh" and similar hesitations in the transcript.'
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+      </>
+    );
+  },
+);
@@ -0,0 +1,67 @@
+import React from "react";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface DiarizationProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const maxSpeakerOptions = [2, 3, 4, 5, 6, 8, 10].map((count) => ({
+  value: count.toString(),
+  label: `Up to ${count} speakers`,
+}));
+
+export const Diarization: React.FC<DiarizationProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("diarization_enabled") ?? false;
+    const maxSpeakers = getSetting("diarization_max_speakers") ?? 4;
+    const speakerLabels = getSetting("speaker_labels") ?? true;
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("diarization_enabled", value)}
+          isUpdating={isUpdating("diarization_enabled")}
+          label="Identify Speakers"
+          description="Split recordings with several people into speaker turns. Deepgram labels speakers itself; local models group voices on this device, which takes longer."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <>
+            <SettingContainer
+              title="Maximum Speakers"
+              description="The most distinct voices to separate in one recording on this device."
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Dropdown
+                options={maxSpeakerOptions}
+                selectedValue={maxSpeakers.toString()}
+                onSelect={(value) =>
+                  updateSetting("diarization_max_speakers", Number(value))
+                }
+                disabled={isUpdating("diarization_max_speakers")}
+              />
+            </SettingContainer>
+            <ToggleSwitch
+              checked={speakerLabels}
+              onChange={(value) => updateSetting("speaker_labels", value)}
+              isUpdating={isUpdating("speaker_labels")}
+              label="Speaker Labels"
+              description="Paste and export the text as 'Speaker 1:' turns when more than one speaker is heard."
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            />
+          </>
+        )}
+      </>
+    );
+  },
+);
@@ -267,29 +267,27 @@ export const HandyShortcut: React.FC<HandyShortcutProps> = ({
     );
   }
 
+  const entries = Object.entries(bindings);
+
   return (
     <SettingContainer
-      title="Handy Shortcut"
+      title={entries.length > 1 ? "Handy Shortcuts" : "Handy Shortcut"}
       description="Set the keyboard shortcut to start and stop speech-to-text recording"
       descriptionMode={descriptionMode}
       grouped={grouped}
       tooltipPosition="bottom"
     >
-      {(() => {
-        const primaryBinding = Object.values(bindings)[0];
-        const primaryId = Object.keys(bindings)[0];
-
-        if (!primaryBinding) {
-          return (
-            <div className="text-sm text-mid-gray">No shortcuts configured</div>
-          );
-        }
-
-        return (
-          <div className="flex items-center space-x-1">
-            {editingShortcutId === primaryId ? (
+      <div className="flex flex-col items-end gap-1">
+        {entries.map(([id, binding]) => (
+          <div key={id} className="flex items-center space-x-1">
+            {entries.length > 1 && (
+              <span className="text-xs text-mid-gray pr-1">
+                {binding.name}
+              </span>
+            )}
+            {editingShortcutId === id ? (
               <div
-                ref={(ref) => setShortcutRef(primaryId, ref)}
+                ref={(ref) => setShortcutRef(id, ref)}
                 className="px-2 py-1 text-sm font-semibold border border-logo-primary bg-logo-primary/30 rounded min-w-[120px] text-center"
               >
                 {formatCurrentKeys()}
@@ -297,18 +295,20 @@ export const HandyShortcut: React.FC<HandyShortcutProps> = ({
             ) : (
               <div
                 className="px-2 py-1 text-sm font-semibold bg-mid-gray/10 border border-mid-gray/80 hover:bg-logo-primary/10 rounded cursor-pointer hover:border-logo-primary"
-                onClick={() => startRecording(primaryId)}
+                onClick={() => startRecording(id)}
               >
-                {formatKeyCombination(primaryBinding.current_binding, osType)}
+                {binding.current_binding
+                  ? formatKeyCombination(binding.current_binding, osType)
+                  : "Not set"}
               </div>
             )}
             <ResetButton
-              onClick={() => resetBinding(primaryId)}
-              disabled={isUpdating(`binding_${primaryId}`)}
+              onClick={() => resetBinding(id)}
+              disabled={isUpdating(`binding_${id}`)}
             />
           </div>
-        );
-      })()}
+        ))}
+      </div>
     </SettingContainer>
   );
 };
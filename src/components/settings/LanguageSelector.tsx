@@ -24,7 +24,7 @@ export const LanguageSelector: React.FC<LanguageSelectorProps> = ({
   const dropdownRef = useRef<HTMLDivElement>(null);
   const searchInputRef = useRef<HTMLInputElement>(null);
 
-  const selectedLanguage = getSetting("selected_language") || "auto";
+  const allowedLanguages = getSetting("allowed_languages") || [];
   const isUnsupported = unsupportedModels.includes(currentModel);
 
   useEffect(() => {
@@ -69,23 +69,42 @@ export const LanguageSelector: React.FC<LanguageSelectorProps> = ({
     [searchQuery],
   );
 
-  const selectedLanguageName = isUnsupported
-    ? "Auto"
-    : LANGUAGES.find((lang) => lang.value === selectedLanguage)?.label ||
-      "Auto";
-
+  const selectedLanguageName =
+    isUnsupported || allowedLanguages.length === 0
+      ? "Auto"
+      : allowedLanguages
+          .map(
+            (code) =>
+              LANGUAGES.find((lang) => lang.value === code)?.label || code,
+          )
+          .join(", ");
+
+  const isLanguageSelected = (languageCode: string) =>
+    languageCode === "auto"
+      ? allowedLanguages.length === 0
+      : allowedLanguages.includes(languageCode);
+
+  // "Auto" clears the set; any other language toggles its membership
   const handleLanguageSelect = async (languageCode: string) => {
-    await updateSetting("selected_language", languageCode);
-    setIsOpen(false);
+    const nextLanguages =
+      languageCode === "auto"
+        ? []
+        : allowedLanguages.includes(languageCode)
+          ? allowedLanguages.filter((code) => code !== languageCode)
+          : [...allowedLanguages, languageCode];
+    await updateSetting("allowed_languages", nextLanguages);
+    if (languageCode === "auto") {
+      setIsOpen(false);
+    }
     setSearchQuery("");
   };
 
   const handleReset = async () => {
-    await resetSetting("selected_language");
+    await resetSetting("allowed_languages");
   };
 
   const handleToggle = () => {
-    if (isUpdating("selected_language") || isUnsupported) return;
+    if (isUpdating("allowed_languages") || isUnsupported) return;
     setIsOpen(!isOpen);
   };
 
@@ -109,7 +128,7 @@ export const LanguageSelector: React.FC<LanguageSelectorProps> = ({
       description={
         isUnsupported
           ? "Parakeet model automatically detects the language. No manual selection is needed."
-          : "Select the language for speech recognition. Auto will automatically determine the language, while selecting a specific language can improve accuracy for that language."
+          : "Select the languages you speak. Auto detects any language; picking one forces it, and picking several restricts detection to just those."
       }
       descriptionMode={descriptionMode}
       grouped={grouped}
@@ -120,12 +139,12 @@ export const LanguageSelector: React.FC<LanguageSelectorProps> = ({
           <button
             type="button"
             className={`px-2 py-1 text-sm font-semibold bg-mid-gray/10 border border-mid-gray/80 rounded min-w-[200px] text-left flex items-center justify-between transition-all duration-150 ${
-              isUpdating("selected_language") || isUnsupported
+              isUpdating("allowed_languages") || isUnsupported
                 ? "opacity-50 cursor-not-allowed"
                 : "hover:bg-logo-primary/10 cursor-pointer hover:border-logo-primary"
             }`}
             onClick={handleToggle}
-            disabled={isUpdating("selected_language") || isUnsupported}
+            disabled={isUpdating("allowed_languages") || isUnsupported}
           >
             <span className="truncate">{selectedLanguageName}</span>
             <svg
@@ -145,7 +164,7 @@ export const LanguageSelector: React.FC<LanguageSelectorProps> = ({
             </svg>
           </button>
 
-          {isOpen && !isUpdating("selected_language") && !isUnsupported && (
+          {isOpen && !isUpdating("allowed_languages") && !isUnsupported && (
             <div className="absolute top-full left-0 right-0 mt-1 bg-background border border-mid-gray/80 rounded shadow-lg z-50 max-h-60 overflow-hidden">
               {/* Search input */}
               <div className="p-2 border-b border-mid-gray/80">
@@ -171,7 +190,7 @@ export const LanguageSelector: React.FC<LanguageSelectorProps> = ({
                       key={language.value}
                       type="button"
                       className={`w-full px-2 py-1 text-sm text-left hover:bg-logo-primary/10 transition-colors duration-150 ${
-                        selectedLanguage === language.value
+                        isLanguageSelected(language.value)
                           ? "bg-logo-primary/20 text-logo-primary font-semibold"
                           : ""
                       }`}
@@ -189,10 +208,10 @@ export const LanguageSelector: React.FC<LanguageSelectorProps> = ({
         </div>
         <ResetButton
           onClick={handleReset}
-          disabled={isUpdating("selected_language") || isUnsupported}
+          disabled={isUpdating("allowed_languages") || isUnsupported}
         />
       </div>
-      {isUpdating("selected_language") && (
+      {isUpdating("allowed_languages") && (
         <div className="absolute inset-0 bg-mid-gray/10 rounded flex items-center justify-center">
           <div className="w-4 h-4 border-2 border-logo-primary border-t-transparent rounded-full animate-spin"></div>
         </div>
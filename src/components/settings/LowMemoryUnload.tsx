@@ -0,0 +1,46 @@
+import React from "react";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface LowMemoryUnloadProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+const thresholdOptions = [
+  { value: "0", label: "Never" },
+  { value: "512", label: "Below 512 MB free" },
+  { value: "1024", label: "Below 1 GB free" },
+  { value: "2048", label: "Below 2 GB free" },
+  { value: "4096", label: "Below 4 GB free" },
+];
+
+export const LowMemoryUnload: React.FC<LowMemoryUnloadProps> = ({
+  descriptionMode = "inline",
+  grouped = false,
+}) => {
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+
+  const currentValue = String(
+    getSetting("low_memory_unload_threshold_mb") ?? 0,
+  );
+
+  return (
+    <SettingContainer
+      title="Unload Model on Low Memory"
+      description="Free the model's memory when the system runs short of available memory. Only supported on Linux."
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+    >
+      <Dropdown
+        options={thresholdOptions}
+        selectedValue={currentValue}
+        onSelect={(value) =>
+          updateSetting("low_memory_unload_threshold_mb", Number(value))
+        }
+        disabled={isUpdating("low_memory_unload_threshold_mb")}
+      />
+    </SettingContainer>
+  );
+};
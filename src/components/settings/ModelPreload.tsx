@@ -0,0 +1,46 @@
+import React from "react";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface ModelPreloadProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ModelPreload: React.FC<ModelPreloadProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={getSetting("preload_model_on_startup") ?? true}
+          onChange={(value) => updateSetting("preload_model_on_startup", value)}
+          isUpdating={isUpdating("preload_model_on_startup")}
+          label="Load Model at Startup"
+          description="Load the selected model when Handy starts so the first dictation isn't delayed."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <ToggleSwitch
+          checked={getSetting("preload_model_on_speech") ?? true}
+          onChange={(value) => updateSetting("preload_model_on_speech", value)}
+          isUpdating={isUpdating("preload_model_on_speech")}
+          label="Load Model When Speech Is Heard"
+          description="With the microphone always on, start loading an unloaded model as soon as you begin speaking."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <ToggleSwitch
+          checked={getSetting("warm_up_model") ?? true}
+          onChange={(value) => updateSetting("warm_up_model", value)}
+          isUpdating={isUpdating("warm_up_model")}
+          label="Warm Up Model"
+          description="Run a short silent transcription after loading so one-off setup work doesn't slow down your first dictation."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+      </>
+    );
+  },
+);
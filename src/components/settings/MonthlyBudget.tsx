@@ -0,0 +1,56 @@
+import React, { useEffect, useState } from "react";
+import { useSettings } from "../../hooks/useSettings";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface MonthlyBudgetProps {
+  descriptionMode?: "tooltip" | "inline";
+  grouped?: boolean;
+}
+
+export const MonthlyBudget: React.FC<MonthlyBudgetProps> = ({
+  descriptionMode = "inline",
+  grouped = false,
+}) => {
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+
+  const budget = getSetting("monthly_budget_usd") ?? 0;
+  const [draft, setDraft] = useState(String(budget));
+
+  useEffect(() => {
+    setDraft(String(budget));
+  }, [budget]);
+
+  const save = () => {
+    const value = parseFloat(draft);
+    if (!isNaN(value) && value >= 0 && value !== budget) {
+      updateSetting("monthly_budget_usd", value);
+    } else {
+      setDraft(String(budget));
+    }
+  };
+
+  return (
+    <SettingContainer
+      title="Monthly Budget"
+      description="Warn when the estimated spend on cloud providers this month reaches 80% and 100% of this amount. Set to 0 to disable."
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      layout="horizontal"
+    >
+      <div className="flex items-center space-x-2">
+        <span className="text-sm text-text">$</span>
+        <Input
+          type="number"
+          min="0"
+          step="0.5"
+          value={draft}
+          onChange={(event) => setDraft(event.target.value)}
+          onBlur={save}
+          disabled={isUpdating("monthly_budget_usd")}
+          className="w-24"
+        />
+      </div>
+    </SettingContainer>
+  );
+};
@@ -0,0 +1,205 @@
+import React, { useEffect, useState } from "react";
+import { useSettings } from "../../hooks/useSettings";
+import { OpenAiResponseFormat } from "../../lib/types";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Input } from "../ui/Input";
+import { Button } from "../ui/Button";
+
+interface OpenAiTranscriptionSettingsProps {
+  grouped?: boolean;
+}
+
+const responseFormatOptions = [
+  { value: "json", label: "JSON" },
+  { value: "text", label: "Plain text" },
+  { value: "verbose_json", label: "Verbose JSON" },
+];
+
+export const OpenAiTranscriptionSettings: React.FC<
+  OpenAiTranscriptionSettingsProps
+> = ({ grouped = false }) => {
+  const { settings, updateSetting, isUpdating, saveOpenAiKey } = useSettings();
+
+  const baseUrl =
+    settings?.openai_transcription_base_url ?? "https://api.openai.com/v1";
+  const model = settings?.openai_transcription_model ?? "whisper-1";
+  const language = settings?.openai_transcription_language ?? "";
+  const prompt = settings?.openai_transcription_prompt ?? "";
+  const responseFormat =
+    settings?.openai_transcription_response_format ?? "json";
+
+  const [baseUrlInput, setBaseUrlInput] = useState(baseUrl);
+  const [modelInput, setModelInput] = useState(model);
+  const [languageInput, setLanguageInput] = useState(language);
+  const [promptInput, setPromptInput] = useState(prompt);
+  const [apiKeyInput, setApiKeyInput] = useState("");
+  const [keyError, setKeyError] = useState<string | null>(null);
+
+  useEffect(() => setBaseUrlInput(baseUrl), [baseUrl]);
+  useEffect(() => setModelInput(model), [model]);
+  useEffect(() => setLanguageInput(language), [language]);
+  useEffect(() => setPromptInput(prompt), [prompt]);
+
+  const handleSaveKey = async () => {
+    setKeyError(null);
+    try {
+      await saveOpenAiKey(apiKeyInput);
+      setApiKeyInput("");
+    } catch (error) {
+      setKeyError(
+        error instanceof Error ? error.message : "Failed to save the API key.",
+      );
+    }
+  };
+
+  return (
+    <>
+      <SettingContainer
+        title="OpenAI-compatible server"
+        description="Used when no API base URL is set. Works with OpenAI, Groq, or a self-hosted faster-whisper or whisper.cpp server."
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex flex-col sm:flex-row gap-2 sm:items-center w-full">
+          <Input
+            className="w-full sm:w-96"
+            placeholder="https://api.openai.com/v1"
+            value={baseUrlInput}
+            onChange={(e) => setBaseUrlInput(e.target.value)}
+            disabled={isUpdating("openai_transcription_base_url")}
+          />
+          <Button
+            size="sm"
+            variant="secondary"
+            onClick={() =>
+              updateSetting("openai_transcription_base_url", baseUrlInput)
+            }
+            disabled={
+              isUpdating("openai_transcription_base_url") ||
+              baseUrlInput.trim() === baseUrl
+            }
+          >
+            Save URL
+          </Button>
+        </div>
+      </SettingContainer>
+
+      <SettingContainer
+        title="OpenAI API key"
+        description="Sent as a bearer token. Leave unset for local servers that don't need one."
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex flex-col sm:flex-row gap-2 sm:items-center w-full">
+          <Input
+            className="w-full sm:w-80"
+            placeholder="sk-..."
+            value={apiKeyInput}
+            onChange={(e) => setApiKeyInput(e.target.value)}
+            disabled={isUpdating("openai_api_key_preview")}
+          />
+          <Button
+            size="sm"
+            onClick={handleSaveKey}
+            disabled={
+              isUpdating("openai_api_key_preview") ||
+              apiKeyInput.trim().length === 0
+            }
+          >
+            Save
+          </Button>
+        </div>
+        <p className="text-xs text-mid-gray mt-2">
+          Saved preview: {settings?.openai_api_key_preview || "Not set"}
+        </p>
+        {keyError && <p className="text-xs mt-1 text-red-500">{keyError}</p>}
+      </SettingContainer>
+
+      <SettingContainer
+        title="Transcription model"
+        description="Model name on the server, e.g. whisper-1, gpt-4o-transcribe or whisper-large-v3-turbo."
+        descriptionMode="inline"
+        grouped={grouped}
+      >
+        <Input
+          className="w-64"
+          placeholder="whisper-1"
+          value={modelInput}
+          onChange={(e) => setModelInput(e.target.value)}
+          onBlur={() => {
+            if (modelInput.trim() && modelInput.trim() !== model) {
+              updateSetting("openai_transcription_model", modelInput.trim());
+            }
+          }}
+          disabled={isUpdating("openai_transcription_model")}
+        />
+      </SettingContainer>
+
+      <SettingContainer
+        title="Language"
+        description="ISO 639-1 code such as en or de. Leave empty to use your language settings."
+        descriptionMode="inline"
+        grouped={grouped}
+      >
+        <Input
+          className="w-24"
+          placeholder="auto"
+          value={languageInput}
+          onChange={(e) => setLanguageInput(e.target.value)}
+          onBlur={() => {
+            if (languageInput.trim() !== language) {
+              updateSetting(
+                "openai_transcription_language",
+                languageInput.trim() || null,
+              );
+            }
+          }}
+          disabled={isUpdating("openai_transcription_language")}
+        />
+      </SettingContainer>
+
+      <SettingContainer
+        title="Prompt"
+        description="Optional text that guides spelling and style, such as names or jargon."
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <Input
+          className="w-full"
+          placeholder="Handy, Tauri, Parakeet"
+          value={promptInput}
+          onChange={(e) => setPromptInput(e.target.value)}
+          onBlur={() => {
+            if (promptInput !== prompt) {
+              updateSetting("openai_transcription_prompt", promptInput);
+            }
+          }}
+          disabled={isUpdating("openai_transcription_prompt")}
+        />
+      </SettingContainer>
+
+      <SettingContainer
+        title="Response format"
+        description="Plain text suits servers with limited JSON support."
+        descriptionMode="inline"
+        grouped={grouped}
+      >
+        <Dropdown
+          options={responseFormatOptions}
+          selectedValue={responseFormat}
+          onSelect={(value) =>
+            updateSetting(
+              "openai_transcription_response_format",
+              value as OpenAiResponseFormat,
+            )
+          }
+          disabled={isUpdating("openai_transcription_response_format")}
+        />
+      </SettingContainer>
+    </>
+  );
+};
@@ -0,0 +1,40 @@
+import React from "react";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import type { RemoteAudioFormat as AudioFormat } from "../../lib/types";
+
+interface RemoteAudioFormatProps {
+  grouped?: boolean;
+}
+
+const formatOptions = [
+  { value: "auto", label: "Automatic" },
+  { value: "wav", label: "WAV" },
+  { value: "flac", label: "FLAC" },
+  { value: "opus", label: "Opus" },
+];
+
+export const RemoteAudioFormat: React.FC<RemoteAudioFormatProps> = ({
+  grouped = false,
+}) => {
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+
+  return (
+    <SettingContainer
+      title="Upload format"
+      description="How recordings are compressed before upload. Automatic sends Opus to providers known to accept it and WAV to everything else; FLAC is lossless at about half the size of WAV."
+      descriptionMode="inline"
+      grouped={grouped}
+    >
+      <Dropdown
+        options={formatOptions}
+        selectedValue={getSetting("remote_audio_format") ?? "auto"}
+        onSelect={(value) =>
+          updateSetting("remote_audio_format", value as AudioFormat)
+        }
+        disabled={isUpdating("remote_audio_format")}
+      />
+    </SettingContainer>
+  );
+};
@@ -0,0 +1,140 @@
+import React, { useState } from "react";
+import { toast } from "sonner";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import type { ReplacementRule } from "../../lib/types";
+
+interface ReplacementRulesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const isValidPattern = (pattern: string) => {
+  try {
+    new RegExp(pattern);
+    return true;
+  } catch {
+    return false;
+  }
+};
+
+export const ReplacementRules: React.FC<ReplacementRulesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [pattern, setPattern] = useState("");
+    const [replacement, setReplacement] = useState("");
+    const [caseInsensitive, setCaseInsensitive] = useState(true);
+
+    const rules: ReplacementRule[] = getSetting("replacement_rules") ?? [];
+    const updating = isUpdating("replacement_rules");
+
+    const handleAdd = () => {
+      if (!pattern) return;
+      if (!isValidPattern(pattern)) {
+        toast.error(`"${pattern}" is not a valid regular expression`);
+        return;
+      }
+      updateSetting("replacement_rules", [
+        ...rules,
+        { pattern, replacement, case_insensitive: caseInsensitive },
+      ]);
+      setPattern("");
+      setReplacement("");
+    };
+
+    const handleRemove = (index: number) => {
+      updateSetting("replacement_rules", rules.filter((_, i) => i !== index));
+    };
+
+    const handleKeyDown = (e: React.KeyboardEvent) => {
+      if (e.key === "Enter") {
+        e.preventDefault();
+        handleAdd();
+      }
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title="Find and Replace"
+          description="Rewrite parts of every transcript with regular expressions. Rules run in order; use $1 in the replacement to insert a captured group."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          layout="stacked"
+        >
+          <div className="flex items-center gap-2">
+            <Input
+              type="text"
+              className="flex-1"
+              value={pattern}
+              onChange={(e) => setPattern(e.target.value)}
+              onKeyDown={handleKeyDown}
+              placeholder="Find (regex)"
+              variant="compact"
+              disabled={updating}
+            />
+            <Input
+              type="text"
+              className="flex-1"
+              value={replacement}
+              onChange={(e) => setReplacement(e.target.value)}
+              onKeyDown={handleKeyDown}
+              placeholder="Replace with"
+              variant="compact"
+              disabled={updating}
+            />
+            <label className="flex items-center gap-1 text-xs">
+              <input
+                type="checkbox"
+                checked={caseInsensitive}
+                onChange={(e) => setCaseInsensitive(e.target.checked)}
+                disabled={updating}
+              />
+              Ignore case
+            </label>
+            <Button
+              onClick={handleAdd}
+              disabled={!pattern || updating}
+              variant="primary"
+              size="md"
+            >
+              Add
+            </Button>
+          </div>
+        </SettingContainer>
+        {rules.length > 0 && (
+          <div
+            className={`px-4 p-2 ${grouped ? "" : "rounded-lg border border-mid-gray/20"} space-y-1`}
+          >
+            {rules.map((rule, index) => (
+              <div
+                key={`${index}-${rule.pattern}`}
+                className="flex items-center gap-2 text-sm"
+              >
+                <code className="flex-1 truncate">{rule.pattern}</code>
+                <span className="text-mid-gray">→</span>
+                <code className="flex-1 truncate">
+                  {rule.replacement || "(remove)"}
+                </code>
+                {rule.case_insensitive && (
+                  <span className="text-xs text-mid-gray">Aa</span>
+                )}
+                <Button
+                  onClick={() => handleRemove(index)}
+                  disabled={updating}
+                  variant="secondary"
+                  size="sm"
+                  aria-label={`Remove rule ${rule.pattern}`}
+                >
+                  Remove
+                </Button>
+              </div>
+            ))}
+          </div>
+        )}
+      </>
+    );
+  },
+);
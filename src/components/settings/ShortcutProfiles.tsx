@@ -0,0 +1,226 @@
+import React, { useEffect, useState } from "react";
+import { type as getOsType } from "@tauri-apps/plugin-os";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import { LANGUAGES } from "../../lib/constants/languages";
+import type {
+  ActionProfile,
+  PasteMethod,
+  ShortcutBinding,
+  TranscriptionProvider,
+} from "../../lib/types";
+
+interface ShortcutProfilesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+// Shortcuts added in the app; the built-in ones can't be removed
+const CUSTOM_BINDING_PREFIX = "transcribe_";
+export const FOLLOW_DEFAULT = "default";
+
+const providerOptions = [
+  { value: FOLLOW_DEFAULT, label: "Default provider" },
+  { value: "local", label: "Local" },
+  { value: "deepgram", label: "Deepgram" },
+  { value: "openai", label: "OpenAI-compatible" },
+];
+
+export const languageOptions = [
+  { value: FOLLOW_DEFAULT, label: "Default language" },
+  ...LANGUAGES,
+];
+
+const translationOptions = [
+  { value: FOLLOW_DEFAULT, label: "Default translation" },
+  { value: "", label: "Don't translate" },
+  ...LANGUAGES.filter((language) => language.value !== "auto"),
+];
+
+export const pasteMethodOptions = (osType: string) => [
+  { value: FOLLOW_DEFAULT, label: "Default paste method" },
+  { value: "ctrl_v", label: "Clipboard (Ctrl+V)" },
+  { value: "direct", label: "Direct" },
+  ...(osType === "windows" || osType === "linux"
+    ? [{ value: "shift_insert", label: "Clipboard (Shift+Insert)" }]
+    : []),
+];
+
+export const orDefault = (value: string) => (value === FOLLOW_DEFAULT ? null : value);
+
+interface BindingProfileProps {
+  binding: ShortcutBinding;
+  osType: string;
+  descriptionMode: "inline" | "tooltip";
+  grouped: boolean;
+}
+
+const BindingProfile: React.FC<BindingProfileProps> = ({
+  binding,
+  osType,
+  descriptionMode,
+  grouped,
+}) => {
+  const { getSetting, updateBindingProfile, removeBinding, isUpdating } =
+    useSettings();
+  const profile: ActionProfile = binding.profile ?? {};
+  const [model, setModel] = useState(profile.model ?? "");
+
+  useEffect(() => {
+    setModel(profile.model ?? "");
+  }, [profile.model]);
+
+  const prompts = getSetting("post_process_prompts") ?? [];
+  const promptOptions = [
+    { value: FOLLOW_DEFAULT, label: "Default prompt" },
+    { value: "", label: "No prompt" },
+    ...prompts.map((prompt) => ({ value: prompt.id, label: prompt.name })),
+  ];
+
+  const busy = isUpdating(`binding_profile_${binding.id}`);
+  const update = (patch: Partial<ActionProfile>) =>
+    updateBindingProfile(binding.id, { ...profile, ...patch });
+
+  return (
+    <SettingContainer
+      title={binding.name}
+      description="Overrides for recordings made with this shortcut. Anything left on default follows the settings above."
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+      layout="stacked"
+    >
+      <div className="grid grid-cols-2 gap-2">
+        <Dropdown
+          options={providerOptions}
+          selectedValue={profile.provider ?? FOLLOW_DEFAULT}
+          onSelect={(value) =>
+            update({
+              provider: orDefault(value) as TranscriptionProvider | null,
+            })
+          }
+          disabled={busy}
+        />
+        <Input
+          type="text"
+          value={model}
+          onChange={(e) => setModel(e.target.value)}
+          onBlur={() => update({ model: model.trim() || null })}
+          placeholder="Default model"
+          variant="compact"
+          disabled={busy}
+        />
+        <Dropdown
+          options={languageOptions}
+          selectedValue={profile.language ?? FOLLOW_DEFAULT}
+          onSelect={(value) => update({ language: orDefault(value) })}
+          disabled={busy}
+        />
+        <Dropdown
+          options={translationOptions}
+          selectedValue={profile.translation_target_language ?? FOLLOW_DEFAULT}
+          onSelect={(value) =>
+            update({ translation_target_language: orDefault(value) })
+          }
+          disabled={busy}
+        />
+        <Dropdown
+          options={promptOptions}
+          selectedValue={profile.post_process_prompt_id ?? FOLLOW_DEFAULT}
+          onSelect={(value) =>
+            update({ post_process_prompt_id: orDefault(value) })
+          }
+          disabled={busy}
+        />
+        <Dropdown
+          options={pasteMethodOptions(osType)}
+          selectedValue={profile.paste_method ?? FOLLOW_DEFAULT}
+          onSelect={(value) =>
+            update({ paste_method: orDefault(value) as PasteMethod | null })
+          }
+          disabled={busy}
+        />
+      </div>
+      {binding.id.startsWith(CUSTOM_BINDING_PREFIX) && (
+        <div className="flex justify-end pt-2">
+          <Button
+            onClick={() => removeBinding(binding.id)}
+            disabled={isUpdating(`binding_${binding.id}`)}
+            variant="danger"
+            size="sm"
+          >
+            Remove shortcut
+          </Button>
+        </div>
+      )}
+    </SettingContainer>
+  );
+};
+
+export const ShortcutProfiles: React.FC<ShortcutProfilesProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, addBinding, isUpdating } = useSettings();
+    const [name, setName] = useState("");
+    const [osType, setOsType] = useState<string>("unknown");
+
+    useEffect(() => {
+      setOsType(getOsType());
+    }, []);
+
+    const bindings = Object.values(getSetting("bindings") ?? {});
+
+    const handleAdd = async () => {
+      if (!name.trim()) return;
+      await addBinding(name.trim());
+      setName("");
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title="Add Shortcut"
+          description="Add another recording shortcut, e.g. one that cleans up the text with a prompt or one for dictating in another language. Set its keys under Handy Shortcuts."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <div className="flex items-center gap-2">
+            <Input
+              type="text"
+              className="max-w-40"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+              onKeyDown={(e) => {
+                if (e.key === "Enter") {
+                  e.preventDefault();
+                  handleAdd();
+                }
+              }}
+              placeholder="Shortcut name"
+              variant="compact"
+              disabled={isUpdating("add_binding")}
+            />
+            <Button
+              onClick={handleAdd}
+              disabled={!name.trim() || isUpdating("add_binding")}
+              variant="primary"
+              size="md"
+            >
+              Add
+            </Button>
+          </div>
+        </SettingContainer>
+        {bindings.map((binding) => (
+          <BindingProfile
+            key={binding.id}
+            binding={binding}
+            osType={osType}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          />
+        ))}
+      </>
+    );
+  },
+);
@@ -0,0 +1,118 @@
+import React from "react";
+import { ChevronDown, ChevronUp } from "lucide-react";
+import { Button } from "../ui/Button";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+import type { PipelineStep, PipelineStepKind } from "../../lib/types";
+
+interface TextPipelineProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PIPELINE_STEP_INFO: Record<
+  PipelineStepKind,
+  { label: string; description: string }
+> = {
+  hallucination_filter: {
+    label: "Filter Hallucinations",
+    description:
+      "Discard phrases like “Thank you for watching” and repeated loops that the model invents on silent or very short recordings.",
+  },
+  custom_words: {
+    label: "Custom Words",
+    description:
+      "Correct words that sound like entries in your custom word list.",
+  },
+  replacements: {
+    label: "Find and Replace",
+    description:
+      "Apply your find-and-replace rules, in order, to the transcript.",
+  },
+  chinese_conversion: {
+    label: "Chinese Conversion",
+    description:
+      "Convert Chinese output to the variant chosen as the transcription language.",
+  },
+  translation: {
+    label: "Translation",
+    description:
+      "Translate the transcript with the post-processing provider when a target language is set and the model did not translate it already.",
+  },
+  llm_prompt: {
+    label: "LLM Prompt",
+    description:
+      "Rewrite the transcript with the selected post-processing prompt.",
+  },
+  formatting: {
+    label: "Formatting",
+    description: "Capitalize sentences and tidy spacing around punctuation.",
+  },
+};
+
+export const TextPipeline: React.FC<TextPipelineProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const steps: PipelineStep[] = getSetting("post_process_pipeline") ?? [];
+    const updating = isUpdating("post_process_pipeline");
+
+    const setEnabled = (index: number, enabled: boolean) => {
+      updateSetting(
+        "post_process_pipeline",
+        steps.map((step, i) => (i === index ? { ...step, enabled } : step)),
+      );
+    };
+
+    const move = (index: number, offset: number) => {
+      const target = index + offset;
+      if (target < 0 || target >= steps.length) return;
+      const reordered = [...steps];
+      [reordered[index], reordered[target]] = [
+        reordered[target],
+        reordered[index],
+      ];
+      updateSetting("post_process_pipeline", reordered);
+    };
+
+    return (
+      <>
+        {steps.map((step, index) => (
+          <div key={step.kind} className="flex items-center">
+            <div className="flex flex-col pl-2">
+              <Button
+                onClick={() => move(index, -1)}
+                disabled={updating || index === 0}
+                variant="ghost"
+                size="sm"
+                aria-label={`Move ${PIPELINE_STEP_INFO[step.kind].label} up`}
+              >
+                <ChevronUp className="w-3 h-3" />
+              </Button>
+              <Button
+                onClick={() => move(index, 1)}
+                disabled={updating || index === steps.length - 1}
+                variant="ghost"
+                size="sm"
+                aria-label={`Move ${PIPELINE_STEP_INFO[step.kind].label} down`}
+              >
+                <ChevronDown className="w-3 h-3" />
+              </Button>
+            </div>
+            <div className="flex-1">
+              <ToggleSwitch
+                checked={step.enabled}
+                onChange={(enabled) => setEnabled(index, enabled)}
+                isUpdating={updating}
+                label={`${index + 1}. ${PIPELINE_STEP_INFO[step.kind].label}`}
+                description={PIPELINE_STEP_INFO[step.kind].description}
+                descriptionMode={descriptionMode}
+                grouped={grouped}
+              />
+            </div>
+          </div>
+        ))}
+      </>
+    );
+  },
+);
@@ -0,0 +1,148 @@
+import React, { useEffect, useState } from "react";
+import { useSettings } from "../../hooks/useSettings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Input } from "../ui/Input";
+import { Button } from "../ui/Button";
+import type { FallbackBackend, TranscriptionProvider } from "../../lib/types";
+
+interface TranscriptionFallbacksProps {
+  grouped?: boolean;
+}
+
+const providerOptions = [
+  { value: "local", label: "Local" },
+  { value: "deepgram", label: "Deepgram" },
+  { value: "openai", label: "OpenAI-compatible" },
+];
+
+const timeoutOptions = [
+  { value: "10", label: "10 seconds" },
+  { value: "30", label: "30 seconds" },
+  { value: "60", label: "1 minute" },
+  { value: "120", label: "2 minutes" },
+];
+
+const modelPlaceholder = (provider: TranscriptionProvider) => {
+  switch (provider) {
+    case "local":
+      return "Model id (e.g. small)";
+    case "deepgram":
+      return "Model (e.g. nova-3)";
+    case "openai":
+      return "Model (e.g. whisper-1)";
+  }
+};
+
+export const TranscriptionFallbacks: React.FC<
+  TranscriptionFallbacksProps
+> = ({ grouped = false }) => {
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+
+  const fallbacks = getSetting("transcription_fallbacks") ?? [];
+  const timeout = String(getSetting("remote_transcription_timeout_secs") ?? 30);
+  const [drafts, setDrafts] = useState<FallbackBackend[]>(fallbacks);
+
+  useEffect(() => {
+    setDrafts(fallbacks);
+  }, [JSON.stringify(fallbacks)]);
+
+  const save = (next: FallbackBackend[]) => {
+    setDrafts(next);
+    updateSetting(
+      "transcription_fallbacks",
+      next.map((entry) => ({
+        provider: entry.provider,
+        model: entry.model?.trim() ? entry.model.trim() : null,
+      })),
+    );
+  };
+
+  const updateDraft = (index: number, patch: Partial<FallbackBackend>) => {
+    setDrafts(
+      drafts.map((entry, i) => (i === index ? { ...entry, ...patch } : entry)),
+    );
+  };
+
+  const busy = isUpdating("transcription_fallbacks");
+
+  return (
+    <>
+      <SettingContainer
+        title="Fallback backends"
+        description="Tried in order when the primary provider fails or times out. Leave the model empty to use the provider's configured model. A different local model is loaded for that recording, then your usual model is reloaded."
+        descriptionMode="inline"
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex flex-col gap-2 w-full">
+          {drafts.map((entry, index) => (
+            <div
+              key={index}
+              className="flex flex-col sm:flex-row gap-2 sm:items-center"
+            >
+              <Dropdown
+                options={providerOptions}
+                selectedValue={entry.provider}
+                onSelect={(value) =>
+                  save(
+                    drafts.map((e, i) =>
+                      i === index
+                        ? { ...e, provider: value as TranscriptionProvider }
+                        : e,
+                    ),
+                  )
+                }
+                disabled={busy}
+              />
+              <Input
+                className="w-full sm:w-56"
+                placeholder={modelPlaceholder(entry.provider)}
+                value={entry.model ?? ""}
+                onChange={(e) => updateDraft(index, { model: e.target.value })}
+                onBlur={() => save(drafts)}
+                disabled={busy}
+              />
+              <Button
+                size="sm"
+                variant="secondary"
+                onClick={() => save(drafts.filter((_, i) => i !== index))}
+                disabled={busy}
+              >
+                Remove
+              </Button>
+            </div>
+          ))}
+          <div>
+            <Button
+              size="sm"
+              variant="secondary"
+              onClick={() =>
+                save([...drafts, { provider: "local", model: null }])
+              }
+              disabled={busy}
+            >
+              Add fallback
+            </Button>
+          </div>
+        </div>
+      </SettingContainer>
+
+      <SettingContainer
+        title="Remote timeout"
+        description="How long to wait for a cloud provider before moving on to the next backend."
+        descriptionMode="inline"
+        grouped={grouped}
+      >
+        <Dropdown
+          options={timeoutOptions}
+          selectedValue={timeout}
+          onSelect={(value) =>
+            updateSetting("remote_transcription_timeout_secs", Number(value))
+          }
+          disabled={isUpdating("remote_transcription_timeout_secs")}
+        />
+      </SettingContainer>
+    </>
+  );
+};
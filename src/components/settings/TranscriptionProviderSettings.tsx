@@ -4,6 +4,11 @@ import { Dropdown } from "../ui/Dropdown";
 import { SettingContainer } from "../ui/SettingContainer";
 import { Input } from "../ui/Input";
 import { Button } from "../ui/Button";
+import { OpenAiTranscriptionSettings } from "./OpenAiTranscriptionSettings";
+import { TranscriptionFallbacks } from "./TranscriptionFallbacks";
+import { DeepgramOptions } from "./DeepgramOptions";
+import { RemoteAudioFormat } from "./RemoteAudioFormat";
+import { AccountLogin } from "./AccountLogin";
 
 interface TranscriptionProviderSettingsProps {
   grouped?: boolean;
@@ -79,7 +84,7 @@ export const TranscriptionProviderSettings: React.FC<
   const providerOptions = [
     { value: "local", label: "Local (Handy built-in)" },
     { value: "deepgram", label: "Deepgram (cloud)" },
-    { value: "openai", label: "OpenAI (cloud)" },
+    { value: "openai", label: "OpenAI-compatible (cloud or self-hosted)" },
   ];
 
   const deepgramModelOptions = [
@@ -145,6 +150,10 @@ export const TranscriptionProviderSettings: React.FC<
         </div>
       </SettingContainer>
 
+      {provider !== "local" && usageMode === "credits" && (
+        <AccountLogin grouped={grouped} />
+      )}
+
       <SettingContainer
         title="Deepgram API key"
         description="Optional API key used for validation; only a preview is stored locally."
@@ -245,6 +254,16 @@ export const TranscriptionProviderSettings: React.FC<
           />
         </SettingContainer>
       )}
+
+      {provider === "deepgram" && <DeepgramOptions grouped={grouped} />}
+
+      {provider === "openai" && (
+        <OpenAiTranscriptionSettings grouped={grouped} />
+      )}
+
+      {provider !== "local" && <RemoteAudioFormat grouped={grouped} />}
+
+      <TranscriptionFallbacks grouped={grouped} />
     </>
   );
 };
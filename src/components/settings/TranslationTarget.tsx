@@ -0,0 +1,57 @@
+import React from "react";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+import { LANGUAGES } from "../../lib/constants/languages";
+
+interface TranslationTargetProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const NO_TRANSLATION = "";
+
+const languageOptions = [
+  { value: NO_TRANSLATION, label: "Don't translate" },
+  ...LANGUAGES.filter((language) => language.value !== "auto"),
+];
+
+export const TranslationTarget: React.FC<TranslationTargetProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const target = getSetting("translation_target_language") ?? "";
+
+    return (
+      <>
+        <SettingContainer
+          title="Translate To"
+          description="Translate transcripts into another language before pasting. English uses Whisper's built-in translation when the model supports it; other languages use the post-processing provider. Shortcut profiles can override it."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={languageOptions}
+            selectedValue={target}
+            onSelect={(value) =>
+              updateSetting("translation_target_language", value)
+            }
+            disabled={isUpdating("translation_target_language")}
+          />
+        </SettingContainer>
+        <ToggleSwitch
+          checked={getSetting("translation_keep_source_text") ?? false}
+          onChange={(value) =>
+            updateSetting("translation_keep_source_text", value)
+          }
+          isUpdating={isUpdating("translation_keep_source_text")}
+          label="Keep Spoken Text"
+          description="When Whisper translates into English, also transcribe the spoken language for history. This runs a second transcription before pasting."
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+      </>
+    );
+  },
+);
@@ -0,0 +1,143 @@
+import React, { useEffect, useState } from "react";
+import { useSettings } from "../../hooks/useSettings";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Input } from "../ui/Input";
+import { Button } from "../ui/Button";
+import type { UsagePrice } from "../../lib/types";
+
+interface UsagePricesProps {
+  grouped?: boolean;
+}
+
+type PriceField =
+  | "per_audio_minute"
+  | "per_million_prompt_tokens"
+  | "per_million_completion_tokens";
+
+const priceFields: { field: PriceField; placeholder: string }[] = [
+  { field: "per_audio_minute", placeholder: "$ / audio min" },
+  { field: "per_million_prompt_tokens", placeholder: "$ / 1M input" },
+  { field: "per_million_completion_tokens", placeholder: "$ / 1M output" },
+];
+
+export const UsagePrices: React.FC<UsagePricesProps> = ({
+  grouped = false,
+}) => {
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+
+  const prices = getSetting("usage_prices") ?? [];
+  const [drafts, setDrafts] = useState<UsagePrice[]>(prices);
+
+  useEffect(() => {
+    setDrafts(prices);
+  }, [JSON.stringify(prices)]);
+
+  const save = (next: UsagePrice[]) => {
+    setDrafts(next);
+    updateSetting(
+      "usage_prices",
+      next
+        .filter((entry) => entry.provider.trim())
+        .map((entry) => ({
+          ...entry,
+          provider: entry.provider.trim(),
+          model: entry.model?.trim() ? entry.model.trim() : null,
+        })),
+    );
+  };
+
+  const updateDraft = (index: number, patch: Partial<UsagePrice>) => {
+    setDrafts(
+      drafts.map((entry, i) => (i === index ? { ...entry, ...patch } : entry)),
+    );
+  };
+
+  const busy = isUpdating("usage_prices");
+
+  return (
+    <SettingContainer
+      title="Prices"
+      description="Used to estimate costs from recorded usage. Leave the model empty to price every model of a provider. Usage without a matching price is counted but not costed."
+      descriptionMode="inline"
+      grouped={grouped}
+      layout="stacked"
+    >
+      <div className="flex flex-col gap-2 w-full">
+        {drafts.map((entry, index) => (
+          <div
+            key={index}
+            className="flex flex-col lg:flex-row gap-2 lg:items-center"
+          >
+            <Input
+              variant="compact"
+              className="w-full lg:w-28"
+              placeholder="Provider"
+              value={entry.provider}
+              onChange={(e) => updateDraft(index, { provider: e.target.value })}
+              onBlur={() => save(drafts)}
+              disabled={busy}
+            />
+            <Input
+              variant="compact"
+              className="w-full lg:w-44"
+              placeholder="Any model"
+              value={entry.model ?? ""}
+              onChange={(e) => updateDraft(index, { model: e.target.value })}
+              onBlur={() => save(drafts)}
+              disabled={busy}
+            />
+            {priceFields.map(({ field, placeholder }) => (
+              <Input
+                key={field}
+                variant="compact"
+                type="number"
+                min="0"
+                step="any"
+                className="w-full lg:w-28"
+                placeholder={placeholder}
+                title={placeholder}
+                value={entry[field] || ""}
+                onChange={(e) =>
+                  updateDraft(index, {
+                    [field]: Math.max(parseFloat(e.target.value) || 0, 0),
+                  })
+                }
+                onBlur={() => save(drafts)}
+                disabled={busy}
+              />
+            ))}
+            <Button
+              size="sm"
+              variant="secondary"
+              onClick={() => save(drafts.filter((_, i) => i !== index))}
+              disabled={busy}
+            >
+              Remove
+            </Button>
+          </div>
+        ))}
+        <div>
+          <Button
+            size="sm"
+            variant="secondary"
+            onClick={() =>
+              setDrafts([
+                ...drafts,
+                {
+                  provider: "",
+                  model: null,
+                  per_audio_minute: 0,
+                  per_million_prompt_tokens: 0,
+                  per_million_completion_tokens: 0,
+                },
+              ])
+            }
+            disabled={busy}
+          >
+            Add price
+          </Button>
+        </div>
+      </div>
+    </SettingContainer>
+  );
+};
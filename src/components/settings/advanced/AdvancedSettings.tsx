@@ -1,7 +1,12 @@
 import React from "react";
 import { ShowOverlay } from "../ShowOverlay";
-import { TranslateToEnglish } from "../TranslateToEnglish";
+import { TranslationTarget } from "../TranslationTarget";
+import { Diarization } from "../Diarization";
+import { TextPipeline } from "../TextPipeline";
+import { ReplacementRules } from "../ReplacementRules";
 import { ModelUnloadTimeoutSetting } from "../ModelUnloadTimeout";
+import { ModelPreload } from "../ModelPreload";
+import { LowMemoryUnload } from "../LowMemoryUnload";
 import { CustomWords } from "../CustomWords";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { StartHidden } from "../StartHidden";
@@ -19,11 +24,23 @@ export const AdvancedSettings: React.FC = () => {
           <ShowOverlay descriptionMode="inline" grouped={true} />
           <PasteMethodSetting descriptionMode="inline" grouped={true} />
           <ClipboardHandlingSetting descriptionMode="inline" grouped={true} />
-          <TranslateToEnglish descriptionMode="inline" grouped={true} />
+          <TranslationTarget descriptionMode="inline" grouped={true} />
+          <Diarization descriptionMode="inline" grouped={true} />
+          <ModelPreload descriptionMode="inline" grouped={true} />
           <ModelUnloadTimeoutSetting descriptionMode="inline" grouped={true} />
+          <LowMemoryUnload descriptionMode="inline" grouped={true} />
           <CustomWords descriptionMode="inline" grouped />
         </SettingsGroup>
       </div>
+      <div className="animate-in slide-in-from-bottom-4 duration-500 delay-200 fill-mode-backwards">
+        <SettingsGroup
+          title="Text Pipeline"
+          description="Steps applied to every transcript before it is pasted, from top to bottom."
+        >
+          <TextPipeline descriptionMode="inline" grouped={true} />
+          <ReplacementRules descriptionMode="inline" grouped={true} />
+        </SettingsGroup>
+      </div>
     </div>
   );
 };
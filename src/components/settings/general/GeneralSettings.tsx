@@ -2,6 +2,8 @@ import React from "react";
 import { MicrophoneSelector } from "../MicrophoneSelector";
 import { LanguageSelector } from "../LanguageSelector";
 import { HandyShortcut } from "../HandyShortcut";
+import { ShortcutProfiles } from "../ShortcutProfiles";
+import { AppProfiles } from "../AppProfiles";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { OutputDeviceSelector } from "../OutputDeviceSelector";
 import { PushToTalk } from "../PushToTalk";
@@ -28,6 +30,24 @@ export const GeneralSettings: React.FC = () => {
         </SettingsGroup>
       </div>
 
+      <div className="animate-in slide-in-from-bottom-4 duration-500 delay-300 fill-mode-backwards">
+        <SettingsGroup
+          title="Shortcut Profiles"
+          description="Give each shortcut its own provider, language, prompt and paste method."
+        >
+          <ShortcutProfiles descriptionMode="inline" grouped={true} />
+        </SettingsGroup>
+      </div>
+
+      <div className="animate-in slide-in-from-bottom-4 duration-500 delay-300 fill-mode-backwards">
+        <SettingsGroup
+          title="App Profiles"
+          description="Override the paste method, prompt, language and custom words for specific apps."
+        >
+          <AppProfiles descriptionMode="inline" grouped={true} />
+        </SettingsGroup>
+      </div>
+
       <div className="animate-in slide-in-from-bottom-4 duration-500 delay-300 fill-mode-backwards">
         <SettingsGroup title="Sound">
           <MicrophoneSelector descriptionMode="inline" grouped={true} />
@@ -1,10 +1,19 @@
 import React, { useState, useEffect, useCallback } from "react";
 import { AudioPlayer } from "../../ui/AudioPlayer";
 import { Button } from "../../ui/Button";
-import { Copy, Star, Check, Trash2, FolderOpen } from "lucide-react";
+import {
+  Copy,
+  Star,
+  Check,
+  Trash2,
+  FolderOpen,
+  Download,
+} from "lucide-react";
 import { convertFileSrc, invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
 import { HistoryEntry } from "../../../lib/types";
+import { PIPELINE_STEP_INFO } from "../TextPipeline";
 
 interface OpenRecordingsButtonProps {
   onClick: () => void;
@@ -226,6 +235,18 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
     setTimeout(() => setShowCopied(false), 2000);
   };
 
+  const handleExport = async () => {
+    try {
+      const path = await invoke<string>("export_history_entry", {
+        id: entry.id,
+      });
+      toast.success(`Exported to ${path}`);
+    } catch (error) {
+      console.error("Failed to export entry:", error);
+      toast.error(`Failed to export: ${error}`);
+    }
+  };
+
   const handleDeleteEntry = async () => {
     try {
       await deleteAudio(entry.id);
@@ -238,7 +259,20 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
   return (
     <div className="px-4 py-2 pb-5 flex flex-col gap-3">
       <div className="flex justify-between items-center">
-        <p className="text-sm font-medium">{entry.title}</p>
+        <div className="flex items-baseline gap-2">
+          <p className="text-sm font-medium">{entry.title}</p>
+          {entry.backend && (
+            <span className="text-xs text-mid-gray">{entry.backend}</span>
+          )}
+          {entry.edited_selection != null && (
+            <span className="text-xs text-mid-gray">edit instruction</span>
+          )}
+          {entry.translation_language && !entry.translation && (
+            <span className="text-xs text-mid-gray">
+              translated to {entry.translation_language}
+            </span>
+          )}
+        </div>
         <div className="flex items-center gap-1">
           <button
             onClick={handleCopyText}
@@ -251,6 +285,13 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
               <Copy width={16} height={16} />
             )}
           </button>
+          <button
+            onClick={handleExport}
+            className="text-text/50 hover:text-logo-primary transition-colors cursor-pointer"
+            title="Export transcription as a text file"
+          >
+            <Download width={16} height={16} />
+          </button>
           <button
             onClick={onToggleSaved}
             className={`p-2 rounded  transition-colors cursor-pointer ${
@@ -275,9 +316,67 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
           </button>
         </div>
       </div>
-      <p className="italic text-text/90 text-sm pb-2">
-        {entry.transcription_text}
-      </p>
+      {entry.speaker_segments && entry.speaker_segments.length > 0 ? (
+        <div className="flex flex-col gap-1 text-sm pb-2">
+          {entry.speaker_segments.map((segment, index) => (
+            <p key={index} className="italic text-text/90">
+              <span className="not-italic font-medium text-mid-gray">
+                Speaker {segment.speaker + 1}:
+              </span>{" "}
+              {segment.text}
+            </p>
+          ))}
+        </div>
+      ) : (
+        <p className="italic text-text/90 text-sm pb-2">
+          {entry.transcription_text}
+        </p>
+      )}
+      {entry.edited_selection != null && (
+        <div className="flex flex-col gap-2 text-sm pb-2">
+          <div className="flex flex-col gap-1">
+            <span className="text-xs text-mid-gray">Before</span>
+            <p className="text-text/90 whitespace-pre-wrap">
+              {entry.edited_selection}
+            </p>
+          </div>
+          <div className="flex flex-col gap-1">
+            <span className="text-xs text-mid-gray">After</span>
+            <p className="text-text/90 whitespace-pre-wrap">
+              {entry.post_processed_text}
+            </p>
+          </div>
+        </div>
+      )}
+      {entry.translation && (
+        <div className="flex flex-col gap-1 text-sm pb-2">
+          <span className="text-xs text-mid-gray">
+            Translation ({entry.translation_language})
+          </span>
+          <p className="italic text-text/90 whitespace-pre-wrap">
+            {entry.translation}
+          </p>
+        </div>
+      )}
+      {entry.pipeline_steps && entry.pipeline_steps.length > 0 && (
+        <details className="text-sm pb-2">
+          <summary className="text-xs text-mid-gray cursor-pointer">
+            Post-processing steps
+          </summary>
+          <div className="flex flex-col gap-2 pt-2">
+            {entry.pipeline_steps.map((output, index) => (
+              <div key={index} className="flex flex-col gap-1">
+                <span className="text-xs text-mid-gray">
+                  {PIPELINE_STEP_INFO[output.step].label}
+                </span>
+                <p className="text-text/90 whitespace-pre-wrap">
+                  {output.text}
+                </p>
+              </div>
+            ))}
+          </div>
+        </details>
+      )}
       {audioUrl && <AudioPlayer src={audioUrl} className="w-full" />}
     </div>
   );
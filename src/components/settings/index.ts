@@ -5,6 +5,7 @@ export { DebugSettings } from "./debug/DebugSettings";
 export { HistorySettings } from "./history/HistorySettings";
 export { AboutSettings } from "./about/AboutSettings";
 export { PostProcessingSettings } from "./post-processing/PostProcessingSettings";
+export { UsageSettings } from "./usage/UsageSettings";
 
 // Individual setting components
 export { MicrophoneSelector } from "./MicrophoneSelector";
@@ -15,15 +16,28 @@ export { PushToTalk } from "./PushToTalk";
 export { AudioFeedback } from "./AudioFeedback";
 export { ShowOverlay } from "./ShowOverlay";
 export { HandyShortcut } from "./HandyShortcut";
-export { TranslateToEnglish } from "./TranslateToEnglish";
+export { ShortcutProfiles } from "./ShortcutProfiles";
+export { AppProfiles } from "./AppProfiles";
+export { TranslationTarget } from "./TranslationTarget";
+export { Diarization } from "./Diarization";
+export { TextPipeline } from "./TextPipeline";
+export { ReplacementRules } from "./ReplacementRules";
 export { CustomWords } from "./CustomWords";
 export { PostProcessingToggle } from "./PostProcessingToggle";
 export { PostProcessingSettingsApi } from "./PostProcessingSettingsApi";
 export { PostProcessingSettingsPrompts } from "./PostProcessingSettingsPrompts";
 export { AppDataDirectory } from "./AppDataDirectory";
 export { ModelUnloadTimeoutSetting } from "./ModelUnloadTimeout";
+export { ModelPreload } from "./ModelPreload";
+export { LowMemoryUnload } from "./LowMemoryUnload";
 export { StartHidden } from "./StartHidden";
 export { HistoryLimit } from "./HistoryLimit";
 export { RecordingRetentionPeriodSelector } from "./RecordingRetentionPeriod";
 export { AutostartToggle } from "./AutostartToggle";
 export { TranscriptionProviderSettings } from "./TranscriptionProviderSettings";
+export { TranscriptionFallbacks } from "./TranscriptionFallbacks";
+export { RemoteAudioFormat } from "./RemoteAudioFormat";
+export { DeepgramOptions } from "./DeepgramOptions";
+export { MonthlyBudget } from "./MonthlyBudget";
+export { UsagePrices } from "./UsagePrices";
+export { AccountLogin } from "./AccountLogin";
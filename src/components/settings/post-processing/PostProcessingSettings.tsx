@@ -138,6 +138,32 @@ const PostProcessingSettingsApiComponent: React.FC = () => {
   );
 };
 
+const PROMPT_VARIABLES = [
+  ["output", "the transcribed text"],
+  ["selection", "selected text, copied via the clipboard if needed"],
+  ["clipboard", "clipboard text when recording started"],
+  ["app_name", "the application you are typing into"],
+  ["window_title", "the title of its window"],
+  ["language", "the language the text should be in"],
+  ["date", "today's date"],
+];
+
+const PromptVariablesTip: React.FC = () => (
+  <div className="text-xs text-mid-gray/70 space-y-1">
+    <p>Tip: these variables are replaced before the prompt is sent.</p>
+    <ul className="space-y-0.5">
+      {PROMPT_VARIABLES.map(([name, meaning]) => (
+        <li key={name}>
+          <code className="px-1 py-0.5 bg-mid-gray/20 rounded text-xs">
+            {`\${${name}}`}
+          </code>{" "}
+          {meaning}
+        </li>
+      ))}
+    </ul>
+  </div>
+);
+
 const PostProcessingSettingsPromptsComponent: React.FC = () => {
   const { getSetting, updateSetting, isUpdating, refreshSettings } =
     useSettings();
@@ -252,7 +278,7 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
   return (
     <SettingContainer
       title="Selected Prompt"
-      description="Select a template for refining transcriptions or create a new one. Use ${output} inside the prompt text to reference the captured transcript."
+      description="Select a template for refining transcriptions or create a new one. Use ${output} inside the prompt text to reference the captured transcript, and variables like ${selection} or ${app_name} to adapt it to where you are typing."
       descriptionMode="inline"
       layout="stacked"
       grouped={true}
@@ -306,13 +332,7 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
                 onChange={(e) => setDraftText(e.target.value)}
                 placeholder="Write the instructions to run after transcription. Example: Improve grammar and clarity for the following text: ${output}"
               />
-              <p className="text-xs text-mid-gray/70">
-                Tip: Use{" "}
-                <code className="px-1 py-0.5 bg-mid-gray/20 rounded text-xs">
-                  $&#123;output&#125;
-                </code>{" "}
-                to insert the transcribed text in your prompt.
-              </p>
+              <PromptVariablesTip />
             </div>
 
             <div className="flex gap-2 pt-2">
@@ -370,13 +390,7 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
                 onChange={(e) => setDraftText(e.target.value)}
                 placeholder="Write the instructions to run after transcription. Example: Improve grammar and clarity for the following text: ${output}"
               />
-              <p className="text-xs text-mid-gray/70">
-                Tip: Use{" "}
-                <code className="px-1 py-0.5 bg-mid-gray/20 rounded text-xs">
-                  $&#123;output&#125;
-                </code>{" "}
-                to insert the transcribed text in your prompt.
-              </p>
+              <PromptVariablesTip />
             </div>
 
             <div className="flex gap-2 pt-2">
@@ -403,6 +417,69 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
   );
 };
 
+const EDIT_SELECTION_VARIABLES = [
+  ["instruction", "what you said"],
+  ["selection", "the selected text to edit"],
+];
+
+const EditSelectionPromptComponent: React.FC = () => {
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+  const prompt = getSetting("edit_selection_prompt") ?? "";
+  const [draft, setDraft] = useState(prompt);
+
+  useEffect(() => {
+    setDraft(prompt);
+  }, [prompt]);
+
+  const isValid = draft.trim() !== "" && draft.includes("${selection}");
+
+  return (
+    <SettingContainer
+      title="Edit Prompt"
+      description="Sent to the provider above when you hold the Edit Selection shortcut. Select text, hold the shortcut and say how to change it, such as 'make this more formal'; the reply replaces the selection. If the shortcut has no keys yet, record some under General."
+      descriptionMode="inline"
+      layout="stacked"
+      grouped={true}
+    >
+      <div className="space-y-3">
+        <Textarea
+          value={draft}
+          onChange={(e) => setDraft(e.target.value)}
+          placeholder="Edit the text as instructed: ${instruction} Text: ${selection}"
+        />
+        <div className="text-xs text-mid-gray/70 space-y-1">
+          <p>
+            Must include ${"{selection}"}. Variables like ${"{app_name}"} work
+            here too.
+          </p>
+          <ul className="space-y-0.5">
+            {EDIT_SELECTION_VARIABLES.map(([name, meaning]) => (
+              <li key={name}>
+                <code className="px-1 py-0.5 bg-mid-gray/20 rounded text-xs">
+                  {`\${${name}}`}
+                </code>{" "}
+                {meaning}
+              </li>
+            ))}
+          </ul>
+        </div>
+        <Button
+          onClick={() => updateSetting("edit_selection_prompt", draft.trim())}
+          variant="primary"
+          size="md"
+          disabled={
+            !isValid ||
+            draft.trim() === prompt.trim() ||
+            isUpdating("edit_selection_prompt")
+          }
+        >
+          Update Prompt
+        </Button>
+      </div>
+    </SettingContainer>
+  );
+};
+
 export const PostProcessingSettingsApi = React.memo(
   PostProcessingSettingsApiComponent,
 );
@@ -413,6 +490,9 @@ export const PostProcessingSettingsPrompts = React.memo(
 );
 PostProcessingSettingsPrompts.displayName = "PostProcessingSettingsPrompts";
 
+export const EditSelectionPrompt = React.memo(EditSelectionPromptComponent);
+EditSelectionPrompt.displayName = "EditSelectionPrompt";
+
 export const PostProcessingSettings: React.FC = () => {
   return (
     <div className="w-full max-w-4xl space-y-8 pb-12 animate-in fade-in duration-500">
@@ -427,6 +507,12 @@ export const PostProcessingSettings: React.FC = () => {
           <PostProcessingSettingsPrompts />
         </SettingsGroup>
       </div>
+
+      <div className="animate-in slide-in-from-bottom-4 duration-500 delay-300 fill-mode-backwards">
+        <SettingsGroup title="Edit Selection">
+          <EditSelectionPrompt />
+        </SettingsGroup>
+      </div>
     </div>
   );
 };
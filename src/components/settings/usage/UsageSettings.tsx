@@ -0,0 +1,151 @@
+import React, { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { SettingsGroup } from "../../ui/SettingsGroup";
+import { MonthlyBudget } from "../MonthlyBudget";
+import { UsagePrices } from "../UsagePrices";
+import type { DailyUsage, UsageSummary } from "../../../lib/types";
+
+const USAGE_DAYS = 30;
+
+const formatCost = (cost: number | null) =>
+  cost === null ? "—" : `$${cost.toFixed(cost < 0.01 && cost > 0 ? 4 : 2)}`;
+
+// Relayed requests are paid through the account, so have no estimate
+const formatRowCost = (row: DailyUsage) =>
+  row.direct ? formatCost(row.estimated_cost) : "via account";
+
+const formatAmount = (row: DailyUsage) => {
+  if (row.kind === "transcription") {
+    const minutes = row.audio_seconds / 60;
+    return `${minutes.toFixed(1)} min audio`;
+  }
+  const tokens = row.prompt_tokens + row.completion_tokens;
+  return `${tokens.toLocaleString()} tokens`;
+};
+
+export const UsageSettings: React.FC = () => {
+  const [usage, setUsage] = useState<DailyUsage[]>([]);
+  const [summary, setSummary] = useState<UsageSummary | null>(null);
+  const [loading, setLoading] = useState(true);
+
+  const loadUsage = useCallback(async () => {
+    try {
+      const [rows, monthSummary] = await Promise.all([
+        invoke<DailyUsage[]>("get_usage_by_day", { days: USAGE_DAYS }),
+        invoke<UsageSummary>("get_usage_summary"),
+      ]);
+      setUsage(rows);
+      setSummary(monthSummary);
+    } catch (error) {
+      console.error("Failed to load usage:", error);
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadUsage();
+
+    // Usage is recorded alongside each history entry
+    const unlistenPromise = listen("history-updated", () => loadUsage());
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [loadUsage]);
+
+  const budget = summary?.monthly_budget ?? 0;
+  const budgetShare =
+    summary && budget > 0 ? Math.min(summary.month_cost / budget, 1) : 0;
+
+  return (
+    <div className="w-full max-w-4xl space-y-8 pb-12 animate-in fade-in duration-500">
+      <SettingsGroup
+        title="This Month"
+        description="Estimated from usage sent straight to providers and the prices below. Requests relayed through your account are not included. Check your provider's dashboard for the amount actually billed."
+      >
+        <div className="p-5 space-y-3">
+          <div className="flex items-baseline gap-2">
+            <span className="text-3xl font-serif text-foreground">
+              {formatCost(summary?.month_cost ?? 0)}
+            </span>
+            {budget > 0 && (
+              <span className="text-sm text-muted-foreground">
+                of {formatCost(budget)} budget
+              </span>
+            )}
+          </div>
+          {budget > 0 && (
+            <div className="h-2 bg-muted rounded-full overflow-hidden">
+              <div
+                className={`h-full ${budgetShare >= 1 ? "bg-red-500" : budgetShare >= 0.8 ? "bg-amber-500" : "bg-primary"}`}
+                style={{ width: `${budgetShare * 100}%` }}
+              />
+            </div>
+          )}
+          {summary && summary.unpriced_requests > 0 && (
+            <p className="text-xs text-muted-foreground">
+              {summary.unpriced_requests} request(s) this month have no
+              matching price and are not included.
+            </p>
+          )}
+        </div>
+        <MonthlyBudget descriptionMode="inline" grouped={true} />
+      </SettingsGroup>
+
+      <SettingsGroup title={`Last ${USAGE_DAYS} Days`}>
+        {loading ? (
+          <div className="p-5 text-sm text-muted-foreground">
+            Loading usage...
+          </div>
+        ) : usage.length === 0 ? (
+          <div className="p-5 text-sm text-muted-foreground">
+            No cloud usage recorded. Local transcription is free and not
+            tracked.
+          </div>
+        ) : (
+          <table className="w-full text-sm">
+            <thead className="text-left text-muted-foreground">
+              <tr>
+                <th className="px-5 py-2 font-medium">Day</th>
+                <th className="px-2 py-2 font-medium">Provider</th>
+                <th className="px-2 py-2 font-medium text-right">Requests</th>
+                <th className="px-2 py-2 font-medium text-right">Usage</th>
+                <th className="px-5 py-2 font-medium text-right">Cost</th>
+              </tr>
+            </thead>
+            <tbody className="divide-y divide-border/50">
+              {usage.map((row) => (
+                <tr
+                  key={`${row.day}-${row.kind}-${row.provider}-${row.model}-${row.direct}`}
+                >
+                  <td className="px-5 py-2">{row.day}</td>
+                  <td className="px-2 py-2">
+                    {row.provider}
+                    {row.model && (
+                      <span className="text-muted-foreground">
+                        {" "}
+                        · {row.model}
+                      </span>
+                    )}
+                  </td>
+                  <td className="px-2 py-2 text-right">{row.requests}</td>
+                  <td className="px-2 py-2 text-right">
+                    {formatAmount(row)}
+                  </td>
+                  <td className="px-5 py-2 text-right">
+                    {formatRowCost(row)}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+      </SettingsGroup>
+
+      <SettingsGroup title="Pricing">
+        <UsagePrices grouped={true} />
+      </SettingsGroup>
+    </div>
+  );
+};
@@ -1,6 +1,12 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import type {
+  BenchmarkOutcome,
+  DownloadProgress,
+  DownloadQueueProgress,
+  ModelVerification,
+} from "../lib/types";
 
 interface ModelInfo {
   id: string;
@@ -17,13 +23,6 @@ interface ModelInfo {
   speed_score: number;
 }
 
-interface DownloadProgress {
-  model_id: string;
-  downloaded: number;
-  total: number;
-  percentage: number;
-}
-
 export const useModels = () => {
   const [models, setModels] = useState<ModelInfo[]>([]);
   const [currentModel, setCurrentModel] = useState<string>("");
@@ -117,6 +116,67 @@ export const useModels = () => {
     }
   };
 
+  // Hashes every model on disk; the problems it finds can be fixed with redownloadModel
+  const verifyModels = async (): Promise<ModelVerification[]> => {
+    try {
+      setError(null);
+      return await invoke<ModelVerification[]>("verify_models");
+    } catch (err) {
+      setError(`Failed to verify models: ${err}`);
+      return [];
+    }
+  };
+
+  const redownloadModel = async (modelId: string) => {
+    try {
+      setError(null);
+      setDownloadingModels((prev) => new Set(prev.add(modelId)));
+      await invoke("redownload_model", { modelId });
+      return true;
+    } catch (err) {
+      setError(`Failed to re-download model: ${err}`);
+      setDownloadingModels((prev) => {
+        const next = new Set(prev);
+        next.delete(modelId);
+        return next;
+      });
+      return false;
+    }
+  };
+
+  // Runs downloaded models over the reference clips; scores refresh afterwards
+  const benchmarkModels = async (
+    modelIds?: string[],
+  ): Promise<BenchmarkOutcome[]> => {
+    try {
+      setError(null);
+      const outcomes = await invoke<BenchmarkOutcome[]>("benchmark_models", {
+        modelIds,
+      });
+      await loadModels();
+      return outcomes;
+    } catch (err) {
+      setError(`Failed to benchmark models: ${err}`);
+      return [];
+    }
+  };
+
+  const pauseDownload = async (modelId: string) => {
+    try {
+      await invoke("pause_download", { modelId });
+    } catch (err) {
+      setError(`Failed to pause download: ${err}`);
+    }
+  };
+
+  const resumeDownload = async (modelId: string) => {
+    try {
+      await invoke("resume_download", { modelId });
+    } catch (err) {
+      setError(`Failed to resume download: ${err}`);
+    }
+  };
+
   const getModelInfo = (modelId: string): ModelInfo | undefined => {
     return models.find((model) => model.id === modelId);
   };
@@ -141,11 +201,16 @@ export const useModels = () => {
     checkFirstRun();
 
     // Listen for download progress
-    const progressUnlisten = listen<DownloadProgress>(
+    const progressUnlisten = listen<DownloadQueueProgress>(
       "model-download-progress",
       (event) => {
         setDownloadProgress(
-          (prev) => new Map(prev.set(event.payload.model_id, event.payload)),
+          new Map(
+            event.payload.downloads.map((progress) => [
+              progress.model_id,
+              progress,
+            ]),
+          ),
         );
       },
     );
@@ -206,8 +271,27 @@ export const useModels = () => {
       setError(`Failed to extract model: ${event.payload.error}`);
     });
 
+    const downloadFailedUnlisten = listen<{
+      model_id: string;
+      error: string;
+    }>("model-download-failed", (event) => {
+      const modelId = event.payload.model_id;
+      setDownloadingModels((prev) => {
+        const next = new Set(prev);
+        next.delete(modelId);
+        return next;
+      });
+      setDownloadProgress((prev) => {
+        const next = new Map(prev);
+        next.delete(modelId);
+        return next;
+      });
+      setError(`Failed to download model: ${event.payload.error}`);
+    });
+
     return () => {
       progressUnlisten.then((fn) => fn());
+      downloadFailedUnlisten.then((fn) => fn());
       completeUnlisten.then((fn) => fn());
       extractionStartedUnlisten.then((fn) => fn());
       extractionCompletedUnlisten.then((fn) => fn());
@@ -231,6 +315,11 @@ export const useModels = () => {
     selectModel,
     downloadModel,
     deleteModel,
+    verifyModels,
+    redownloadModel,
+    pauseDownload,
+    resumeDownload,
+    benchmarkModels,
     getModelInfo,
     isModelDownloading,
     isModelExtracting,
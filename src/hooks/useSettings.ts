@@ -1,6 +1,6 @@
 import { useEffect } from "react";
 import { useSettingsStore } from "../stores/settingsStore";
-import { Settings, AudioDevice } from "../lib/types";
+import { Settings, AudioDevice, ActionProfile } from "../lib/types";
 
 interface UseSettingsReturn {
   // State
@@ -25,6 +25,9 @@ interface UseSettingsReturn {
   // Binding-specific actions
   updateBinding: (id: string, binding: string) => Promise<void>;
   resetBinding: (id: string) => Promise<void>;
+  updateBindingProfile: (id: string, profile: ActionProfile) => Promise<void>;
+  addBinding: (name: string) => Promise<void>;
+  removeBinding: (id: string) => Promise<void>;
 
   // Convenience getters
   getSetting: <K extends keyof Settings>(key: K) => Settings[K] | undefined;
@@ -42,6 +45,7 @@ interface UseSettingsReturn {
   updatePostProcessModel: (providerId: string, model: string) => Promise<void>;
   fetchPostProcessModels: (providerId: string) => Promise<string[]>;
   validateDeepgramKey: (apiKey: string) => Promise<void>;
+  saveOpenAiKey: (apiKey: string) => Promise<void>;
   setSecureKeyStorage: (enabled: boolean) => Promise<void>;
 }
 
@@ -70,6 +74,9 @@ export const useSettings = (): UseSettingsReturn => {
     refreshOutputDevices: store.refreshOutputDevices,
     updateBinding: store.updateBinding,
     resetBinding: store.resetBinding,
+    updateBindingProfile: store.updateBindingProfile,
+    addBinding: store.addBinding,
+    removeBinding: store.removeBinding,
     getSetting: store.getSetting,
     setPostProcessProvider: store.setPostProcessProvider,
     updatePostProcessBaseUrl: store.updatePostProcessBaseUrl,
@@ -77,6 +84,7 @@ export const useSettings = (): UseSettingsReturn => {
     updatePostProcessModel: store.updatePostProcessModel,
     fetchPostProcessModels: store.fetchPostProcessModels,
     validateDeepgramKey: store.validateDeepgramKey,
+    saveOpenAiKey: store.saveOpenAiKey,
     setSecureKeyStorage: (enabled: boolean) =>
       store.updateSetting("use_secure_key_storage", enabled as any),
   };
@@ -1,13 +1,59 @@
 import { z } from "zod";
 
+export const ActionProfileSchema = z.object({
+  provider: z.enum(["local", "deepgram", "openai"]).nullable().optional(),
+  model: z.string().nullable().optional(),
+  language: z.string().nullable().optional(),
+  translation_target_language: z.string().nullable().optional(),
+  post_process_prompt_id: z.string().nullable().optional(),
+  paste_method: z
+    .enum(["ctrl_v", "direct", "shift_insert"])
+    .nullable()
+    .optional(),
+});
+export type ActionProfile = z.infer<typeof ActionProfileSchema>;
+
 export const ShortcutBindingSchema = z.object({
   id: z.string(),
   name: z.string(),
   description: z.string(),
   default_binding: z.string(),
   current_binding: z.string(),
+  profile: ActionProfileSchema.optional().default({}),
 });
 
+export const AppProfileSchema = z.object({
+  name: z.string(),
+  apps: z.array(z.string()).optional().default([]),
+  profile: ActionProfileSchema.optional().default({}),
+  custom_words: z.array(z.string()).nullable().optional(),
+});
+export type AppProfile = z.infer<typeof AppProfileSchema>;
+
+export const PipelineStepKindSchema = z.enum([
+  "hallucination_filter",
+  "custom_words",
+  "replacements",
+  "chinese_conversion",
+  "translation",
+  "llm_prompt",
+  "formatting",
+]);
+export type PipelineStepKind = z.infer<typeof PipelineStepKindSchema>;
+
+export const PipelineStepSchema = z.object({
+  kind: PipelineStepKindSchema,
+  enabled: z.boolean(),
+});
+export type PipelineStep = z.infer<typeof PipelineStepSchema>;
+
+export const ReplacementRuleSchema = z.object({
+  pattern: z.string(),
+  replacement: z.string(),
+  case_insensitive: z.boolean().optional().default(false),
+});
+export type ReplacementRule = z.infer<typeof ReplacementRuleSchema>;
+
 export const ShortcutBindingsMapSchema = z.record(
   z.string(),
   ShortcutBindingSchema,
@@ -50,6 +96,31 @@ export const TranscriptionProviderSchema = z.enum([
 ]);
 export type TranscriptionProvider = z.infer<typeof TranscriptionProviderSchema>;
 
+export const FallbackBackendSchema = z.object({
+  provider: TranscriptionProviderSchema,
+  model: z.string().nullable().optional(),
+});
+export type FallbackBackend = z.infer<typeof FallbackBackendSchema>;
+
+export const UsagePriceSchema = z.object({
+  provider: z.string(),
+  model: z.string().nullable().optional(),
+  per_audio_minute: z.number().optional().default(0),
+  per_million_prompt_tokens: z.number().optional().default(0),
+  per_million_completion_tokens: z.number().optional().default(0),
+});
+export type UsagePrice = z.infer<typeof UsagePriceSchema>;
+
+export const OpenAiResponseFormatSchema = z.enum([
+  "json",
+  "text",
+  "verbose_json",
+]);
+export type OpenAiResponseFormat = z.infer<typeof OpenAiResponseFormatSchema>;
+
+export const RemoteAudioFormatSchema = z.enum(["auto", "wav", "flac", "opus"]);
+export type RemoteAudioFormat = z.infer<typeof RemoteAudioFormatSchema>;
+
 export const UsageModeSchema = z.enum(["own_keys", "credits"]);
 export type UsageMode = z.infer<typeof UsageModeSchema>;
 
@@ -94,6 +165,7 @@ export type SidebarSection =
   | "advanced"
   | "postprocessing"
   | "history"
+  | "usage"
   | "debug"
   | "about"
   | "dictionary"
@@ -113,12 +185,26 @@ export const SettingsSchema = z.object({
   selected_model: z.string(),
   provider: TranscriptionProviderSchema.optional().default("local"),
   deepgram_model: z.string().optional().default("nova-3"),
+  deepgram_smart_format: z.boolean().optional().default(true),
+  deepgram_punctuate: z.boolean().optional().default(true),
+  deepgram_numerals: z.boolean().optional().default(false),
+  deepgram_filler_words: z.boolean().optional().default(false),
+  deepgram_streaming: z.boolean().optional().default(false),
   use_secure_key_storage: z.boolean().optional().default(true),
   api_base_url: z.string().nullable().optional(),
   deepgram_api_key_preview: z.string().optional().default(""),
   deepgram_api_key: z.string().optional().default(""),
   openai_api_key_preview: z.string().optional().default(""),
   openai_api_key: z.string().optional().default(""),
+  openai_transcription_base_url: z
+    .string()
+    .optional()
+    .default("https://api.openai.com/v1"),
+  openai_transcription_model: z.string().optional().default("whisper-1"),
+  openai_transcription_language: z.string().nullable().optional(),
+  openai_transcription_prompt: z.string().optional().default(""),
+  openai_transcription_response_format:
+    OpenAiResponseFormatSchema.optional().default("json"),
   auth_token: z.string().nullable().optional(),
   user_id: z.string().nullable().optional(),
   minutes_remaining: z.number().nullable().optional(),
@@ -127,8 +213,9 @@ export const SettingsSchema = z.object({
   selected_microphone: z.string().nullable().optional(),
   clamshell_microphone: z.string().nullable().optional(),
   selected_output_device: z.string().nullable().optional(),
-  translate_to_english: z.boolean(),
-  selected_language: z.string(),
+  translation_target_language: z.string().optional().default(""),
+  translation_keep_source_text: z.boolean().optional().default(false),
+  allowed_languages: z.array(z.string()).optional().default([]),
   overlay_position: OverlayPositionSchema,
   debug_mode: z.boolean(),
   log_level: LogLevelSchema.optional().default(2),
@@ -150,7 +237,30 @@ export const SettingsSchema = z.object({
   post_process_models: z.record(z.string()).optional().default({}),
   post_process_prompts: z.array(LLMPromptSchema).optional().default([]),
   post_process_selected_prompt_id: z.string().nullable().optional(),
+  edit_selection_prompt: z.string().optional().default(""),
   mute_while_recording: z.boolean().optional().default(false),
+  max_concurrent_downloads: z.number().optional().default(2),
+  download_bandwidth_limit_kbps: z.number().optional().default(0),
+  post_process_pipeline: z.array(PipelineStepSchema).optional().default([]),
+  replacement_rules: z.array(ReplacementRuleSchema).optional().default([]),
+  app_profiles: z.array(AppProfileSchema).optional().default([]),
+  min_speech_ratio: z.number().optional().default(0.05),
+  max_parallel_transcriptions: z.number().optional().default(1),
+  preload_model_on_startup: z.boolean().optional().default(true),
+  preload_model_on_speech: z.boolean().optional().default(true),
+  warm_up_model: z.boolean().optional().default(true),
+  low_memory_unload_threshold_mb: z.number().optional().default(0),
+  transcription_fallbacks: z
+    .array(FallbackBackendSchema)
+    .optional()
+    .default([]),
+  remote_transcription_timeout_secs: z.number().optional().default(30),
+  remote_audio_format: RemoteAudioFormatSchema.optional().default("auto"),
+  usage_prices: z.array(UsagePriceSchema).optional().default([]),
+  monthly_budget_usd: z.number().optional().default(0),
+  diarization_enabled: z.boolean().optional().default(false),
+  diarization_max_speakers: z.number().optional().default(4),
+  speaker_labels: z.boolean().optional().default(true),
 });
 
 export const BindingResponseSchema = z.object({
@@ -167,6 +277,19 @@ export type Settings = z.infer<typeof SettingsSchema>;
 export type UsageModeValue = z.infer<typeof UsageModeSchema>;
 export type ProviderValue = z.infer<typeof TranscriptionProviderSchema>;
 
+export const ModelBenchmarkSchema = z.object({
+  load_time_ms: z.number(),
+  real_time_factor: z.number(),
+  peak_memory_mb: z.number().nullable(),
+  word_error_rate: z.number(),
+  character_error_rate: z.number(),
+  clip_count: z.number(),
+  audio_seconds: z.number(),
+  benchmarked_at: z.number(),
+});
+
+export type ModelBenchmark = z.infer<typeof ModelBenchmarkSchema>;
+
 export const ModelInfoSchema = z.object({
   id: z.string(),
   name: z.string(),
@@ -180,10 +303,66 @@ export const ModelInfoSchema = z.object({
   is_directory: z.boolean(),
   accuracy_score: z.number(),
   speed_score: z.number(),
+  is_custom: z.boolean().optional().default(false),
+  local_path: z.string().nullable().optional(),
+  sha256: z.string().nullable().optional(),
+  size_bytes: z.number().nullable().optional(),
+  mirrors: z.array(z.string()).optional().default([]),
+  benchmark: ModelBenchmarkSchema.nullable().optional(),
 });
 
 export type ModelInfo = z.infer<typeof ModelInfoSchema>;
 
+export const IntegrityStatusSchema = z.enum([
+  "verified",
+  "unverified",
+  "incomplete",
+  "corrupted",
+]);
+
+export const ModelVerificationSchema = z.object({
+  model_id: z.string(),
+  status: IntegrityStatusSchema,
+  detail: z.string().nullable().optional(),
+  can_redownload: z.boolean(),
+});
+
+export const DownloadStateSchema = z.enum(["queued", "active", "paused"]);
+export const DownloadPrioritySchema = z.enum(["low", "normal", "high"]);
+
+export const DownloadProgressSchema = z.object({
+  model_id: z.string(),
+  downloaded: z.number(),
+  total: z.number(),
+  percentage: z.number(),
+  state: DownloadStateSchema,
+  priority: DownloadPrioritySchema,
+  queue_position: z.number().nullable(),
+  bytes_per_sec: z.number(),
+  eta_seconds: z.number().nullable(),
+});
+
+export const DownloadQueueProgressSchema = z.object({
+  downloads: z.array(DownloadProgressSchema),
+  downloaded: z.number(),
+  total: z.number(),
+  bytes_per_sec: z.number(),
+  eta_seconds: z.number().nullable(),
+});
+
+export type DownloadState = z.infer<typeof DownloadStateSchema>;
+export type DownloadPriority = z.infer<typeof DownloadPrioritySchema>;
+export type DownloadProgress = z.infer<typeof DownloadProgressSchema>;
+export type DownloadQueueProgress = z.infer<typeof DownloadQueueProgressSchema>;
+export interface BenchmarkOutcome {
+  model_id: string;
+  benchmark: ModelBenchmark | null;
+  error: string | null;
+}
+
+export type IntegrityStatus = z.infer<typeof IntegrityStatusSchema>;
+export type ModelVerification = z.infer<typeof ModelVerificationSchema>;
+
 export interface HistoryEntry {
   id: number;
   file_name: string;
@@ -193,4 +372,65 @@ export interface HistoryEntry {
   transcription_text: string;
   post_processed_text?: string | null;
   post_process_prompt?: string | null;
+  backend?: string | null;
+  speaker_segments?: SpeakerSegment[] | null;
+  translation?: string | null;
+  translation_language?: string | null;
+  pipeline_steps?: PipelineStepOutput[] | null;
+  edited_selection?: string | null;
+}
+
+export interface PipelineStepOutput {
+  step: PipelineStepKind;
+  text: string;
+}
+
+export interface SpeakerSegment {
+  speaker: number;
+  start: number;
+  end: number;
+  text: string;
+}
+
+export interface AccountStatus {
+  signed_in: boolean;
+  user_id: string | null;
+  email: string | null;
+  minutes_remaining: number | null;
+}
+
+export interface DeviceLogin {
+  device_code: string;
+  user_code: string;
+  verification_uri: string;
+  verification_uri_complete?: string | null;
+  expires_in: number;
+  interval: number;
+}
+
+export type UsageKind = "transcription" | "post_process";
+
+export interface DailyUsage {
+  day: string;
+  kind: UsageKind;
+  provider: string;
+  model: string;
+  direct: boolean;
+  requests: number;
+  audio_seconds: number;
+  prompt_tokens: number;
+  completion_tokens: number;
+  estimated_cost: number | null;
+}
+
+export interface UsageSummary {
+  month_cost: number;
+  monthly_budget: number;
+  unpriced_requests: number;
+}
+
+export interface BudgetWarningEvent {
+  level: "approaching" | "exceeded";
+  month_cost: number;
+  monthly_budget: number;
 }
@@ -6,14 +6,38 @@ import "./RecordingOverlay.css";
 
 type OverlayState = "recording" | "transcribing" | "error";
 
+type ErrorKind = "timeout" | "auth" | "quota" | "network" | "other";
+
+interface TranscriptionError {
+  kind: ErrorKind;
+  message: string;
+}
+
+// Short enough for the overlay; the full message is in the tooltip
+const errorHeadlines: Record<ErrorKind, string | null> = {
+  timeout: "Request timed out",
+  auth: "Check your API key",
+  quota: "Quota or rate limit reached",
+  network: "No connection",
+  other: null,
+};
+
+interface JobStatusEvent {
+  job_id: number;
+  status: string;
+  pending: number;
+  error: string | null;
+}
+
 const RecordingOverlay: React.FC = () => {
   const [isVisible, setIsVisible] = useState(false);
   const [state, setState] = useState<OverlayState>("recording");
   const [levels, setLevels] = useState<number[]>(Array(12).fill(0));
   const smoothedLevelsRef = useRef<number[]>(Array(12).fill(0));
-  const [errorMessage, setErrorMessage] = useState<string | null>(null);
+  const [error, setError] = useState<TranscriptionError | null>(null);
   const errorTimeoutRef = useRef<NodeJS.Timeout | null>(null);
   const [idleTime, setIdleTime] = useState(0);
+  const [pendingJobs, setPendingJobs] = useState(0);
   const idleAnimationRef = useRef<number | null>(null);
 
   // Animation timer for idle shimmer and processing states
@@ -62,13 +86,13 @@ const RecordingOverlay: React.FC = () => {
         const overlayState = event.payload as OverlayState;
         setState(overlayState);
         setIsVisible(true);
-        setErrorMessage(null);
+        setError(null);
       });
 
       // Listen for hide-overlay event from Rust
       const unlistenHide = await listen("hide-overlay", () => {
         setIsVisible(false);
-        setErrorMessage(null);
+        setError(null);
       });
 
       // Listen for mic-level updates
@@ -88,11 +112,10 @@ const RecordingOverlay: React.FC = () => {
       });
 
       // Listen for transcription errors to show inline toast
-      const unlistenError = await listen<string>(
+      const unlistenError = await listen<TranscriptionError>(
         "transcription-error",
         (event) => {
-          const message = event.payload;
-          setErrorMessage(message);
+          setError(event.payload);
           setState("error");
           setIsVisible(true);
 
@@ -101,17 +124,26 @@ const RecordingOverlay: React.FC = () => {
           }
           errorTimeoutRef.current = setTimeout(() => {
             setIsVisible(false);
-            setErrorMessage(null);
+            setError(null);
           }, 3200);
         },
       );
 
+      // Track how many recordings are still waiting to be pasted
+      const unlistenJobs = await listen<JobStatusEvent>(
+        "transcription-job-status",
+        (event) => {
+          setPendingJobs(event.payload.pending);
+        },
+      );
+
       // Cleanup function
       return () => {
         unlistenShow();
         unlistenHide();
         unlistenLevel();
         unlistenError();
+        unlistenJobs();
         if (errorTimeoutRef.current) {
           clearTimeout(errorTimeoutRef.current);
         }
@@ -137,7 +169,7 @@ const RecordingOverlay: React.FC = () => {
       </div>
 
       <div className="overlay-middle">
-        {state === "recording" && !errorMessage && (
+        {state === "recording" && !error && (
           <div className="waveform-shell">
             <div className="waveform-bars">
               {mirroredLevels.map((value, index) => {
@@ -179,7 +211,7 @@ const RecordingOverlay: React.FC = () => {
             </div>
           </div>
         )}
-        {state === "transcribing" && !errorMessage && (
+        {state === "transcribing" && !error && (
           <div className="waveform-shell">
             <div className="waveform-bars">
               {Array(24)
@@ -216,14 +248,21 @@ const RecordingOverlay: React.FC = () => {
             </div>
           </div>
         )}
-        {state === "error" && errorMessage && (
-          <div className="error-text" title={errorMessage}>
-            {errorMessage}
+        {state === "error" && error && (
+          <div className="error-text" title={error.message}>
+            {errorHeadlines[error.kind] ?? error.message}
           </div>
         )}
       </div>
 
       <div className="overlay-right">
+        {/* While recording, every pending job is an earlier recording; while
+            transcribing, one of them is the job in progress */}
+        {!error &&
+          ((state === "recording" && pendingJobs > 0) ||
+            (state === "transcribing" && pendingJobs > 1)) && (
+            <div className="pending-badge">{pendingJobs} pending</div>
+          )}
         {state === "recording" && (
           <div
             className="stop-button"
@@ -1,7 +1,7 @@
 import { create } from "zustand";
 import { subscribeWithSelector } from "zustand/middleware";
 import { invoke } from "@tauri-apps/api/core";
-import { Settings, AudioDevice } from "../lib/types";
+import { Settings, AudioDevice, ActionProfile } from "../lib/types";
 
 interface SettingsStore {
   settings: Settings | null;
@@ -24,11 +24,15 @@ interface SettingsStore {
   refreshOutputDevices: () => Promise<void>;
   updateBinding: (id: string, binding: string) => Promise<void>;
   resetBinding: (id: string) => Promise<void>;
+  updateBindingProfile: (id: string, profile: ActionProfile) => Promise<void>;
+  addBinding: (name: string) => Promise<void>;
+  removeBinding: (id: string) => Promise<void>;
   getSetting: <K extends keyof Settings>(key: K) => Settings[K] | undefined;
   isUpdatingKey: (key: string) => boolean;
   playTestSound: (soundType: "start" | "stop") => Promise<void>;
   checkCustomSounds: () => Promise<void>;
   validateDeepgramKey: (apiKey: string) => Promise<void>;
+  saveOpenAiKey: (apiKey: string) => Promise<void>;
   setPostProcessProvider: (providerId: string) => Promise<void>;
   updatePostProcessSetting: (
     settingType: "base_url" | "api_key" | "model",
@@ -65,11 +69,21 @@ const DEFAULT_SETTINGS: Partial<Settings> = {
   deepgram_api_key: "",
   openai_api_key_preview: "",
   openai_api_key: "",
+  openai_transcription_base_url: "https://api.openai.com/v1",
+  openai_transcription_model: "whisper-1",
+  openai_transcription_language: null,
+  openai_transcription_prompt: "",
+  openai_transcription_response_format: "json",
   auth_token: null,
   user_id: null,
   minutes_remaining: null,
   usage_mode: "own_keys",
   deepgram_model: "nova-3",
+  deepgram_smart_format: true,
+  deepgram_punctuate: true,
+  deepgram_numerals: false,
+  deepgram_filler_words: false,
+  deepgram_streaming: false,
   use_secure_key_storage: true,
   always_on_microphone: false,
   audio_feedback: true,
@@ -81,8 +95,9 @@ const DEFAULT_SETTINGS: Partial<Settings> = {
   selected_microphone: "Default",
   clamshell_microphone: "Default",
   selected_output_device: "Default",
-  translate_to_english: false,
-  selected_language: "auto",
+  translation_target_language: "",
+  translation_keep_source_text: false,
+  allowed_languages: [],
   overlay_position: "bottom",
   debug_mode: false,
   log_level: 2,
@@ -90,6 +105,33 @@ const DEFAULT_SETTINGS: Partial<Settings> = {
   history_limit: 5,
   recording_retention_period: "preserve_limit",
   mute_while_recording: false,
+  max_concurrent_downloads: 2,
+  download_bandwidth_limit_kbps: 0,
+  post_process_pipeline: [
+    { kind: "hallucination_filter", enabled: true },
+    { kind: "custom_words", enabled: true },
+    { kind: "replacements", enabled: true },
+    { kind: "chinese_conversion", enabled: true },
+    { kind: "translation", enabled: true },
+    { kind: "llm_prompt", enabled: false },
+    { kind: "formatting", enabled: false },
+  ],
+  replacement_rules: [],
+  app_profiles: [],
+  min_speech_ratio: 0.05,
+  max_parallel_transcriptions: 1,
+  preload_model_on_startup: true,
+  preload_model_on_speech: true,
+  warm_up_model: true,
+  low_memory_unload_threshold_mb: 0,
+  transcription_fallbacks: [],
+  remote_transcription_timeout_secs: 30,
+  remote_audio_format: "auto",
+  usage_prices: [],
+  monthly_budget_usd: 0,
+  diarization_enabled: false,
+  diarization_max_speakers: 4,
+  speaker_labels: true,
 };
 
 const DEFAULT_AUDIO_DEVICE: AudioDevice = {
@@ -130,6 +172,26 @@ const settingUpdaters: {
     invoke("change_transcription_provider", { provider: value }),
   deepgram_model: (value) =>
     invoke("change_deepgram_model", { model: value }),
+  deepgram_smart_format: (value) =>
+    invoke("change_deepgram_smart_format_setting", { enabled: value }),
+  deepgram_punctuate: (value) =>
+    invoke("change_deepgram_punctuate_setting", { enabled: value }),
+  deepgram_numerals: (value) =>
+    invoke("change_deepgram_numerals_setting", { enabled: value }),
+  deepgram_filler_words: (value) =>
+    invoke("change_deepgram_filler_words_setting", { enabled: value }),
+  deepgram_streaming: (value) =>
+    invoke("change_deepgram_streaming_setting", { enabled: value }),
+  openai_transcription_base_url: (value) =>
+    invoke("change_openai_transcription_base_url", { baseUrl: value }),
+  openai_transcription_model: (value) =>
+    invoke("change_openai_transcription_model", { model: value }),
+  openai_transcription_language: (value) =>
+    invoke("change_openai_transcription_language", { language: value }),
+  openai_transcription_prompt: (value) =>
+    invoke("change_openai_transcription_prompt", { prompt: value }),
+  openai_transcription_response_format: (value) =>
+    invoke("change_openai_transcription_response_format", { format: value }),
   use_secure_key_storage: (value) =>
     invoke("set_secure_key_storage", { enabled: value }),
   usage_mode: (value) => invoke("set_usage_mode", { mode: value }),
@@ -137,10 +199,14 @@ const settingUpdaters: {
     invoke("set_api_base_url", { apiBaseUrl: value ?? "" }),
   recording_retention_period: (value) =>
     invoke("update_recording_retention_period", { period: value }),
-  translate_to_english: (value) =>
-    invoke("change_translate_to_english_setting", { enabled: value }),
-  selected_language: (value) =>
-    invoke("change_selected_language_setting", { language: value }),
+  translation_target_language: (value) =>
+    invoke("change_translation_target_language_setting", {
+      language: value,
+    }),
+  translation_keep_source_text: (value) =>
+    invoke("change_translation_keep_source_text_setting", { enabled: value }),
+  allowed_languages: (value) =>
+    invoke("change_allowed_languages_setting", { languages: value }),
   overlay_position: (value) =>
     invoke("change_overlay_position_setting", { position: value }),
   debug_mode: (value) =>
@@ -153,12 +219,60 @@ const settingUpdaters: {
   clipboard_handling: (value) =>
     invoke("change_clipboard_handling_setting", { handling: value }),
   history_limit: (value) => invoke("update_history_limit", { limit: value }),
-  post_process_enabled: (value) =>
-    invoke("change_post_process_enabled_setting", { enabled: value }),
+  post_process_enabled: async (value) => {
+    await invoke("change_post_process_enabled_setting", { enabled: value });
+    // The pipeline's LLM step follows this toggle
+    await useSettingsStore.getState().refreshSettings();
+  },
   post_process_selected_prompt_id: (value) =>
     invoke("set_post_process_selected_prompt", { id: value }),
+  edit_selection_prompt: (value) =>
+    invoke("change_edit_selection_prompt_setting", { prompt: value }),
   mute_while_recording: (value) =>
     invoke("change_mute_while_recording_setting", { enabled: value }),
+  max_concurrent_downloads: (value) =>
+    invoke("change_max_concurrent_downloads_setting", { count: value }),
+  download_bandwidth_limit_kbps: (value) =>
+    invoke("change_download_bandwidth_limit_setting", { kbps: value }),
+  post_process_pipeline: async (value) => {
+    await invoke("change_post_process_pipeline_setting", { steps: value });
+    // Enabling the LLM step also turns on post_process_enabled
+    await useSettingsStore.getState().refreshSettings();
+  },
+  replacement_rules: (value) =>
+    invoke("change_replacement_rules_setting", { rules: value }),
+  app_profiles: (value) =>
+    invoke("change_app_profiles_setting", { profiles: value }),
+  min_speech_ratio: (value) =>
+    invoke("change_min_speech_ratio_setting", { ratio: value }),
+  max_parallel_transcriptions: (value) =>
+    invoke("change_max_parallel_transcriptions_setting", { count: value }),
+  preload_model_on_startup: (value) =>
+    invoke("change_preload_model_on_startup_setting", { enabled: value }),
+  preload_model_on_speech: (value) =>
+    invoke("change_preload_model_on_speech_setting", { enabled: value }),
+  warm_up_model: (value) =>
+    invoke("change_warm_up_model_setting", { enabled: value }),
+  low_memory_unload_threshold_mb: (value) =>
+    invoke("change_low_memory_unload_threshold_setting", {
+      thresholdMb: value,
+    }),
+  transcription_fallbacks: (value) =>
+    invoke("change_transcription_fallbacks_setting", { fallbacks: value }),
+  remote_transcription_timeout_secs: (value) =>
+    invoke("change_remote_transcription_timeout_setting", { seconds: value }),
+  remote_audio_format: (value) =>
+    invoke("change_remote_audio_format_setting", { format: value }),
+  usage_prices: (value) =>
+    invoke("change_usage_prices_setting", { prices: value }),
+  monthly_budget_usd: (value) =>
+    invoke("change_monthly_budget_setting", { budget: value }),
+  diarization_enabled: (value) =>
+    invoke("change_diarization_enabled_setting", { enabled: value }),
+  diarization_max_speakers: (value) =>
+    invoke("change_diarization_max_speakers_setting", { count: value }),
+  speaker_labels: (value) =>
+    invoke("change_speaker_labels_setting", { enabled: value }),
   log_level: (value) => invoke("set_log_level", { level: value }),
 };
 
@@ -198,6 +312,22 @@ export const useSettingsStore = create<SettingsStore>()(
       }
     },
 
+    saveOpenAiKey: async (apiKey) => {
+      const { refreshSettings, setUpdating } = get();
+      const updateKey = "openai_api_key_preview";
+
+      setUpdating(updateKey, true);
+      try {
+        await invoke("store_openai_api_key", { apiKey });
+        await refreshSettings();
+      } catch (error) {
+        console.error("Failed to save OpenAI key:", error);
+        throw error;
+      } finally {
+        setUpdating(updateKey, false);
+      }
+    },
+
     // Getters
     getSetting: (key) => get().settings?.[key],
     isUpdatingKey: (key) => get().isUpdating[key] || false,
@@ -421,6 +551,54 @@ export const useSettingsStore = create<SettingsStore>()(
       }
     },
 
+    // Replace the action profile of one binding
+    updateBindingProfile: async (id, profile) => {
+      const { setUpdating, refreshSettings } = get();
+      const updateKey = `binding_profile_${id}`;
+
+      setUpdating(updateKey, true);
+
+      try {
+        await invoke("change_binding_profile", { id, profile });
+        await refreshSettings();
+      } catch (error) {
+        console.error(`Failed to update profile for ${id}:`, error);
+      } finally {
+        setUpdating(updateKey, false);
+      }
+    },
+
+    addBinding: async (name) => {
+      const { setUpdating, refreshSettings } = get();
+
+      setUpdating("add_binding", true);
+
+      try {
+        await invoke("add_binding", { name });
+        await refreshSettings();
+      } catch (error) {
+        console.error("Failed to add binding:", error);
+      } finally {
+        setUpdating("add_binding", false);
+      }
+    },
+
+    removeBinding: async (id) => {
+      const { setUpdating, refreshSettings } = get();
+      const updateKey = `binding_${id}`;
+
+      setUpdating(updateKey, true);
+
+      try {
+        await invoke("remove_binding", { id });
+        await refreshSettings();
+      } catch (error) {
+        console.error(`Failed to remove binding ${id}:`, error);
+      } finally {
+        setUpdating(updateKey, false);
+      }
+    },
+
     setPostProcessProvider: async (providerId) => {
       const { settings, setUpdating, refreshSettings } = get();
       const updateKey = "post_process_provider_id";